@@ -1,34 +1,56 @@
 
-/// (std::ops method, method, assign_method, error_type, op)
+/// (std::ops method, method, assign_method, broadcast method, broadcast assign_method)
 macro_rules! impl_buffer_sigop_and_assign {
-    ($atomic_method:ident, $method:ident, $assign_method:ident, $error_variant:ident, $op:expr) => {
+    ($atomic_method:ident, $method:ident, $assign_method:ident, $method_with:ident, $assign_method_with:ident) => {
         // &Buffer + &[T]
-        fn $method<U: AsRef<[Self::Sample]>>(&self, rhs: U) -> Result<Self, SignalOpsError> {
+        fn $method<U: AsRef<[Self::Sample]>>(&self, rhs: U) -> Result<Self, SigOpsError> {
             let rhs = rhs.as_ref();
-            if (self.len() != rhs.len()) {
-                return Err(SignalOpsError::$error_variant(self.len(), rhs.len()));
+            if self.len() != rhs.len() {
+                return Err(SigOpsError::BufferMismatch(self.len(), rhs.len()));
             }
             let data = self
                 .data
                 .iter()
                 .copied()
                 .zip(rhs.iter().copied())
-                .map($op)
+                .map(|(x, y)| x.$atomic_method(y))
                 .collect();
             Ok(Self { data })
         }
         
         // &mut Buffer += &[T]
-        fn $assign_method<U: AsRef<[Self::Sample]>>(&mut self, rhs: U) -> Result<(), SignalOpsError> {
+        fn $assign_method<U: AsRef<[Self::Sample]>>(&mut self, rhs: U) -> Result<(), SigOpsError> {
             let rhs = rhs.as_ref();
-            if (self.len() != rhs.len()) {
-                return Err(SignalOpsError::$error_variant(self.len(), rhs.len()));
+            if self.len() != rhs.len() {
+                return Err(SigOpsError::BufferMismatch(self.len(), rhs.len()));
             }
             for (a, b) in self.data.iter_mut().zip(rhs.iter().copied()) {
                 *a = (*a).$atomic_method(b);
             }
             Ok(())
         }
+        
+        // &Buffer + &[T] conformed by mode
+        fn $method_with<U: AsRef<[Self::Sample]>>(&self, rhs: U, mode: SigBroadcast<Self::Sample>) -> Result<Self, SigOpsError> {
+            let rhs = mode.conform(rhs.as_ref(), self.len())?;
+            let data = self
+                .data
+                .iter()
+                .copied()
+                .zip(rhs.iter().copied())
+                .map(|(x, y)| x.$atomic_method(y))
+                .collect();
+            Ok(Self { data })
+        }
+        
+        // &mut Buffer += &[T] conformed by mode
+        fn $assign_method_with<U: AsRef<[Self::Sample]>>(&mut self, rhs: U, mode: SigBroadcast<Self::Sample>) -> Result<(), SigOpsError> {
+            let rhs = mode.conform(rhs.as_ref(), self.len())?;
+            for (a, b) in self.data.iter_mut().zip(rhs.iter().copied()) {
+                *a = (*a).$atomic_method(b);
+            }
+            Ok(())
+        }
     };
 }
 
@@ -127,13 +149,105 @@ macro_rules! impl_buffer_binop_and_assign_scalar {
     };
 }
 
+/// (trait, method, assign_trait, assign_method, checked_method, sig method)
+/// - mismatched lengths panic, use `Broadcast` or the checked method otherwise
+macro_rules! impl_buffer_binop_and_assign {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $checked_method:ident, $sig_method:ident) => {
+        impl<T: Unit> Buffer<T> {
+            /// Result returning operator, same as the `SignalOps` method
+            pub fn $checked_method<U: AsRef<[T]>>(&self, rhs: U) -> Result<Self, SigOpsError> {
+                self.$sig_method(rhs)
+            }
+        }
+        
+        // Buffer += &[T]
+        impl<'a, T> $assign_trait<&'a [T]> for Buffer<T>
+        where
+            T: Unit
+        {
+            fn $assign_method(&mut self, rhs: &'a [T]) {
+                if self.len() != rhs.len() {
+                    panic!("{}", SigOpsError::BufferMismatch(self.len(), rhs.len()));
+                }
+                for (a, b) in self.data.iter_mut().zip(rhs.iter().copied()) {
+                    *a = (*a).$method(b);
+                }
+            }
+        }
+        
+        // Buffer += &Buffer
+        impl<'a, T> $assign_trait<&'a Buffer<T>> for Buffer<T>
+        where
+            T: Unit
+        {
+            fn $assign_method(&mut self, rhs: &'a Buffer<T>) {
+                <Self as $assign_trait<&[T]>>::$assign_method(self, &rhs.data);
+            }
+        }
+        
+        // Buffer += Buffer
+        impl<T> $assign_trait<Buffer<T>> for Buffer<T>
+        where
+            T: Unit
+        {
+            fn $assign_method(&mut self, rhs: Buffer<T>) {
+                <Self as $assign_trait<&[T]>>::$assign_method(self, &rhs.data);
+            }
+        }
+        
+        // Buffer += Broadcast
+        impl<'a, T> $assign_trait<Broadcast<'a, T>> for Buffer<T>
+        where
+            T: Unit
+        {
+            fn $assign_method(&mut self, rhs: Broadcast<'a, T>) {
+                let conformed = rhs.mode.conform(rhs.rhs, self.len()).unwrap_or_else(|err| panic!("{err}"));
+                <Self as $assign_trait<&[T]>>::$assign_method(self, &conformed);
+            }
+        }
+        
+        impl_buffer_binop_from_assign!($trait, $method, $assign_trait, $assign_method, &'a [T]);
+        impl_buffer_binop_from_assign!($trait, $method, $assign_trait, $assign_method, &'a Buffer<T>);
+        impl_buffer_binop_from_assign!($trait, $method, $assign_trait, $assign_method, Buffer<T>);
+        impl_buffer_binop_from_assign!($trait, $method, $assign_trait, $assign_method, Broadcast<'a, T>);
+    };
+}
+
+/// (trait, method, assign_trait, assign_method, rhs type)
+macro_rules! impl_buffer_binop_from_assign {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $Rhs:ty) => {
+        // Buffer + rhs
+        impl<'a, T> $trait<$Rhs> for Buffer<T>
+        where
+            T: Unit
+        {
+            type Output = Buffer<T>;
+            fn $method(mut self, rhs: $Rhs) -> Self::Output {
+                <Self as $assign_trait<$Rhs>>::$assign_method(&mut self, rhs);
+                self
+            }
+        }
+        
+        // &Buffer + rhs
+        impl<'a, 'b, T> $trait<$Rhs> for &'b Buffer<T>
+        where
+            T: Unit
+        {
+            type Output = Buffer<T>;
+            fn $method(self, rhs: $Rhs) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+    };
+}
+
 /// (trait, method, op)
 macro_rules! impl_buffer_unop {
     ($trait:ident, $method:ident) => {
         // Buffer
         impl<T> $trait for Buffer<T>
         where
-            T: Unit,
+            T: Unit + $trait<Output = T>,
         {
             type Output = Buffer<T>;
             fn $method(self) -> Self::Output {
@@ -149,7 +263,7 @@ macro_rules! impl_buffer_unop {
         // &Buffer
         impl<'a, T> $trait for &'a Buffer<T>
         where
-            T: Unit,
+            T: Unit + $trait<Output = T>,
         {
             type Output = Buffer<T>;
             fn $method(self) -> Self::Output {
@@ -1,92 +1,405 @@
-// /// 1-D Buffer Signal
-// use std::vec::IntoIter;
-// use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
-// use super::*;
-// 
-// #[macro_use]
-// mod binop;
-// 
-// #[repr(transparent)]
-// #[derive(Debug, Clone, Hash, Ord, PartialOrd, Eq, PartialEq, Default)]
-// pub struct Buffer<T: Unit> {
-//     data: Vec<T>
-// }
-// 
-// // super trait implementations ==========================================
-// 
-// impl<T: Unit> IntoIterator for Buffer<T> {
-//     type Item = T;
-//     type IntoIter = IntoIter<T>;
-//     fn into_iter(self) -> Self::IntoIter {
-//         self.data.into_iter()
-//     }
-// }
-// 
-// impl<T: Unit> AsRef<[T]> for Buffer<T> {
-//     fn as_ref(&self) -> &[T] { self.data.as_slice() }
-// }
-// 
-// impl<T: Unit> AsMut<[T]> for Buffer<T> {
-//     fn as_mut(&mut self) -> &mut [T] { self.data.as_mut_slice() }
-// }
-// 
-// impl<T: Unit> Deref for Buffer<T> {
-//     type Target = [T];
-//     fn deref(&self) -> &Self::Target { self.data.as_slice() }
-// }
-// 
-// impl<T: Unit> DerefMut for Buffer<T> {
-//     fn deref_mut(&mut self) -> &mut Self::Target { self.data.as_mut_slice() }
-// }
-// 
-// // std::ops and SignalOps Implementations ===========================================
-// 
-// impl<T: Unit> SignalOps for Buffer<T> {
-//     impl_buffer_sigop_and_assign!(add, sig_add, sig_add_assign, BufferMismatch, |(x, y)| x + y);
-//     impl_buffer_sigop_and_assign!(sub, sig_sub, sig_sub_assign, BufferMismatch, |(x, y)| x - y);
-//     impl_buffer_sigop_and_assign!(mul, sig_mul, sig_mul_assign, BufferMismatch, |(x, y)| x * y);
-//     impl_buffer_sigop_and_assign!(div, sig_div, sig_div_assign, BufferMismatch, |(x, y)| x / y);
-// }
-// impl_buffer_binop_and_assign_scalar!(Add, add, AddAssign, add_assign);
-// impl_buffer_binop_and_assign_scalar!(Sub, sub, SubAssign, sub_assign);
-// impl_buffer_binop_and_assign_scalar!(Mul, mul, MulAssign, mul_assign);
-// impl_buffer_binop_and_assign_scalar!(Div, div, DivAssign, div_assign);
-// impl_buffer_unop!(Neg, neg);
-// 
-// // std::io Implementations ============================================
-// 
-// // Signal Trait Implementations =======================================
-// 
-// impl<T: Unit> Signal for Buffer<T> {
-//     type Sample = T;
-// }
-// 
-// impl<T: Unit> SignalMut for Buffer<T> {
-// }
-// 
-// impl<T: Unit> SignalOwned for Buffer<T> {
-//     type Container = Vec<T>;
-//     fn as_container(&self) -> &Self::Container {
-//         &self.data
-//     }
-//     fn as_container_mut(&mut self) -> &mut Self::Container {
-//         &mut self.data
-//     }
-//     fn into_container(self) -> Self::Container {
-//         self.data
-//     }
-// }
-// 
-// impl<T: Unit> SignalResizable for Buffer<T> {
-//     fn resize(&mut self, new_len: usize, fill_value: Self::Sample) {
-//         todo!()
-//     }
-//     fn clear(&mut self) {
-//         todo!()
-//     }
-//     fn append(&mut self, value: Self::Sample) {
-//         todo!()
-//     }
-// }
-// 
-// // impl<T: Unit> SignalStream for Buffer<T> {}
\ No newline at end of file
+/// 1-D Buffer Signal
+use std::vec::IntoIter;
+use super::*;
+
+#[macro_use]
+mod binop;
+
+#[repr(transparent)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Default)]
+pub struct Buffer<T: Unit> {
+    data: Vec<T>
+}
+
+// super trait implementations ==========================================
+
+impl<T: Unit> IntoIterator for Buffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<T: Unit> FromIterator<T> for Buffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Buffer { data: iter.into_iter().collect() }
+    }
+}
+
+impl<T: Unit> From<Vec<T>> for Buffer<T> {
+    fn from(data: Vec<T>) -> Self { Buffer { data } }
+}
+
+impl<T: Unit> From<&[T]> for Buffer<T> {
+    fn from(data: &[T]) -> Self { Buffer { data: data.to_vec() } }
+}
+
+impl<T: Unit> AsRef<[T]> for Buffer<T> {
+    fn as_ref(&self) -> &[T] { self.data.as_slice() }
+}
+
+impl<T: Unit> AsMut<[T]> for Buffer<T> {
+    fn as_mut(&mut self) -> &mut [T] { self.data.as_mut_slice() }
+}
+
+impl<T: Unit> Deref for Buffer<T> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target { self.data.as_slice() }
+}
+
+impl<T: Unit> DerefMut for Buffer<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target { self.data.as_mut_slice() }
+}
+
+// std::ops and SignalOps Implementations ===========================================
+
+impl<T: Unit> SignalOps for Buffer<T> {
+    impl_buffer_sigop_and_assign!(add, sig_add, sig_add_assign, sig_add_with, sig_add_assign_with);
+    impl_buffer_sigop_and_assign!(sub, sig_sub, sig_sub_assign, sig_sub_with, sig_sub_assign_with);
+    impl_buffer_sigop_and_assign!(mul, sig_mul, sig_mul_assign, sig_mul_with, sig_mul_assign_with);
+    impl_buffer_sigop_and_assign!(div, sig_div, sig_div_assign, sig_div_with, sig_div_assign_with);
+
+    fn project<U: AsRef<[T]>>(&mut self, onto: U) -> Result<(), SigOpsError> where T: Float {
+        let onto = onto.as_ref();
+        let scale = self.data.as_slice().inner(onto)?;
+        let norm = onto.energy();
+        if norm == T::_ZERO {
+            return Err(SigOpsError::ZeroNorm);
+        }
+        let scale = scale / norm;
+        for (x, &o) in self.data.iter_mut().zip(onto) {
+            *x = o * scale;
+        }
+        Ok(())
+    }
+
+    fn convolve<U: AsRef<[T]>>(&mut self, kernel: U) -> Result<(), SigOpsError> {
+        let kernel = kernel.as_ref();
+        if kernel.is_empty() {
+            return Err(SigOpsError::EmptyKernel);
+        }
+        // back to front, so every input sample is read before it is overwritten
+        for n in (0..self.data.len()).rev() {
+            let past = self.data[..=n].iter().rev();
+            self.data[n] = kernel.iter().zip(past).fold(T::_ZERO, |acc, (&h, &x)| acc + h * x);
+        }
+        Ok(())
+    }
+
+    fn resample(&mut self, factor: T) -> Result<(), SigOpsError> where T: Float {
+        let factor = factor
+            .to_f64()
+            .filter(|f| f.is_finite() && *f > 0.0)
+            .ok_or(SigOpsError::InvalidFactor)?;
+        let Some(last) = self.data.len().checked_sub(1) else {
+            return Ok(());
+        };
+        // a length past what a `Vec` can hold would saturate the cast or abort the allocation
+        let span = (last as f64 * factor).floor();
+        if span >= (isize::MAX as usize / size_of::<T>().max(1)) as f64 {
+            return Err(SigOpsError::InvalidFactor);
+        }
+        let x = &self.data;
+        let len = span as usize + 1;
+        self.data = (0..len)
+            .map(|i| {
+                let pos = i as f64 / factor;
+                let k = (pos as usize).min(last);
+                let frac = T::from_f64(pos - k as f64).unwrap_or(T::_ZERO);
+                if k == last { x[k] } else { x[k] + (x[k + 1] - x[k]) * frac }
+            })
+            .collect();
+        Ok(())
+    }
+
+    fn diff(&mut self, mode: SigDiff) {
+        let x = &mut self.data;
+        if x.len() < 2 {
+            x.fill(T::_ZERO);
+            return;
+        }
+        let last = x.len() - 1;
+        let two = T::_ONE + T::_ONE;
+        let mut prev = x[0];
+        for n in 0..=last {
+            let cur = x[n];
+            x[n] = match mode {
+                _ if n == 0 => x[1] - cur,
+                _ if n == last => cur - prev,
+                SigDiff::Forward => x[n + 1] - cur,
+                SigDiff::Backward => cur - prev,
+                SigDiff::Central => (x[n + 1] - prev) / two,
+            };
+            prev = cur;
+        }
+    }
+
+    fn cumsum(&mut self, carry: T) -> T {
+        self.data.iter_mut().fold(carry, |acc, x| {
+            *x = acc + *x;
+            *x
+        })
+    }
+}
+impl_buffer_binop_and_assign_scalar!(Add, add, AddAssign, add_assign);
+impl_buffer_binop_and_assign_scalar!(Sub, sub, SubAssign, sub_assign);
+impl_buffer_binop_and_assign_scalar!(Mul, mul, MulAssign, mul_assign);
+impl_buffer_binop_and_assign_scalar!(Div, div, DivAssign, div_assign);
+impl_buffer_binop_and_assign!(Add, add, AddAssign, add_assign, checked_add, sig_add);
+impl_buffer_binop_and_assign!(Sub, sub, SubAssign, sub_assign, checked_sub, sig_sub);
+impl_buffer_binop_and_assign!(Mul, mul, MulAssign, mul_assign, checked_mul, sig_mul);
+impl_buffer_binop_and_assign!(Div, div, DivAssign, div_assign, checked_div, sig_div);
+impl_buffer_unop!(Neg, neg);
+
+// std::io Implementations ============================================
+
+// Signal Trait Implementations =======================================
+
+impl<T: Unit> Signal for Buffer<T> {
+    type Sample = T;
+    type Container = Vec<T>;
+}
+
+impl<T: Unit> SignalMut for Buffer<T> {
+}
+
+impl<T: Float> ScalarOps for Buffer<T> {
+    type Sample = T;
+}
+
+impl<T: Unit> SignalOwned for Buffer<T> {
+    fn as_container(&self) -> &Self::Container {
+        &self.data
+    }
+    fn as_container_mut(&mut self) -> &mut Self::Container {
+        &mut self.data
+    }
+    fn into_container(self) -> Self::Container {
+        self.data
+    }
+}
+
+impl<T: Unit> SignalResizable for Buffer<T> {
+    fn resize(&mut self, new_len: usize, fill_value: Self::Sample) {
+        self.data.resize(new_len, fill_value);
+    }
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+    fn append(&mut self, value: Self::Sample) {
+        self.data.push(value);
+    }
+}
+
+// impl<T: Unit> SignalStream for Buffer<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_approx_eq;
+    use super::*;
+
+    #[test]
+    fn test_signal() {
+        let buf = Buffer::new(vec![3i16, -7, 12, -7, 12]);
+        assert_eq!((buf.len(), buf.is_empty()), (5, false));
+        assert_eq!((buf.min(), buf.max()), (Some(-7), Some(12)));
+        assert_eq!((buf.argmin(), buf.argmax()), (Some(1), Some(2)));
+        assert_eq!(Buffer::new_copy(&buf[1..3]).into_container(), vec![-7, 12]);
+        let floats: Buffer<f32> = [f32::NAN, 2.0, -1.0, f32::NAN, 5.0].into_iter().collect();
+        assert_eq!((floats.min(), floats.max()), (Some(-1.0), Some(5.0)));
+        assert_eq!((floats.argmin(), floats.argmax()), (Some(2), Some(4)));
+        let nans = Buffer::new(vec![f64::NAN; 2]);
+        assert_eq!(nans.argmax(), Some(0));
+        let empty = Buffer::<F16>::default();
+        assert_eq!((empty.min(), empty.argmin()), (None, None));
+    }
+
+    #[test]
+    fn test_resizable() {
+        let mut buf = Buffer::from(&[1.0f64, 2.0][..]);
+        buf[0] = -1.0;
+        buf.append(3.0);
+        assert_eq!(buf.as_ref(), [-1.0, 2.0, 3.0]);
+        buf.resize(5, 0.5);
+        assert_eq!(buf.as_container(), &vec![-1.0, 2.0, 3.0, 0.5, 0.5]);
+        buf.resize(2, 0.0);
+        buf.as_container_mut().push(4.0);
+        assert_eq!(buf.as_mut(), [-1.0, 2.0, 4.0]);
+        buf.clear();
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_sig_ops() {
+        let mut buf = Buffer::new(vec![1.0f32, 2.0, 3.0]);
+        assert_eq!(buf.sig_mul([2.0, 0.5, -1.0]).unwrap().as_ref(), [2.0, 1.0, -3.0]);
+        assert_eq!(buf.sig_add([1.0, 2.0]), Err(SigOpsError::BufferMismatch(3, 2)));
+        buf.sig_sub_assign([1.0; 3]).unwrap();
+        assert_eq!((-(&buf * 2.0) + 1.0).as_ref(), [1.0, -1.0, -3.0]);
+    }
+
+    #[test]
+    fn test_broadcast_scalar() {
+        let buf = Buffer::new(vec![1i32, 2, 3]);
+        assert_eq!(buf.sig_mul_with([10], SigBroadcast::Scalar).unwrap().as_ref(), [10, 20, 30]);
+        assert_eq!(buf.sig_mul_with([1, 2, 3], SigBroadcast::Scalar).unwrap().as_ref(), [1, 4, 9]);
+        assert_eq!(buf.sig_mul_with([1, 2], SigBroadcast::Scalar), Err(SigOpsError::BufferMismatch(3, 2)));
+        assert_eq!(buf.sig_mul_with([1, 2, 3, 4], SigBroadcast::Scalar), Err(SigOpsError::BufferMismatch(3, 4)));
+    }
+
+    #[test]
+    fn test_broadcast_zero_pad() {
+        let mut buf = Buffer::new(vec![1i32, 2, 3]);
+        buf.sig_add_assign_with([10], SigBroadcast::ZeroPad).unwrap();
+        assert_eq!(buf.as_ref(), [11, 2, 3]);
+        assert_eq!(buf.sig_mul_with([], SigBroadcast::ZeroPad).unwrap().as_ref(), [0, 0, 0]);
+        assert_eq!(buf.sig_sub_with([1, 1, 1, 1, 1], SigBroadcast::ZeroPad).unwrap().as_ref(), [10, 1, 2]);
+    }
+
+    #[test]
+    fn test_broadcast_fill() {
+        let buf = Buffer::new(vec![2.0f64, 4.0, 8.0, 16.0]);
+        assert_eq!(buf.sig_div_with([2.0], SigBroadcast::Fill(1.0)).unwrap().as_ref(), [1.0, 4.0, 8.0, 16.0]);
+        assert_eq!(buf.sig_div_with([], SigBroadcast::Fill(4.0)).unwrap().as_ref(), [0.5, 1.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_broadcast_latch() {
+        let mut buf = Buffer::new(vec![1i64; 4]);
+        buf.sig_add_assign_with([1, 5], SigBroadcast::Latch).unwrap();
+        assert_eq!(buf.as_ref(), [2, 6, 6, 6]);
+        assert_eq!(buf.sig_add_with([], SigBroadcast::Latch), Err(SigOpsError::EmptyBroadcast(4)));
+    }
+
+    #[test]
+    fn test_broadcast_tile() {
+        let buf = Buffer::new(vec![1u8; 5]);
+        assert_eq!(buf.sig_add_with([0, 1], SigBroadcast::Tile).unwrap().as_ref(), [1, 2, 1, 2, 1]);
+        assert_eq!(buf.sig_add_with([], SigBroadcast::Tile), Err(SigOpsError::EmptyBroadcast(5)));
+        let empty = Buffer::<u8>::default();
+        assert!(empty.sig_add_with([], SigBroadcast::Tile).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scalar_ops() {
+        let buf = Buffer::new(vec![3.0f64, -4.0, 0.0, 1.0]);
+        assert_eq!(buf.inner([1.0, 1.0, 1.0, 1.0]), Ok(0.0));
+        assert_eq!(buf.inner([1.0]), Err(SigOpsError::BufferMismatch(4, 1)));
+        assert_eq!((buf.norm_l1(), buf.norm_inf(), buf.peak(), buf.energy()), (8.0, 4.0, 4.0, 26.0));
+        assert_eq!((buf.mean(), buf.variance()), (0.0, 6.5));
+        assert_eq!(buf.rms(), buf.std_dev());
+        assert_eq!(buf[..2].norm_l2(), 5.0);
+        assert_approx_eq!([buf.crest_factor()], [4.0 / 6.5f64.sqrt()]);
+        assert_approx_eq!([[1.0f32, 0.0].angle([1.0, 1.0]).unwrap()], [std::f32::consts::FRAC_PI_4]);
+        assert_eq!([1.0f32, 1.0].angle([-2.0, -2.0]), Ok(std::f32::consts::PI));
+        assert_eq!(buf.angle([0.0; 4]), Err(SigOpsError::ZeroNorm));
+        assert!(Buffer::<f32>::default().mean().is_nan());
+    }
+
+    #[test]
+    fn test_compensated_sums() {
+        let tone: Buffer<f32> = (0..1 << 20).map(|i| 1.0 + (i % 7) as f32 * 0.1).collect();
+        let exact = tone.iter().map(|&x| x as f64).sum::<f64>() / tone.len() as f64;
+        let naive = tone.iter().sum::<f32>() / tone.len() as f32;
+        assert!((naive as f64 - exact).abs() > 1e-3);
+        assert!((tone.mean() as f64 - exact).abs() < 1e-6);
+        assert_approx_eq!([tone.variance() as f64], [0.04], rel = 1e-5, abs = 0.0, ulps = 0);
+    }
+
+    #[test]
+    fn test_project() {
+        let mut buf = Buffer::new(vec![2.0f64, 1.0]);
+        buf.project([3.0, 0.0]).unwrap();
+        assert_eq!(buf.as_ref(), [2.0, 0.0]);
+        let mut buf = Buffer::new(vec![1.0f32, 3.0]);
+        buf.project([1.0, 1.0]).unwrap();
+        assert_eq!(buf.as_ref(), [2.0, 2.0]);
+        assert_eq!(buf.project([0.0, 0.0]), Err(SigOpsError::ZeroNorm));
+        assert_eq!(buf.project([1.0]), Err(SigOpsError::BufferMismatch(2, 1)));
+    }
+
+    #[test]
+    fn test_convolve() {
+        let mut buf = Buffer::new(vec![1i32, 2, 3, 4]);
+        buf.convolve([1, 1]).unwrap();
+        assert_eq!(buf.as_ref(), [1, 3, 5, 7]);
+        buf.convolve([0, 0, 0, 0, 0, 1]).unwrap();
+        assert_eq!(buf.as_ref(), [0; 4]);
+        let mut impulse = Buffer::new(vec![1.0f32, 0.0, 0.0, 0.0]);
+        impulse.convolve([0.5, 0.25, 0.125]).unwrap();
+        assert_eq!(impulse.as_ref(), [0.5, 0.25, 0.125, 0.0]);
+        assert_eq!(impulse.convolve([]), Err(SigOpsError::EmptyKernel));
+    }
+
+    #[test]
+    fn test_resample() {
+        let mut buf = Buffer::new(vec![0.0f64, 1.0, 4.0]);
+        buf.resample(2.0).unwrap();
+        assert_eq!(buf.as_ref(), [0.0, 0.5, 1.0, 2.5, 4.0]);
+        buf.resample(0.5).unwrap();
+        assert_eq!(buf.as_ref(), [0.0, 1.0, 4.0]);
+        buf.resample(0.4).unwrap();
+        assert_eq!(buf.as_ref(), [0.0]);
+        assert_eq!(buf.resample(0.0), Err(SigOpsError::InvalidFactor));
+        assert_eq!(buf.resample(f64::NAN), Err(SigOpsError::InvalidFactor));
+        let mut buf = Buffer::new(vec![0.0f64, 1.0]);
+        assert_eq!(buf.resample(1e20), Err(SigOpsError::InvalidFactor));
+        assert_eq!(buf.resample(f64::MAX), Err(SigOpsError::InvalidFactor));
+        assert_eq!(buf.as_ref(), [0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_diff_and_cumsum() {
+        let ramp = Buffer::new(vec![1i32, 4, 9, 16]);
+        for (mode, expected) in [
+            (SigDiff::Forward, [3, 5, 7, 7]),
+            (SigDiff::Backward, [3, 3, 5, 7]),
+            (SigDiff::Central, [3, 4, 6, 7]),
+        ] {
+            let mut buf = ramp.clone();
+            buf.diff(mode);
+            assert_eq!(buf.as_ref(), expected);
+        }
+        let mut single = Buffer::new(vec![5.0f32]);
+        single.diff(SigDiff::Central);
+        assert_eq!(single.as_ref(), [0.0]);
+        let mut stream = [Buffer::new(vec![1i32, 2]), Buffer::new(vec![3, 4, 5])];
+        let carry = stream.iter_mut().fold(0, |carry, block| block.cumsum(carry));
+        assert_eq!((stream[0].as_ref(), stream[1].as_ref(), carry), ([1, 3].as_ref(), [6, 10, 15].as_ref(), 15));
+    }
+
+    #[test]
+    fn test_buffer_ops() {
+        let a = Buffer::new(vec![1.0f32, 2.0, 3.0]);
+        let b = Buffer::new(vec![0.5f32, 0.5, 2.0]);
+        assert_eq!((&a + &b).as_ref(), [1.5, 2.5, 5.0]);
+        assert_eq!((&a - b.clone()).as_ref(), [0.5, 1.5, 1.0]);
+        assert_eq!((a.clone() * &b).as_ref(), [0.5, 1.0, 6.0]);
+        assert_eq!((a.clone() / &[2.0, 4.0, 3.0][..]).as_ref(), [0.5, 0.5, 1.0]);
+        let mut c = a.clone();
+        c += &b;
+        c -= b.clone();
+        c *= &a[..];
+        assert_eq!(c.as_ref(), [1.0, 4.0, 9.0]);
+        assert_eq!((-&a * 2.0 + &b).as_ref(), [-1.5, -3.5, -4.0]);
+        assert_eq!(a.checked_add(&b[..2]), Err(SigOpsError::BufferMismatch(3, 2)));
+        assert_eq!(a.checked_div(&b), Ok(Buffer::new(vec![2.0, 4.0, 1.5])));
+    }
+
+    #[test]
+    fn test_buffer_ops_broadcast() {
+        let mut a = Buffer::new(vec![1i32, 2, 3, 4]);
+        assert_eq!((&a * Broadcast::new(&[1, -1], SigBroadcast::Tile)).as_ref(), [1, -2, 3, -4]);
+        a += Broadcast::new(&[10], SigBroadcast::Scalar);
+        assert_eq!(a.as_ref(), [11, 12, 13, 14]);
+        let b = Buffer::new(vec![1, 2]);
+        assert_eq!((a - Broadcast::new(&b, SigBroadcast::Latch)).as_ref(), [10, 10, 11, 12]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Buffers are not the same size! 3 != 2")]
+    fn test_buffer_ops_mismatch() {
+        let _ = Buffer::new(vec![1u8, 2, 3]) + Buffer::new(vec![1, 2]);
+    }
+}
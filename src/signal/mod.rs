@@ -12,7 +12,9 @@
 ///     - maybe we don't even need this... since chunking is only really only useful for streaming data into some buffer continuously via another thread while reading it
 
 use std::ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::borrow::Cow;
 use std::fmt::{Debug};
+use std::iter::repeat_n;
 use std::io::{BufRead, Read, Seek, Write};
 use crate::units::*;
 use thiserror::Error;
@@ -23,106 +25,291 @@ pub mod buffer;
 
 // GENERAL =========================================================================================
 
-// /// Signal trait : buffer backed data
-// /// - must guarantee immutable access to the underlying data via as_ref
-// /// - must guarantee immutable access to the underlying container via as_container
-// /// - must guarantee an iterator can be made on the underlying data
-// /// - must guarantee that there is an underlying buffer from which we can take a slice
-// ///     - consequence : all streamed or procedural signals must be buffer-backed
-// pub trait Signal:
-//     Debug +
-//     IntoIterator<Item = Self::Sample> + 
-//     FromIterator<Self::Sample> +
-//     AsRef<[Self::Sample]> + 
-//     Deref<Target = [Self::Sample]> +
-//     From<&[Self::Sample]> + From<Self::Container>
-// {
-//     type Sample: Unit;
-//     type Container: FromIterator<Self::Sample>;
-//     fn new(data: Self::Container) -> Self {
-//         Self::from(data)
-//     }
-//     fn new_copy<T: AsRef<[Self::Sample]>>(data: T) -> Self {
-//         Self::from(data.as_ref())
-//     }
-//     fn len(&self) -> usize {
-//         self.as_ref().len()
-//     }
-//     fn min(&self) -> Self::Sample;
-//     fn max(&self) -> Self::Sample;
-//     fn argmax(&self) -> usize;
-//     fn argmin(&self) -> usize;
-// }
-// 
-// /// Mutable Signal Trait
-// /// - must guarantee mutable (interior) access to the underlying data via a slice
-// pub trait SignalMut:
-//     Signal + 
-//     AsMut<[Self::Sample]> +
-//     DerefMut<Target = [Self::Sample]>
-// {
-// }
-// 
-// /// Container Mutable Signal Trait
-// /// - must guarantee that access to the underlying buffer is permitted
-// ///     - underlying buffer may or may not have interior mutability, so we choose worst case (SignalMut bound)
-// /// - must guarantee there is ownership of the underlying buffer
-// pub trait SignalOwned: SignalMut {
-//     fn into_container(self) -> Self::Container;
-//     fn as_container(&self) -> &Self::Container;
-//     fn as_container_mut(&mut self) -> &mut Self::Container;
-// }
-// 
-// /// Resizeable Container Signal Trait
-// pub trait SignalResizable: SignalOwned {
-//     fn resize(&mut self, new_len: usize, fill_value: Self::Sample);
-//     fn clear(&mut self);
-//     fn append(&mut self, value: Self::Sample);
-// }
-// 
-// /// Signal to Scalar Operations Trait
-// /// - guarantees scalar arithmetic via op overload
-// /// - always &self since output is scalar
-// pub trait ScalarOps: Signal + Add<Self::Sample> + Mul<Self::Sample> + Div<Self::Sample> + Sub<Self::Sample> + Neg {
-//     fn inner(&self, other: impl AsRef<[Self::Sample]>) -> Result<Self::Sample, SigOpsError>;
-//     fn angle(&self, other: impl AsRef<[Self::Sample]>) -> Result<Self::Sample, SigOpsError>;
-//     fn norm_l2(&self) -> Self::Sample;
-//     fn norm_l1(&self) -> Self::Sample;
-//     fn norm_inf(&self) -> Self::Sample;
-//     fn mean(&self) -> Self::Sample;
-// }
-// 
-// /// Signal to Signal Operations Trait
-// /// - guarantees point-wise buffer arithmetic via trait methods
-// /// - always &mut self due to output being a signal as well (if copy wanted, clone beforehand)
-// pub trait SignalOps: SignalMut + AddAssign + SubAssign + MulAssign + DivAssign {
-//     fn sig_add<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
-//     fn sig_sub<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
-//     fn sig_mul<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
-//     fn sig_div<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
-//     fn project(&mut self, other: &Self) -> Result<(), SigOpsError>;
-//     fn convolve(&mut self, other: &Self) -> Result<(), SigOpsError>;
-//     fn resample(&mut self, factor: Self::Sample) -> Result<(), SigOpsError>;
-//     fn diff(&mut self);
-//     fn sum(&mut self);
-// }
-// 
+/// Signal trait : buffer backed data
+/// - must guarantee immutable access to the underlying data via as_ref
+/// - must guarantee an iterator can be made on the underlying data
+/// - must guarantee that there is an underlying buffer from which we can take a slice
+///     - consequence : all streamed or procedural signals must be buffer-backed
+pub trait Signal:
+    Debug +
+    IntoIterator<Item = Self::Sample> +
+    FromIterator<Self::Sample> +
+    AsRef<[Self::Sample]> +
+    Deref<Target = [Self::Sample]> +
+    for<'a> From<&'a [Self::Sample]> + From<Self::Container>
+{
+    type Sample: Unit;
+    type Container: FromIterator<Self::Sample>;
+    fn new(data: Self::Container) -> Self {
+        Self::from(data)
+    }
+    fn new_copy<T: AsRef<[Self::Sample]>>(data: T) -> Self {
+        Self::from(data.as_ref())
+    }
+    fn len(&self) -> usize {
+        self.as_ref().len()
+    }
+    fn is_empty(&self) -> bool {
+        self.as_ref().is_empty()
+    }
+    /// Reduction with `_min`, `None` when empty
+    fn min(&self) -> Option<Self::Sample> where Self::Sample: Ordered {
+        self.as_ref().iter().copied().reduce(Ordered::_min)
+    }
+    /// Reduction with `_max`, `None` when empty
+    fn max(&self) -> Option<Self::Sample> where Self::Sample: Ordered {
+        self.as_ref().iter().copied().reduce(Ordered::_max)
+    }
+    /// Index of the first smallest sample, unordered samples (NaN) are skipped
+    fn argmin(&self) -> Option<usize> where Self::Sample: Ordered {
+        arg_extreme(self.as_ref(), |x, best| x < best)
+    }
+    /// Index of the first largest sample, unordered samples (NaN) are skipped
+    fn argmax(&self) -> Option<usize> where Self::Sample: Ordered {
+        arg_extreme(self.as_ref(), |x, best| x > best)
+    }
+}
+
+/// Index of the first sample no other sample `beats`, samples unordered with themselves (NaN) only
+/// win when every sample is
+fn arg_extreme<T: PartialOrd>(data: &[T], beats: impl Fn(&T, &T) -> bool) -> Option<usize> {
+    let unordered = |x: &T| x.partial_cmp(x).is_none();
+    let mut best: Option<usize> = None;
+    for (i, x) in data.iter().enumerate() {
+        match best {
+            Some(b) if !beats(x, &data[b]) && (unordered(x) || !unordered(&data[b])) => {}
+            _ => best = Some(i),
+        }
+    }
+    best
+}
+
+/// Mutable Signal Trait
+/// - must guarantee mutable (interior) access to the underlying data via a slice
+pub trait SignalMut:
+    Signal +
+    AsMut<[Self::Sample]> +
+    DerefMut<Target = [Self::Sample]>
+{
+}
+
+/// Container Mutable Signal Trait
+/// - must guarantee that access to the underlying buffer is permitted
+///     - underlying buffer may or may not have interior mutability, so we choose worst case (SignalMut bound)
+/// - must guarantee there is ownership of the underlying buffer
+pub trait SignalOwned: SignalMut {
+    fn into_container(self) -> Self::Container;
+    fn as_container(&self) -> &Self::Container;
+    fn as_container_mut(&mut self) -> &mut Self::Container;
+}
+
+/// Resizeable Container Signal Trait
+pub trait SignalResizable: SignalOwned {
+    /// Truncates, or extends with `fill_value`, to `new_len` samples
+    fn resize(&mut self, new_len: usize, fill_value: Self::Sample);
+    fn clear(&mut self);
+    /// Pushes `value` onto the end
+    fn append(&mut self, value: Self::Sample);
+}
+
+/// Signal to Scalar Operations Trait
+/// - reductions of a signal (or a plain slice) to a single sample
+/// - every sum is Kahan compensated, so long f32 buffers keep their precision
+/// - averages of an empty signal are NaN
+pub trait ScalarOps: AsRef<[Self::Sample]> {
+    type Sample: Float;
+    fn inner(&self, other: impl AsRef<[Self::Sample]>) -> Result<Self::Sample, SigOpsError> {
+        let (lhs, rhs) = (self.as_ref(), other.as_ref());
+        if lhs.len() != rhs.len() {
+            return Err(SigOpsError::BufferMismatch(lhs.len(), rhs.len()));
+        }
+        Ok(kahan_sum(lhs.iter().zip(rhs).map(|(&x, &y)| x * y)))
+    }
+    /// Angle in radians between the two signals as vectors
+    fn angle(&self, other: impl AsRef<[Self::Sample]>) -> Result<Self::Sample, SigOpsError> {
+        let other = other.as_ref();
+        let norms = self.norm_l2() * other.norm_l2();
+        if norms == Self::Sample::_ZERO {
+            return Err(SigOpsError::ZeroNorm);
+        }
+        let cos = self.inner(other)? / norms;
+        Ok(cos._clamp(Self::Sample::_NEG_ONE, Self::Sample::_ONE)._acos())
+    }
+    fn norm_l1(&self) -> Self::Sample {
+        kahan_sum(self.as_ref().iter().map(|x| x._abs()))
+    }
+    fn norm_l2(&self) -> Self::Sample {
+        self.energy()._sqrt()
+    }
+    fn norm_inf(&self) -> Self::Sample {
+        self.as_ref().iter().fold(Self::Sample::_ZERO, |peak, x| peak._max(x._abs()))
+    }
+    fn mean(&self) -> Self::Sample {
+        kahan_sum(self.as_ref().iter().copied()) / count(self.as_ref())
+    }
+    /// Population variance, taken around the mean in a second pass
+    fn variance(&self) -> Self::Sample {
+        let mean = self.mean();
+        kahan_sum(self.as_ref().iter().map(|&x| (x - mean)._sq())) / count(self.as_ref())
+    }
+    fn std_dev(&self) -> Self::Sample {
+        self.variance()._sqrt()
+    }
+    fn rms(&self) -> Self::Sample {
+        (self.energy() / count(self.as_ref()))._sqrt()
+    }
+    /// Largest magnitude, same as `norm_inf`
+    fn peak(&self) -> Self::Sample {
+        self.norm_inf()
+    }
+    /// Peak to RMS ratio
+    fn crest_factor(&self) -> Self::Sample {
+        self.peak() / self.rms()
+    }
+    /// Sum of squares
+    fn energy(&self) -> Self::Sample {
+        kahan_sum(self.as_ref().iter().map(|x| x._sq()))
+    }
+}
+
+impl<T: Float> ScalarOps for [T] {
+    type Sample = T;
+}
+
+/// Kahan compensated sum, the error is at most about 2ε·Σ|x| (plus a term in nε² that is negligible
+/// for practical lengths), so almost independent of the number of terms but relative to the sum of
+/// magnitudes rather than to the result when the terms cancel
+pub fn kahan_sum<T: Float>(values: impl IntoIterator<Item = T>) -> T {
+    let (mut sum, mut compensation) = (T::_ZERO, T::_ZERO);
+    for x in values {
+        let y = x - compensation;
+        let t = sum + y;
+        compensation = (t - sum) - y;
+        sum = t;
+    }
+    sum
+}
+
+/// Number of samples as a sample
+fn count<T: Float>(data: &[T]) -> T {
+    T::from_usize(data.len()).unwrap_or(T::_INFINITY)
+}
+
+/// Signal to Signal Operations Trait
+/// - guarantees point-wise buffer arithmetic via trait methods
+/// - `sig_*` return a new signal, `sig_*_assign` work in place (if copy wanted, clone beforehand)
+/// - the rhs must be as long as self, the `_with` variants conform it to self by a `SigBroadcast` instead
+pub trait SignalOps: SignalMut {
+    fn sig_add<T: AsRef<[Self::Sample]>>(&self, rhs: T) -> Result<Self, SigOpsError>;
+    fn sig_sub<T: AsRef<[Self::Sample]>>(&self, rhs: T) -> Result<Self, SigOpsError>;
+    fn sig_mul<T: AsRef<[Self::Sample]>>(&self, rhs: T) -> Result<Self, SigOpsError>;
+    fn sig_div<T: AsRef<[Self::Sample]>>(&self, rhs: T) -> Result<Self, SigOpsError>;
+    fn sig_add_assign<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
+    fn sig_sub_assign<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
+    fn sig_mul_assign<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
+    fn sig_div_assign<T: AsRef<[Self::Sample]>>(&mut self, rhs: T) -> Result<(), SigOpsError>;
+    fn sig_add_with<T: AsRef<[Self::Sample]>>(&self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<Self, SigOpsError>;
+    fn sig_sub_with<T: AsRef<[Self::Sample]>>(&self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<Self, SigOpsError>;
+    fn sig_mul_with<T: AsRef<[Self::Sample]>>(&self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<Self, SigOpsError>;
+    fn sig_div_with<T: AsRef<[Self::Sample]>>(&self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<Self, SigOpsError>;
+    fn sig_add_assign_with<T: AsRef<[Self::Sample]>>(&mut self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<(), SigOpsError>;
+    fn sig_sub_assign_with<T: AsRef<[Self::Sample]>>(&mut self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<(), SigOpsError>;
+    fn sig_mul_assign_with<T: AsRef<[Self::Sample]>>(&mut self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<(), SigOpsError>;
+    fn sig_div_assign_with<T: AsRef<[Self::Sample]>>(&mut self, rhs: T, mode: SigBroadcast<Self::Sample>) -> Result<(), SigOpsError>;
+    /// Orthogonal projection onto `onto`
+    fn project<T: AsRef<[Self::Sample]>>(&mut self, onto: T) -> Result<(), SigOpsError> where Self::Sample: Float;
+    /// Causal FIR filter by `kernel`, y[n] = sum of kernel[k] * x[n - k] with x zero before the start
+    fn convolve<T: AsRef<[Self::Sample]>>(&mut self, kernel: T) -> Result<(), SigOpsError>;
+    /// Linear interpolation to `factor` times the sample rate, from the first sample on
+    /// - the output has floor(last * factor) + 1 samples (last the index of the final input sample), so
+    ///   it covers the input times [0, floor(last * factor) / factor] and drops the end of the input
+    ///   when last * factor is not an integer, e.g. 5 samples at 0.4 give the times 0 and 2.5
+    /// - there is no anti-aliasing, low-pass before decimating
+    /// - a factor whose resampled length cannot be allocated is an `InvalidFactor`
+    fn resample(&mut self, factor: Self::Sample) -> Result<(), SigOpsError> where Self::Sample: Float;
+    /// Difference between neighbouring samples, one-sided at the edges the stencil does not fit
+    fn diff(&mut self, mode: SigDiff);
+    /// Prefix sum starting from `carry`, returns the carry for the next block of a stream
+    fn cumsum(&mut self, carry: Self::Sample) -> Self::Sample;
+}
+
 // pub trait SigOwnedOps: SignalOwned {}
 // pub trait SigResizeOps: SignalResizable {}
-// 
-// pub enum SigBroadcast {
-//     Scalar(),
-//     ZeroPad(),
-//     Fill(),
-//     Latch(),
-//     Tile(),
-// }
-// 
-// #[derive(Error, Debug)]
-// pub enum SigOpsError {
-//     #[error("Buffers are not the same size! {0} != {1}")]
-//     BufferMismatch(usize, usize),
-// }
-// 
+
+/// How an operand of another length is conformed to the length of a signal
+/// - a longer operand is truncated in every mode but `Scalar`
+/// - `Latch` and `Tile` need at least one sample to repeat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigBroadcast<T> {
+    /// A single sample applies to every sample, otherwise the lengths must match
+    Scalar,
+    /// Missing samples are zero
+    ZeroPad,
+    /// Missing samples are the given value
+    Fill(T),
+    /// The last sample is held
+    Latch,
+    /// The operand repeats from its start
+    Tile,
+}
+
+impl<T: Unit> SigBroadcast<T> {
+    /// `rhs` conformed to `len` samples, borrowed when already that long
+    pub fn conform<'a>(&self, rhs: &'a [T], len: usize) -> Result<Cow<'a, [T]>, SigOpsError> {
+        if rhs.len() == len || (rhs.len() > len && !matches!(self, SigBroadcast::Scalar)) {
+            return Ok(Cow::Borrowed(&rhs[..len]));
+        }
+        let pad = match (*self, rhs) {
+            (SigBroadcast::Scalar, &[x]) => return Ok(Cow::Owned(vec![x; len])),
+            (SigBroadcast::Scalar, _) => return Err(SigOpsError::BufferMismatch(len, rhs.len())),
+            (SigBroadcast::ZeroPad, _) => T::_ZERO,
+            (SigBroadcast::Fill(value), _) => value,
+            (SigBroadcast::Latch, &[.., last]) => last,
+            (SigBroadcast::Tile, &[_, ..]) => return Ok(Cow::Owned(rhs.iter().copied().cycle().take(len).collect())),
+            (SigBroadcast::Latch | SigBroadcast::Tile, &[]) => return Err(SigOpsError::EmptyBroadcast(len)),
+        };
+        Ok(Cow::Owned(rhs.iter().copied().chain(repeat_n(pad, len - rhs.len())).collect()))
+    }
+}
+
+/// Operand of the buffer operators that is conformed by `mode` rather than panicking on a length mismatch,
+/// e.g. `&a * Broadcast::new(&b, SigBroadcast::Tile)`
+#[derive(Debug, Clone, Copy)]
+pub struct Broadcast<'a, T> {
+    pub rhs: &'a [T],
+    pub mode: SigBroadcast<T>,
+}
+
+impl<'a, T> Broadcast<'a, T> {
+    pub fn new(rhs: &'a [T], mode: SigBroadcast<T>) -> Self {
+        Broadcast { rhs, mode }
+    }
+}
+
+/// Finite difference stencil of `SignalOps::diff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigDiff {
+    /// x[n + 1] - x[n]
+    Forward,
+    /// x[n] - x[n - 1]
+    Backward,
+    /// (x[n + 1] - x[n - 1]) / 2
+    Central,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SigOpsError {
+    #[error("Buffers are not the same size! {0} != {1}")]
+    BufferMismatch(usize, usize),
+    #[error("Cannot broadcast an empty buffer to {0} samples")]
+    EmptyBroadcast(usize),
+    #[error("Operation is undefined for a zero vector")]
+    ZeroNorm,
+    #[error("Cannot convolve with an empty kernel")]
+    EmptyKernel,
+    #[error("Resampling factor must be finite, positive and give an allocatable length")]
+    InvalidFactor,
+}
+
 // /// Procedurally Generated or Streamed Signal
 // pub trait SignalStream: Signal + BufRead + Read + Seek + Write {}
\ No newline at end of file
@@ -0,0 +1,325 @@
+use super::*;
+
+/// Cheaper than libm approximations of common transcendental functions for realtime processing
+/// - `_fast` variants evaluate a near-minimax polynomial after range reduction
+/// - `_lut` variants linearly interpolate a precomputed table (built at compile time)
+///
+/// Maximum error against the exact `ExpFloat`/`Trig` implementations, checked by the tests below:
+///
+/// | method       | f32    | f64     | error    | domain          |
+/// |--------------|--------|---------|----------|-----------------|
+/// | `_sin_fast`  | 1.5e-6 | 6e-16   | absolute | abs(x) <= 64pi  |
+/// | `_cos_fast`  | 1.5e-6 | 1e-15   | absolute | abs(x) <= 64pi  |
+/// | `_exp2_fast` | 3e-7   | 3e-16   | relative | normal results  |
+/// | `_exp_fast`  | 1.5e-6 | 2.5e-15 | relative | abs(x) <= 20    |
+/// | `_log2_fast` | 6e-7   | 1.2e-15 | absolute | x > 0           |
+/// | `_ln_fast`   | 6e-7   | 1.2e-15 | absolute | x > 0           |
+/// | `_tanh_fast` | 3e-7   | 4e-16   | absolute | all x           |
+/// | `_sin_lut`   | 1.5e-6 | 1.2e-6  | absolute | abs(x) <= 64pi  |
+/// | `_cos_lut`   | 1.5e-6 | 1.2e-6  | absolute | abs(x) <= 64pi  |
+/// | `_exp2_lut`  | 1.2e-6 | 1e-6    | relative | normal results  |
+///
+/// Absolute errors are relative once the result exceeds 1 in magnitude (i.e. large logarithms)
+///
+/// Results that would be subnormal flush to zero, other special values (NaN, +-inf) follow libm
+pub trait ApproxFloat: Float {
+    fn _sin_fast(self) -> Self;
+    fn _cos_fast(self) -> Self;
+    fn _exp2_fast(self) -> Self;
+    fn _exp_fast(self) -> Self;
+    fn _log2_fast(self) -> Self;
+    fn _ln_fast(self) -> Self;
+    fn _tanh_fast(self) -> Self;
+    fn _sin_lut(self) -> Self;
+    fn _cos_lut(self) -> Self;
+    fn _exp2_lut(self) -> Self;
+}
+
+/// Number of intervals in the sine table (one full period)
+const SIN_LUT_SIZE: usize = 2048;
+/// Number of intervals in the 2^x table (one octave)
+const EXP2_LUT_SIZE: usize = 256;
+
+/// sin(x) for abs(x) <= pi via its Taylor series, only used to build tables at compile time
+const fn taylor_sin(x: f64) -> f64 {
+    let mut sum = x;
+    let mut term = x;
+    let mut n = 1.0;
+    while n < 40.0 {
+        term = -term * x * x / ((n + 1.0) * (n + 2.0));
+        sum += term;
+        n += 2.0;
+    }
+    sum
+}
+
+/// e^x for abs(x) <= 1 via its Taylor series, only used to build tables at compile time
+const fn taylor_exp(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut n = 1.0;
+    while n < 30.0 {
+        term = term * x / n;
+        sum += term;
+        n += 1.0;
+    }
+    sum
+}
+
+macro_rules! impl_approx_float {
+    (
+        $SrcT:ident, $SrcReprT:ident,
+        tau: [$tau_hi:expr, $tau_mid:expr, $tau_lo:expr],
+        sin: [$($sin_c:expr),*],
+        exp2: [$($exp2_c:expr),*],
+        log2: [$($log2_c:expr),*]
+    ) => {
+        impl ApproxFloat for $SrcT {
+            fn _sin_fast(self) -> Self {
+                sin_reduced(reduce_pi(self))
+            }
+            fn _cos_fast(self) -> Self {
+                // shifting after the reduction keeps the rounding error at the scale of pi
+                sin_reduced(reduce_pi(self) + FRAC_PI_2)
+            }
+            fn _exp2_fast(self) -> Self {
+                const EXP2: &[$SrcT] = &[$($exp2_c as $SrcT),*];
+                match exp2_split(self) {
+                    Ok((scale, f)) => scale * horner(f, EXP2),
+                    Err(special) => special,
+                }
+            }
+            fn _exp_fast(self) -> Self {
+                (self * LOG2_E)._exp2_fast()
+            }
+            fn _log2_fast(self) -> Self {
+                const LOG2: &[$SrcT] = &[$($log2_c as $SrcT),*];
+                #[allow(clippy::neg_cmp_op_on_partial_ord)]
+                if !(self > 0.0) || self == $SrcT::INFINITY {
+                    // NaN, negative, zero and inf handled by libm semantics
+                    return $SrcT::log2(self);
+                }
+                let (x, mut exp) = if self < $SrcT::MIN_POSITIVE {
+                    (self * (1u64 << SIG_BITS) as $SrcT, -(SIG_BITS as i32))
+                } else {
+                    (self, 0)
+                };
+                let bits = x.to_bits();
+                exp += (bits >> SIG_BITS) as i32 - BIAS;
+                // mantissa in [1, 2), then folded onto [sqrt(1/2), sqrt(2)]
+                let mut m = $SrcT::from_bits((bits & SIG_MASK) | ((BIAS as $SrcReprT) << SIG_BITS));
+                if m > SQRT_2 {
+                    m *= 0.5;
+                    exp += 1;
+                }
+                let t = (m - 1.0) / (m + 1.0);
+                exp as $SrcT + t * horner(t * t, LOG2)
+            }
+            fn _ln_fast(self) -> Self {
+                self._log2_fast() * LN_2
+            }
+            fn _tanh_fast(self) -> Self {
+                // tanh(x) = 1 - 2 / (e^2x + 1), saturates to 1 once e^2x overflows
+                let e = (self.abs() * (2.0 * LOG2_E))._exp2_fast();
+                (1.0 - 2.0 / (e + 1.0)).copysign(self)
+            }
+            fn _sin_lut(self) -> Self {
+                let p = reduce_pi(self) * (SIN_LUT_SIZE as $SrcT / TAU);
+                lut_periodic(&SIN_LUT, p)
+            }
+            fn _cos_lut(self) -> Self {
+                let p = reduce_pi(self) * (SIN_LUT_SIZE as $SrcT / TAU) + (SIN_LUT_SIZE / 4) as $SrcT;
+                lut_periodic(&SIN_LUT, p)
+            }
+            fn _exp2_lut(self) -> Self {
+                match exp2_split(self) {
+                    Ok((scale, f)) => {
+                        let p = f * EXP2_LUT_SIZE as $SrcT;
+                        let i = (p as usize).min(EXP2_LUT_SIZE - 1);
+                        let frac = p - i as $SrcT;
+                        scale * (EXP2_LUT[i] + frac * (EXP2_LUT[i + 1] - EXP2_LUT[i]))
+                    }
+                    Err(special) => special,
+                }
+            }
+        }
+
+        use core::$SrcT::consts::{FRAC_PI_2, LN_2, LOG2_E, PI, SQRT_2, TAU};
+        const SIG_BITS: u32 = $SrcT::MANTISSA_DIGITS - 1;
+        const SIG_MASK: $SrcReprT = (1 << SIG_BITS) - 1;
+        const BIAS: i32 = $SrcT::MAX_EXP - 1;
+
+        fn horner(x: $SrcT, coeffs: &[$SrcT]) -> $SrcT {
+            coeffs.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+        }
+
+        /// Cody-Waite reduction onto [-pi, pi], tau is split so `k * hi` and `k * mid` are exact
+        fn reduce_pi(x: $SrcT) -> $SrcT {
+            let k = (x * (1.0 / TAU)).round();
+            ((x - k * $tau_hi) - k * $tau_mid) - k * $tau_lo
+        }
+
+        /// sin(r) for abs(r) <= 3pi/2, folded onto [-pi/2, pi/2] where the odd polynomial is fitted
+        fn sin_reduced(r: $SrcT) -> $SrcT {
+            const SIN: &[$SrcT] = &[$($sin_c as $SrcT),*];
+            let r = if r > FRAC_PI_2 {
+                PI - r
+            } else if r < -FRAC_PI_2 {
+                -PI - r
+            } else {
+                r
+            };
+            r * horner(r * r, SIN)
+        }
+
+        /// Splits 2^x into an exact power of two and a fraction in [0, 1), or the final result
+        fn exp2_split(x: $SrcT) -> Result<($SrcT, $SrcT), $SrcT> {
+            if x.is_nan() {
+                return Err(x);
+            }
+            if x >= $SrcT::MAX_EXP as $SrcT {
+                return Err($SrcT::INFINITY);
+            }
+            if x < ($SrcT::MIN_EXP - 1) as $SrcT {
+                return Err(0.0);
+            }
+            let n = x.floor();
+            let scale = $SrcT::from_bits(((n as i32 + BIAS) as $SrcReprT) << SIG_BITS);
+            Ok((scale, x - n))
+        }
+
+        /// Linear interpolation in a table covering one period, `p` is in table units
+        fn lut_periodic(table: &[$SrcT], p: $SrcT) -> $SrcT {
+            let n = table.len() - 1;
+            let p = if p < 0.0 { p + n as $SrcT } else { p };
+            let i = (p as usize).min(n - 1);
+            let frac = p - i as $SrcT;
+            table[i] + frac * (table[i + 1] - table[i])
+        }
+
+        /// sin over one period [0, tau]
+        static SIN_LUT: [$SrcT; SIN_LUT_SIZE + 1] = {
+            let mut table = [0.0; SIN_LUT_SIZE + 1];
+            let mut i = 0;
+            while i <= SIN_LUT_SIZE {
+                // reduce to [-pi, pi] so the series converges quickly
+                let turns = i as f64 / SIN_LUT_SIZE as f64 - (2 * i > SIN_LUT_SIZE) as i32 as f64;
+                table[i] = taylor_sin(core::f64::consts::TAU * turns) as $SrcT;
+                i += 1;
+            }
+            table
+        };
+
+        /// 2^x over one octave [0, 1]
+        static EXP2_LUT: [$SrcT; EXP2_LUT_SIZE + 1] = {
+            let mut table = [0.0; EXP2_LUT_SIZE + 1];
+            let mut i = 0;
+            while i <= EXP2_LUT_SIZE {
+                table[i] = taylor_exp(core::f64::consts::LN_2 * i as f64 / EXP2_LUT_SIZE as f64) as $SrcT;
+                i += 1;
+            }
+            table
+        };
+    };
+}
+
+mod approx_f32 {
+    use super::*;
+    impl_approx_float!(
+        f32, u32,
+        tau: [6.28125, 0.0019350052, 3.019916e-7],
+        sin: [0.9999992, -0.16665676, 0.008313191, -0.00018522539],
+        exp2: [0.9999999, 0.6931545, 0.24014182, 0.055860337, 0.008949591, 0.001893754],
+        log2: [2.8853905, 0.9615889, 0.59575963]
+    );
+}
+
+mod approx_f64 {
+    use super::*;
+    impl_approx_float!(
+        f64, u64,
+        tau: [6.283185243606567, 6.357301884918343e-8, 2.4492935982947064e-16],
+        sin: [
+            0.9999999999999999, -0.16666666666666072, 0.008333333333282756, -0.00019841269824861897,
+            2.7557316609073673e-6, -2.505188194671259e-8, 1.6048168318165643e-10, -7.374387503862757e-13
+        ],
+        exp2: [
+            1.0, 0.6931471805599465, 0.24022650695904277, 0.0555041086659012, 0.00961812909724503,
+            0.0013333558734919516, 0.0001540350928370369, 1.5253231141364262e-5, 1.320769749209033e-6,
+            1.0258188854184174e-7, 6.538608553499254e-9, 6.301478117433779e-10
+        ],
+        log2: [
+            2.8853900817779268, 0.9617966939259898, 0.5770780163455203, 0.4121985858409005,
+            0.32059853491395984, 0.262334352504183, 0.2209130842311768, 0.2136589569431927
+        ]
+    );
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Max error of `approx` against `exact` over `n` evenly spaced points in [lo, hi]
+    /// (absolute errors become relative once the exact result exceeds 1 in magnitude)
+    fn sweep<T: Float>(lo: f64, hi: f64, n: usize, relative: bool, approx: fn(T) -> T, exact: fn(T) -> T) -> f64 {
+        (0..=n)
+            .map(|i| T::from_f64(lo + (hi - lo) * i as f64 / n as f64).unwrap())
+            .map(|x| {
+                let (a, e) = (approx(x).to_f64().unwrap(), exact(x).to_f64().unwrap());
+                if relative { ((a - e) / e).abs() } else { (a - e).abs() / e.abs().max(1.0) }
+            })
+            .fold(0.0, f64::max)
+    }
+
+    macro_rules! assert_bound {
+        ($T:ident, $lo:expr, $hi:expr, $relative:expr, $approx:ident, $exact:ident, $bound:expr) => {
+            let err = sweep::<$T>($lo, $hi, 200_000, $relative, $T::$approx, $T::$exact);
+            assert!(err <= $bound, "{}::{} error {:e} exceeds {:e}", stringify!($T), stringify!($approx), err, $bound);
+        };
+    }
+
+    #[test]
+    fn test_f32_bounds() {
+        let range = 64.0 * f64::_PI;
+        assert_bound!(f32, -range, range, false, _sin_fast, _sin, 1.5e-6);
+        assert_bound!(f32, -range, range, false, _cos_fast, _cos, 1.5e-6);
+        assert_bound!(f32, -126.0, 127.9, true, _exp2_fast, _exp2, 3e-7);
+        assert_bound!(f32, -20.0, 20.0, true, _exp_fast, _exp, 1.5e-6);
+        assert_bound!(f32, 1e-30, 1e30, false, _log2_fast, _log2, 6e-7);
+        assert_bound!(f32, 1e-3, 8.0, false, _log2_fast, _log2, 6e-7);
+        assert_bound!(f32, 1e-3, 8.0, false, _ln_fast, _ln, 6e-7);
+        assert_bound!(f32, -20.0, 20.0, false, _tanh_fast, _tanh, 3e-7);
+        assert_bound!(f32, -range, range, false, _sin_lut, _sin, 1.5e-6);
+        assert_bound!(f32, -range, range, false, _cos_lut, _cos, 1.5e-6);
+        assert_bound!(f32, -126.0, 127.9, true, _exp2_lut, _exp2, 1.2e-6);
+    }
+
+    #[test]
+    fn test_f64_bounds() {
+        let range = 64.0 * f64::_PI;
+        assert_bound!(f64, -range, range, false, _sin_fast, _sin, 6e-16);
+        assert_bound!(f64, -range, range, false, _cos_fast, _cos, 1e-15);
+        assert_bound!(f64, -1022.0, 1023.9, true, _exp2_fast, _exp2, 3e-16);
+        assert_bound!(f64, -20.0, 20.0, true, _exp_fast, _exp, 2.5e-15);
+        assert_bound!(f64, 1e-300, 1e300, false, _log2_fast, _log2, 1.2e-15);
+        assert_bound!(f64, 1e-3, 8.0, false, _log2_fast, _log2, 1.2e-15);
+        assert_bound!(f64, 1e-3, 8.0, false, _ln_fast, _ln, 1.2e-15);
+        assert_bound!(f64, -40.0, 40.0, false, _tanh_fast, _tanh, 4e-16);
+        assert_bound!(f64, -range, range, false, _sin_lut, _sin, 1.2e-6);
+        assert_bound!(f64, -range, range, false, _cos_lut, _cos, 1.2e-6);
+        assert_bound!(f64, -1022.0, 1023.9, true, _exp2_lut, _exp2, 1e-6);
+    }
+
+    #[test]
+    fn test_special_values() {
+        assert!(f32::NAN._exp2_fast().is_nan());
+        assert_eq!(f32::_INFINITY._exp2_fast(), f32::_INFINITY);
+        assert_eq!(f64::_NEG_INFINITY._exp2_lut(), 0.0);
+        assert_eq!(0f64._log2_fast(), f64::_NEG_INFINITY);
+        assert!((-1f32)._log2_fast().is_nan());
+        assert_eq!(f32::_INFINITY._tanh_fast(), 1.0);
+        assert_eq!(f64::_NEG_INFINITY._tanh_fast(), -1.0);
+        assert!((f32::from_bits(1)._log2_fast() - -149.0).abs() < 1e-5);
+        assert_eq!(0f32._sin_fast(), 0.0);
+        assert_eq!(0f64._sin_lut(), 0.0);
+    }
+}
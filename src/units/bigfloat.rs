@@ -0,0 +1,963 @@
+use std::cmp::Ordering;
+use std::f64::consts::{LN_2, LOG10_2};
+use std::fmt::{Display, Formatter, LowerExp};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use thiserror::Error;
+use super::*;
+
+/// Arbitrary precision binary floating point number with a runtime selected precision
+/// - the value is mant * 2^exp with at most `prec` significant bits and an unbounded (i64) exponent
+/// - arithmetic, sqrt and conversions round to nearest even; the transcendental functions are evaluated
+///   with guard bits and are faithful but not guaranteed to be correctly rounded
+/// - binary ops produce the larger precision of their operands
+/// - heap backed, so it is not `Copy` and can not be a `Unit`/`Float`; the `Float`/`ExpFloat` api is
+///   mirrored by inherent methods of the same name without the underscore
+#[derive(Clone)]
+pub struct BigFloat {
+    prec: u32,
+    negative: bool,
+    kind: Kind,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum Kind {
+    /// mant has no trailing zero bits, zero is stored as (0, 0)
+    Finite { mant: BigUint, exp: i64 },
+    Infinite,
+    Nan,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigFloatError {
+    #[error("cannot parse float from empty string")]
+    Empty,
+    #[error("invalid float literal")]
+    Invalid,
+    #[error("decimal exponent out of range")]
+    ExponentOutOfRange,
+}
+
+/// Guard bits used by the transcendental functions
+const GUARD_BITS: u32 = 32;
+
+/// Decimal exponents are limited to this, or to `EXP10_PER_BIT` per bit of precision when larger, as
+/// parsing builds the exact power of ten
+const EXP10_LIMIT: u64 = 1 << 16;
+const EXP10_PER_BIT: u64 = 64;
+
+impl BigFloat {
+    /// Rounds (-1)^negative * mant * 2^exp to `prec` bits, nearest even
+    fn finite(negative: bool, mant: BigUint, exp: i64, prec: u32) -> Self {
+        assert!(prec > 0, "precision must be at least one bit");
+        let Some(tz) = mant.trailing_zeros() else {
+            return BigFloat { prec, negative, kind: Kind::Finite { mant, exp: 0 } };
+        };
+        let (mut mant, mut exp) = (mant >> tz as usize, exp + tz as i64);
+        let bits = mant.bits();
+        if bits > prec as u64 {
+            let shift = bits - prec as u64;
+            let half = mant.bit(shift - 1);
+            // mant has no trailing zeros, so anything below the half bit is non-zero when shift > 1
+            let sticky = shift > 1;
+            let mut q = &mant >> shift as usize;
+            if half && (sticky || q.bit(0)) {
+                q += BigUint::one();
+            }
+            let tz = q.trailing_zeros().unwrap_or(0);
+            mant = q >> tz as usize;
+            exp += (shift + tz) as i64;
+        }
+        BigFloat { prec, negative, kind: Kind::Finite { mant, exp } }
+    }
+
+    pub fn zero(prec: u32) -> Self {
+        Self::finite(false, BigUint::ZERO, 0, prec)
+    }
+    pub fn one(prec: u32) -> Self {
+        Self::finite(false, BigUint::one(), 0, prec)
+    }
+    pub fn nan(prec: u32) -> Self {
+        BigFloat { prec, negative: false, kind: Kind::Nan }
+    }
+    pub fn infinity(prec: u32) -> Self {
+        BigFloat { prec, negative: false, kind: Kind::Infinite }
+    }
+    pub fn neg_infinity(prec: u32) -> Self {
+        BigFloat { prec, negative: true, kind: Kind::Infinite }
+    }
+    pub fn from_bigint(n: &BigInt, prec: u32) -> Self {
+        Self::finite(n.is_negative(), n.magnitude().clone(), 0, prec)
+    }
+    pub fn from_i64_prec(n: i64, prec: u32) -> Self {
+        Self::from_bigint(&BigInt::from(n), prec)
+    }
+    /// Exact conversion of a f64 at the given precision
+    pub fn from_f64_prec(n: f64, prec: u32) -> Self {
+        if n.is_nan() {
+            return Self::nan(prec);
+        }
+        if n.is_infinite() {
+            return BigFloat { prec, negative: n < 0.0, kind: Kind::Infinite };
+        }
+        let bits = n.to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i64;
+        let frac = bits & ((1 << 52) - 1);
+        let (mant, exp) = if biased == 0 { (frac, -1074) } else { (frac | (1 << 52), biased - 1075) };
+        Self::finite(n.is_sign_negative(), BigUint::from(mant), exp, prec)
+    }
+
+    pub fn prec(&self) -> u32 {
+        self.prec
+    }
+    /// Copy rounded to a new precision
+    pub fn with_prec(&self, prec: u32) -> Self {
+        match &self.kind {
+            Kind::Finite { mant, exp } => Self::finite(self.negative, mant.clone(), *exp, prec),
+            kind => BigFloat { prec, negative: self.negative, kind: kind.clone() },
+        }
+    }
+    pub fn is_nan(&self) -> bool {
+        self.kind == Kind::Nan
+    }
+    pub fn is_infinite(&self) -> bool {
+        self.kind == Kind::Infinite
+    }
+    pub fn is_finite(&self) -> bool {
+        matches!(self.kind, Kind::Finite { .. })
+    }
+    pub fn is_zero(&self) -> bool {
+        matches!(&self.kind, Kind::Finite { mant, .. } if mant.is_zero())
+    }
+    pub fn is_sign_negative(&self) -> bool {
+        self.negative
+    }
+    pub fn abs(&self) -> Self {
+        BigFloat { negative: false, ..self.clone() }
+    }
+    /// 1 or -1 by sign, NaN for NaN
+    pub fn signum(&self) -> Self {
+        match self.kind {
+            Kind::Nan => self.clone(),
+            _ => BigFloat { negative: self.negative, ..Self::one(self.prec) },
+        }
+    }
+    /// Exponent of the bit just above the leading one, |self| lies in [2^(top-1), 2^top)
+    fn top(&self) -> Option<i64> {
+        match &self.kind {
+            Kind::Finite { mant, exp } if !mant.is_zero() => Some(exp + mant.bits() as i64),
+            _ => None,
+        }
+    }
+    /// self * 2^k, exact
+    fn scale2(&self, k: i64) -> Self {
+        match &self.kind {
+            Kind::Finite { mant, exp } if !mant.is_zero() => {
+                BigFloat { kind: Kind::Finite { mant: mant.clone(), exp: exp + k }, ..self.clone() }
+            }
+            _ => self.clone(),
+        }
+    }
+    /// Integer value if self is a finite integer that fits
+    fn to_i64_exact(&self) -> Option<i64> {
+        match &self.kind {
+            Kind::Finite { exp, .. } if *exp >= 0 => self.to_i64(),
+            _ => None,
+        }
+    }
+
+    // Correctly rounded arithmetic ================================================================
+
+    fn add_prec(&self, rhs: &Self, rhs_negative: bool, prec: u32) -> Self {
+        let (ma, ea, mb, eb) = match (&self.kind, &rhs.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) => return Self::nan(prec),
+            (Kind::Infinite, Kind::Infinite) if self.negative != rhs_negative => return Self::nan(prec),
+            (Kind::Infinite, _) => return BigFloat { prec, ..self.clone() },
+            (_, Kind::Infinite) => return BigFloat { prec, negative: rhs_negative, kind: Kind::Infinite },
+            (Kind::Finite { mant: ma, exp: ea }, Kind::Finite { mant: mb, exp: eb }) => (ma, ea, mb, eb),
+        };
+        if mb.is_zero() {
+            let negative = self.negative && (rhs_negative || !ma.is_zero());
+            return Self::finite(negative, ma.clone(), *ea, prec);
+        }
+        if ma.is_zero() {
+            return Self::finite(rhs_negative, mb.clone(), *eb, prec);
+        }
+        // an operand far below the rounding position only matters as a sticky bit
+        let (ta, tb) = (ea + ma.bits() as i64, eb + mb.bits() as i64);
+        let limit = ta.max(tb) - prec as i64 - 3;
+        let sticky = |m: &BigUint, e: i64, t: i64| if t < limit { (BigUint::one(), limit - 1) } else { (m.clone(), e) };
+        let ((ma, ea), (mb, eb)) = (sticky(ma, *ea, ta), sticky(mb, *eb, tb));
+        let e = ea.min(eb);
+        let a = ma << (ea - e) as usize;
+        let b = mb << (eb - e) as usize;
+        if self.negative == rhs_negative {
+            return Self::finite(self.negative, a + b, e, prec);
+        }
+        match a.cmp(&b) {
+            Ordering::Greater => Self::finite(self.negative, a - b, e, prec),
+            Ordering::Less => Self::finite(rhs_negative, b - a, e, prec),
+            Ordering::Equal => Self::zero(prec),
+        }
+    }
+
+    fn mul_prec(&self, rhs: &Self, prec: u32) -> Self {
+        let negative = self.negative != rhs.negative;
+        match (&self.kind, &rhs.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) => Self::nan(prec),
+            (Kind::Infinite, _) | (_, Kind::Infinite) if self.is_zero() || rhs.is_zero() => Self::nan(prec),
+            (Kind::Infinite, _) | (_, Kind::Infinite) => BigFloat { prec, negative, kind: Kind::Infinite },
+            (Kind::Finite { mant: ma, exp: ea }, Kind::Finite { mant: mb, exp: eb }) => {
+                Self::finite(negative, ma * mb, ea + eb, prec)
+            }
+        }
+    }
+
+    fn div_prec(&self, rhs: &Self, prec: u32) -> Self {
+        let negative = self.negative != rhs.negative;
+        match (&self.kind, &rhs.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) | (Kind::Infinite, Kind::Infinite) => Self::nan(prec),
+            (Kind::Infinite, _) => BigFloat { prec, negative, kind: Kind::Infinite },
+            (_, Kind::Infinite) => BigFloat { negative, ..Self::zero(prec) },
+            _ if rhs.is_zero() && self.is_zero() => Self::nan(prec),
+            _ if rhs.is_zero() => BigFloat { prec, negative, kind: Kind::Infinite },
+            (Kind::Finite { mant: ma, exp: ea }, Kind::Finite { mant: mb, exp: eb }) => {
+                // at least prec + 2 quotient bits, the remainder becomes a sticky bit
+                let shift = (prec as i64 + 2 + mb.bits() as i64 - ma.bits() as i64).max(0);
+                let (q, r) = (ma << shift as usize).div_rem(mb);
+                let (q, extra) = if r.is_zero() { (q, 0) } else { ((q << 1) + BigUint::one(), 1) };
+                Self::finite(negative, q, ea - eb - shift - extra, prec)
+            }
+        }
+    }
+
+    /// Truncated remainder with the sign of self, exact before the final rounding
+    fn rem_prec(&self, rhs: &Self, prec: u32) -> Self {
+        match (&self.kind, &rhs.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) | (Kind::Infinite, _) => Self::nan(prec),
+            _ if rhs.is_zero() => Self::nan(prec),
+            (_, Kind::Infinite) => self.with_prec(prec),
+            (Kind::Finite { mant: ma, exp: ea }, Kind::Finite { mant: mb, exp: eb }) => {
+                let e = (*ea).min(*eb);
+                let r = (ma << (ea - e) as usize) % (mb << (eb - e) as usize);
+                Self::finite(self.negative, r, e, prec)
+            }
+        }
+    }
+
+    /// Correctly rounded square root, NaN below zero
+    pub fn sqrt(&self) -> Self {
+        let prec = self.prec;
+        match &self.kind {
+            Kind::Nan => self.clone(),
+            _ if self.is_zero() => self.clone(),
+            _ if self.negative => Self::nan(prec),
+            Kind::Infinite => self.clone(),
+            Kind::Finite { mant, exp } => {
+                let mut shift = (2 * prec as i64 + 4 - mant.bits() as i64).max(0);
+                if (exp - shift) % 2 != 0 {
+                    shift += 1;
+                }
+                let m = mant << shift as usize;
+                let s = m.sqrt();
+                let e = (exp - shift) / 2;
+                if &s * &s == m { Self::finite(false, s, e, prec) } else { Self::finite(false, (s << 1) + BigUint::one(), e - 1, prec) }
+            }
+        }
+    }
+    /// self^2
+    pub fn sq(&self) -> Self {
+        self * self
+    }
+
+    // Rounding functions ==========================================================================
+
+    /// Rounds the magnitude to an integer, `up` decides from (integer part, dropped half bit, dropped rest non-zero)
+    fn round_int(&self, up: impl Fn(&BigUint, bool, bool) -> bool) -> Self {
+        let Kind::Finite { mant, exp } = &self.kind else {
+            return self.clone();
+        };
+        if *exp >= 0 {
+            return self.clone();
+        }
+        let shift = (-exp) as u64;
+        let q = if shift > mant.bits() + 1 { BigUint::ZERO } else { mant >> shift as usize };
+        let half = mant.bit(shift - 1);
+        let rest = mant.trailing_zeros().is_some_and(|tz| tz < shift - 1);
+        let q = if up(&q, half, rest) { q + BigUint::one() } else { q };
+        Self::finite(self.negative, q, 0, self.prec)
+    }
+    pub fn trunc(&self) -> Self {
+        self.round_int(|_, _, _| false)
+    }
+    pub fn floor(&self) -> Self {
+        let negative = self.negative;
+        self.round_int(|_, half, rest| negative && (half || rest))
+    }
+    pub fn ceil(&self) -> Self {
+        let negative = self.negative;
+        self.round_int(|_, half, rest| !negative && (half || rest))
+    }
+    /// Rounds half away from zero like `f64::round`
+    pub fn round(&self) -> Self {
+        self.round_int(|_, half, _| half)
+    }
+    /// Rounds half to even
+    pub fn round_ties_even(&self) -> Self {
+        self.round_int(|q, half, rest| half && (rest || q.bit(0)))
+    }
+    pub fn fract(&self) -> Self {
+        self - &self.trunc()
+    }
+    /// Truncated integer value, `None` for NaN and infinities
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match &self.trunc().kind {
+            Kind::Finite { mant, exp } => Some(BigInt::from_parts(self.negative, mant << *exp as usize)),
+            _ => None,
+        }
+    }
+
+    // Constants ===================================================================================
+
+    /// π = 16 atan(1/5) - 4 atan(1/239) (Machin)
+    pub fn pi(prec: u32) -> Self {
+        let wp = prec + GUARD_BITS;
+        let a = Self::atan_inv(5, wp).scale2(4);
+        let b = Self::atan_inv(239, wp).scale2(2);
+        (a - b).with_prec(prec)
+    }
+    pub fn tau(prec: u32) -> Self {
+        Self::pi(prec).scale2(1)
+    }
+    pub fn e(prec: u32) -> Self {
+        Self::one(prec + GUARD_BITS).exp().with_prec(prec)
+    }
+    /// ln(2) = 2 atanh(1/3)
+    pub fn ln_2(prec: u32) -> Self {
+        let wp = prec + GUARD_BITS;
+        Self::atanh_series(&Self::one(wp).div_prec(&Self::from_i64_prec(3, wp), wp)).scale2(1).with_prec(prec)
+    }
+    pub fn ln_10(prec: u32) -> Self {
+        Self::from_i64_prec(10, prec + GUARD_BITS).ln().with_prec(prec)
+    }
+
+    /// atan(1/n) = sum (-1)^k / ((2k+1) n^(2k+1))
+    fn atan_inv(n: i64, wp: u32) -> Self {
+        let x = Self::one(wp).div_prec(&Self::from_i64_prec(n, wp), wp);
+        let x2 = &x * &x;
+        let mut power = x.clone();
+        let mut sum = x;
+        for k in 1.. {
+            power = &power * &x2;
+            let term = power.div_prec(&Self::from_i64_prec(2 * k + 1, wp), wp);
+            if term.is_negligible(&sum, wp) {
+                break;
+            }
+            sum = if k % 2 == 1 { &sum - &term } else { &sum + &term };
+        }
+        sum
+    }
+
+    /// atanh(z) = sum z^(2k+1) / (2k+1) for small |z|
+    fn atanh_series(z: &Self) -> Self {
+        let wp = z.prec;
+        let z2 = z * z;
+        let mut power = z.clone();
+        let mut sum = z.clone();
+        for k in 1.. {
+            power = &power * &z2;
+            let term = power.div_prec(&Self::from_i64_prec(2 * k + 1, wp), wp);
+            if term.is_negligible(&sum, wp) {
+                break;
+            }
+            sum += term;
+        }
+        sum
+    }
+
+    /// Whether adding self to sum can no longer change it at precision wp
+    fn is_negligible(&self, sum: &Self, wp: u32) -> bool {
+        match (self.top(), sum.top()) {
+            (None, _) => true,
+            (Some(t), Some(s)) => t < s - wp as i64 - 2,
+            _ => false,
+        }
+    }
+
+    // Exponentials and logarithms =================================================================
+
+    /// e^self
+    pub fn exp(&self) -> Self {
+        let prec = self.prec;
+        match self.kind {
+            Kind::Nan => return self.clone(),
+            Kind::Infinite if self.negative => return Self::zero(prec),
+            Kind::Infinite => return self.clone(),
+            _ if self.is_zero() => return Self::one(prec),
+            _ => {}
+        }
+        let approx = self.to_f64().unwrap_or(f64::NAN);
+        if approx.abs() > 1e18 {
+            return if self.negative { Self::zero(prec) } else { Self::infinity(prec) };
+        }
+        // self = k ln2 + r, then e^r = (e^(r / 2^s))^(2^s) with the squarings costing s guard bits
+        let k = (approx / LN_2).round() as i64;
+        let s = (prec as f64).sqrt() as u32 / 2 + 1;
+        let wp = prec + GUARD_BITS + s;
+        let wk = wp + 64 - k.unsigned_abs().leading_zeros();
+        let r = self.with_prec(wk) - Self::ln_2(wk) * Self::from_i64_prec(k, wk);
+        let r = r.scale2(-(s as i64)).with_prec(wp);
+        let mut term = Self::one(wp);
+        let mut sum = Self::one(wp);
+        for n in 1.. {
+            term = (&term * &r).div_prec(&Self::from_i64_prec(n, wp), wp);
+            if term.is_negligible(&sum, wp) {
+                break;
+            }
+            sum += &term;
+        }
+        for _ in 0..s {
+            sum = sum.sq();
+        }
+        sum.scale2(k).with_prec(prec)
+    }
+    /// 2^self
+    pub fn exp2(&self) -> Self {
+        if let Some(k) = self.to_i64_exact() {
+            return Self::one(self.prec).scale2(k);
+        }
+        let wp = self.prec + GUARD_BITS + 64;
+        (self.with_prec(wp) * Self::ln_2(wp)).exp().with_prec(self.prec)
+    }
+    /// e^self - 1, accurate for small self
+    pub fn exp_m1(&self) -> Self {
+        let extra = self.top().map_or(0, |t| (-t).max(0) as u32);
+        let wp = self.prec + extra + 8;
+        (self.with_prec(wp).exp() - Self::one(wp)).with_prec(self.prec)
+    }
+    /// Natural logarithm, NaN below zero
+    pub fn ln(&self) -> Self {
+        let prec = self.prec;
+        match self.kind {
+            Kind::Nan => return self.clone(),
+            _ if self.is_zero() => return Self::neg_infinity(prec),
+            _ if self.negative => return Self::nan(prec),
+            Kind::Infinite => return self.clone(),
+            _ => {}
+        }
+        // self = m 2^t with m in [1/sqrt(2), sqrt(2)), ln(m) = 2 atanh((m - 1) / (m + 1))
+        let wp = prec + GUARD_BITS;
+        let mut t = self.top().unwrap();
+        let mut m = self.scale2(-t).with_prec(wp);
+        if m.sq() < Self::from_f64_prec(0.5, wp) {
+            m = m.scale2(1);
+            t -= 1;
+        }
+        let one = Self::one(wp);
+        let z = (&m - &one).div_prec(&(&m + &one), wp);
+        let ln_m = Self::atanh_series(&z).scale2(1);
+        if t == 0 {
+            return ln_m.with_prec(prec);
+        }
+        let wt = wp + 64 - t.unsigned_abs().leading_zeros();
+        (Self::ln_2(wt) * Self::from_i64_prec(t, wt) + ln_m).with_prec(prec)
+    }
+    /// ln(1 + self), accurate for small self
+    pub fn ln_1p(&self) -> Self {
+        let extra = self.top().map_or(0, |t| (-t).max(0) as u32);
+        let wp = self.prec + extra + 8;
+        (Self::one(wp) + self.with_prec(wp)).ln().with_prec(self.prec)
+    }
+    /// log_base(self)
+    pub fn log(&self, base: &Self) -> Self {
+        let prec = self.prec.max(base.prec);
+        let wp = prec + GUARD_BITS;
+        (self.with_prec(wp).ln() / base.with_prec(wp).ln()).with_prec(prec)
+    }
+    /// log2(self), exact for powers of two
+    pub fn log2(&self) -> Self {
+        match &self.kind {
+            Kind::Finite { mant, exp } if !self.negative && *mant == BigUint::one() => Self::from_i64_prec(*exp, self.prec),
+            _ => {
+                let wp = self.prec + GUARD_BITS;
+                (self.with_prec(wp).ln() / Self::ln_2(wp)).with_prec(self.prec)
+            }
+        }
+    }
+    pub fn log10(&self) -> Self {
+        let wp = self.prec + GUARD_BITS;
+        (self.with_prec(wp).ln() / Self::ln_10(wp)).with_prec(self.prec)
+    }
+    /// self^rhs, integer exponents use repeated squaring and allow negative bases
+    pub fn pow(&self, rhs: &Self) -> Self {
+        let prec = self.prec.max(rhs.prec);
+        if rhs.is_zero() {
+            return Self::one(prec);
+        }
+        if self.is_nan() || rhs.is_nan() {
+            return Self::nan(prec);
+        }
+        if let Some(n) = rhs.to_i64_exact().filter(|n| n.unsigned_abs() <= 1 << 32) {
+            return self.powi(n, prec);
+        }
+        if self.negative {
+            return Self::nan(prec);
+        }
+        if self.is_zero() || self.is_infinite() || rhs.is_infinite() {
+            // 0^y, inf^y and x^±inf follow e^(y ln x)
+            let large = self.is_infinite() || self.top().is_some_and(|t| t > 0);
+            let grows = large != rhs.negative;
+            return if grows { Self::infinity(prec) } else { Self::zero(prec) };
+        }
+        // the magnitude of y ln(x) scales the absolute error of the exponent
+        let extra = rhs.top().unwrap_or(0).max(0) as u32 + 64 - self.top().unwrap().unsigned_abs().leading_zeros();
+        let wp = prec + GUARD_BITS + extra;
+        (rhs.with_prec(wp) * self.with_prec(wp).ln()).exp().with_prec(prec)
+    }
+    fn powi(&self, n: i64, prec: u32) -> Self {
+        let wp = prec + GUARD_BITS + 64 - n.unsigned_abs().leading_zeros();
+        let mut base = self.with_prec(wp);
+        let mut acc = Self::one(wp);
+        let mut e = n.unsigned_abs();
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = &acc * &base;
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.sq();
+            }
+        }
+        if n < 0 { Self::one(wp).div_prec(&acc, prec) } else { acc.with_prec(prec) }
+    }
+    /// self^(1/n), odd integer roots of negative values are negative
+    pub fn root(&self, n: &Self) -> Self {
+        let prec = self.prec.max(n.prec);
+        if n.to_i64_exact() == Some(2) {
+            return self.with_prec(prec).sqrt();
+        }
+        if self.negative && n.to_i64_exact().is_some_and(|n| n % 2 != 0) {
+            return -(-self).root(n);
+        }
+        let wp = prec + GUARD_BITS;
+        self.with_prec(wp).pow(&Self::one(wp).div_prec(n, wp)).with_prec(prec)
+    }
+
+    // Formatting ==================================================================================
+
+    /// (decimal digits, power of ten of the first digit) rounded half even to `digits` significant digits
+    fn to_decimal(&self, digits: usize) -> (String, i64) {
+        let Kind::Finite { mant, exp } = &self.kind else {
+            unreachable!()
+        };
+        if mant.is_zero() {
+            return ("0".repeat(digits), 0);
+        }
+        let ten = BigUint::from(10u32);
+        let lower = ten.pow(digits as u32 - 1);
+        let upper = ten.pow(digits as u32);
+        let mut k = ((self.top().unwrap() - 1) as f64 * LOG10_2).floor() as i64;
+        loop {
+            let p = digits as i64 - 1 - k;
+            let num = (mant << exp.max(&0).unsigned_abs() as usize) * ten.pow(p.max(0) as u32);
+            let den = (BigUint::one() << (-exp).max(0) as usize) * ten.pow((-p).max(0) as u32);
+            let (q, r) = num.div_rem(&den);
+            let q = match (r << 1).cmp(&den) {
+                Ordering::Greater => q + BigUint::one(),
+                Ordering::Equal if q.bit(0) => q + BigUint::one(),
+                _ => q,
+            };
+            if q >= upper {
+                k += 1;
+            } else if q < lower {
+                k -= 1;
+            } else {
+                return (q.to_string(), k);
+            }
+        }
+    }
+
+    /// Fewest significant digits that parse back to self at the same precision
+    fn to_shortest_decimal(&self) -> (String, i64) {
+        let max = (self.prec as f64 * LOG10_2).ceil() as usize + 1;
+        for digits in 1..max {
+            let (d, k) = self.to_decimal(digits);
+            let parsed = Self::from_str_prec(&format!("{d}e{}", k - digits as i64 + 1), self.prec);
+            if parsed.is_ok_and(|p| p.abs() == self.abs()) {
+                return (d, k);
+            }
+        }
+        self.to_decimal(max)
+    }
+
+    /// Parses `[+-]digits[.digits][e[+-]digits]`, `inf` and `nan`, correctly rounded to `prec` bits
+    pub fn from_str_prec(s: &str, prec: u32) -> Result<Self, ParseBigFloatError> {
+        let (negative, body) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if body.is_empty() {
+            return Err(ParseBigFloatError::Empty);
+        }
+        match body.to_ascii_lowercase().as_str() {
+            "inf" | "infinity" => return Ok(BigFloat { prec, negative, kind: Kind::Infinite }),
+            "nan" => return Ok(Self::nan(prec)),
+            _ => {}
+        }
+        let (number, exp10) = match body.find(['e', 'E']) {
+            Some(i) => (&body[..i], body[i + 1..].parse::<i64>().map_err(|_| ParseBigFloatError::Invalid)?),
+            None => (body, 0),
+        };
+        let (int, frac) = number.split_once('.').unwrap_or((number, ""));
+        if int.is_empty() && frac.is_empty() {
+            return Err(ParseBigFloatError::Invalid);
+        }
+        if !int.bytes().chain(frac.bytes()).all(|c| c.is_ascii_digit()) {
+            return Err(ParseBigFloatError::Invalid);
+        }
+        let digits = BigUint::from_str_radix(&format!("{int}{frac}"), 10).map_err(|_| ParseBigFloatError::Invalid)?;
+        let exp10 = exp10.checked_sub(frac.len() as i64).ok_or(ParseBigFloatError::ExponentOutOfRange)?;
+        if exp10.unsigned_abs() > EXP10_LIMIT.max(EXP10_PER_BIT * prec as u64) + frac.len() as u64 {
+            return Err(ParseBigFloatError::ExponentOutOfRange);
+        }
+        let ten = BigUint::from(10u32);
+        let exact = |m: BigUint| {
+            let bits = m.bits().max(1) as u32;
+            Self::finite(negative, m, 0, bits)
+        };
+        let pow10 = |e: i64| u32::try_from(e).map(|e| ten.pow(e)).map_err(|_| ParseBigFloatError::ExponentOutOfRange);
+        if exp10 >= 0 {
+            return Ok(Self::finite(negative, digits * pow10(exp10)?, 0, prec));
+        }
+        // a single correctly rounded division of two exact values
+        let den = exact(pow10(exp10.checked_neg().ok_or(ParseBigFloatError::ExponentOutOfRange)?)?).abs();
+        Ok(exact(digits).div_prec(&den, prec))
+    }
+}
+
+impl Display for BigFloat {
+    /// Scientific notation with the shortest digits that round trip, or the formatter precision after the point
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.negative { "-" } else if f.sign_plus() { "+" } else { "" };
+        match self.kind {
+            Kind::Nan => return f.write_str("NaN"),
+            Kind::Infinite => return write!(f, "{sign}inf"),
+            _ => {}
+        }
+        let (digits, k) = match f.precision() {
+            Some(p) => self.to_decimal(p + 1),
+            None => self.to_shortest_decimal(),
+        };
+        let (lead, rest) = digits.split_at(1);
+        let rest = if f.precision().is_some() { rest } else { rest.trim_end_matches('0') };
+        let point = if rest.is_empty() { "" } else { "." };
+        write!(f, "{sign}{lead}{point}{rest}e{k}")
+    }
+}
+
+impl LowerExp for BigFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::fmt::Debug for BigFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self} (prec {})", self.prec)
+    }
+}
+
+impl PartialEq for BigFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for BigFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let magnitude = match (&self.kind, &other.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) => return None,
+            _ if self.is_zero() && other.is_zero() => return Some(Ordering::Equal),
+            _ if self.negative != other.negative || self.is_zero() || other.is_zero() => {
+                let sign = |x: &Self| if x.is_zero() { 0 } else if x.negative { -1 } else { 1 };
+                return Some(sign(self).cmp(&sign(other)));
+            }
+            (Kind::Infinite, Kind::Infinite) => Ordering::Equal,
+            (Kind::Infinite, _) => Ordering::Greater,
+            (_, Kind::Infinite) => Ordering::Less,
+            (Kind::Finite { mant: ma, exp: ea }, Kind::Finite { mant: mb, exp: eb }) => {
+                self.top().cmp(&other.top()).then_with(|| {
+                    let e = (*ea).min(*eb);
+                    (ma << (ea - e) as usize).cmp(&(mb << (eb - e) as usize))
+                })
+            }
+        };
+        Some(if self.negative { magnitude.reverse() } else { magnitude })
+    }
+}
+
+impl Ordered for BigFloat {
+    /// NaN is ignored like `f64::min`
+    fn _min(self, other: Self) -> Self {
+        if other < self || self.is_nan() { other } else { self }
+    }
+    fn _max(self, other: Self) -> Self {
+        if other > self || self.is_nan() { other } else { self }
+    }
+    fn _clamp(self, min: Self, max: Self) -> Self {
+        self._max(min)._min(max)
+    }
+}
+
+// std::ops implementations ========================================================================
+
+impl Add<&BigFloat> for &BigFloat {
+    type Output = BigFloat;
+    fn add(self, rhs: &BigFloat) -> BigFloat {
+        self.add_prec(rhs, rhs.negative, self.prec.max(rhs.prec))
+    }
+}
+impl Sub<&BigFloat> for &BigFloat {
+    type Output = BigFloat;
+    fn sub(self, rhs: &BigFloat) -> BigFloat {
+        self.add_prec(rhs, !rhs.negative, self.prec.max(rhs.prec))
+    }
+}
+impl Mul<&BigFloat> for &BigFloat {
+    type Output = BigFloat;
+    fn mul(self, rhs: &BigFloat) -> BigFloat {
+        self.mul_prec(rhs, self.prec.max(rhs.prec))
+    }
+}
+impl Div<&BigFloat> for &BigFloat {
+    type Output = BigFloat;
+    fn div(self, rhs: &BigFloat) -> BigFloat {
+        self.div_prec(rhs, self.prec.max(rhs.prec))
+    }
+}
+impl Rem<&BigFloat> for &BigFloat {
+    type Output = BigFloat;
+    fn rem(self, rhs: &BigFloat) -> BigFloat {
+        self.rem_prec(rhs, self.prec.max(rhs.prec))
+    }
+}
+impl Neg for &BigFloat {
+    type Output = BigFloat;
+    fn neg(self) -> BigFloat {
+        BigFloat { negative: !self.negative, ..self.clone() }
+    }
+}
+impl Neg for BigFloat {
+    type Output = BigFloat;
+    fn neg(self) -> BigFloat {
+        BigFloat { negative: !self.negative, ..self }
+    }
+}
+
+forward_bigint_binop!(BigFloat, Add, add, AddAssign, add_assign);
+forward_bigint_binop!(BigFloat, Sub, sub, SubAssign, sub_assign);
+forward_bigint_binop!(BigFloat, Mul, mul, MulAssign, mul_assign);
+forward_bigint_binop!(BigFloat, Div, div, DivAssign, div_assign);
+forward_bigint_binop!(BigFloat, Rem, rem, RemAssign, rem_assign);
+
+// Casting implementations =========================================================================
+
+impl BigFloat {
+    /// Rounds to a binary float with `sig` significand bits and smallest subnormal exponent `min_exp`,
+    /// returning the value as f64 (exact for both f32 and f64 targets)
+    fn to_binary_float(&self, sig: u32, min_exp: i64) -> f64 {
+        let sign = if self.negative { -1.0 } else { 1.0 };
+        let (mant, exp) = match &self.kind {
+            Kind::Nan => return f64::NAN,
+            Kind::Infinite => return sign * f64::INFINITY,
+            Kind::Finite { mant, exp } => (mant, *exp),
+        };
+        let Some(top) = self.top() else {
+            return sign * 0.0;
+        };
+        // subnormals keep fewer bits, below half the smallest subnormal everything rounds to zero
+        let bits = (top - min_exp).min(sig as i64);
+        let rounded = match bits {
+            ..0 => return sign * 0.0,
+            0 => {
+                let tie = *mant == BigUint::one();
+                let half = (min_exp / 2) as i32;
+                return if tie { sign * 0.0 } else { sign * 2f64.powi(half) * 2f64.powi(min_exp as i32 - half) };
+            }
+            _ => Self::finite(self.negative, mant.clone(), exp, bits as u32),
+        };
+        let Kind::Finite { mant, exp } = rounded.kind else { unreachable!() };
+        if exp + mant.bits() as i64 > 1100 {
+            return sign * f64::INFINITY;
+        }
+        let m = mant.to_u64().unwrap() as f64;
+        // two steps keep the intermediate normal
+        let half = (exp / 2) as i32;
+        sign * m * 2f64.powi(half) * 2f64.powi(exp as i32 - half)
+    }
+}
+
+macro_rules! impl_bigfloat_to_primitive {
+    ($( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            self.to_bigint()?.$method()
+        }
+    )*}
+}
+
+macro_rules! impl_bigfloat_from_primitive {
+    ($( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            Some(Self::from_bigint(&BigInt::from(n), $SrcT::BITS))
+        }
+    )*}
+}
+
+impl ToPrimitive for BigFloat {
+    impl_bigfloat_to_primitive! {
+        fn to_isize -> isize;
+        fn to_i8 -> i8;
+        fn to_i16 -> i16;
+        fn to_i32 -> i32;
+        fn to_i64 -> i64;
+        fn to_i128 -> i128;
+        fn to_usize -> usize;
+        fn to_u8 -> u8;
+        fn to_u16 -> u16;
+        fn to_u32 -> u32;
+        fn to_u64 -> u64;
+        fn to_u128 -> u128;
+    }
+    /// Correctly rounded including subnormals
+    fn to_f32(&self) -> Option<f32> {
+        Some(self.to_binary_float(f32::MANTISSA_DIGITS, -149) as f32)
+    }
+    /// Correctly rounded including subnormals
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.to_binary_float(f64::MANTISSA_DIGITS, -1074))
+    }
+}
+
+/// Conversions are exact, the precision is the bit width of the source type
+impl FromPrimitive for BigFloat {
+    impl_bigfloat_from_primitive! {
+        fn from_isize(isize);
+        fn from_i8(i8);
+        fn from_i16(i16);
+        fn from_i32(i32);
+        fn from_i64(i64);
+        fn from_i128(i128);
+        fn from_usize(usize);
+        fn from_u8(u8);
+        fn from_u16(u16);
+        fn from_u32(u32);
+        fn from_u64(u64);
+        fn from_u128(u128);
+    }
+    fn from_f32(n: f32) -> Option<Self> {
+        Some(Self::from_f64_prec(n as f64, f32::MANTISSA_DIGITS))
+    }
+    fn from_f64(n: f64) -> Option<Self> {
+        Some(Self::from_f64_prec(n, f64::MANTISSA_DIGITS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PI_100: &str = "3.141592653589793238462643383279502884197169399375105820974944592307816406286208998628034825342117068";
+
+    fn parse(s: &str, prec: u32) -> BigFloat {
+        BigFloat::from_str_prec(s, prec).unwrap()
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(format!("{:.99}", BigFloat::pi(400)), format!("{}e0", &PI_100[..101]));
+        assert_eq!(BigFloat::pi(53).to_f64(), Some(std::f64::consts::PI));
+        assert_eq!(BigFloat::e(53).to_f64(), Some(std::f64::consts::E));
+        assert_eq!(BigFloat::ln_2(24).to_f32(), Some(std::f32::consts::LN_2));
+        assert_eq!(BigFloat::ln_10(53).to_f64(), Some(std::f64::consts::LN_10));
+        assert_eq!(format!("{:.29}", BigFloat::e(200)), "2.71828182845904523536028747135e0");
+    }
+
+    #[test]
+    fn test_arithmetic_rounding() {
+        // single operations at 53 bits must agree with the hardware
+        let xs = [1.0, 0.1, -3.75, 1e300, 2.5e-310, 7.0 / 3.0, -1e-5, 123456.789];
+        for &a in &xs {
+            for &b in &xs {
+                let (x, y) = (BigFloat::from_f64_prec(a, 53), BigFloat::from_f64_prec(b, 53));
+                assert_eq!((&x + &y).to_f64(), Some(a + b), "{a} + {b}");
+                assert_eq!((&x - &y).to_f64(), Some(a - b), "{a} - {b}");
+                assert_eq!((&x * &y).to_f64(), Some(a * b), "{a} * {b}");
+                assert_eq!((&x / &y).to_f64(), Some(a / b), "{a} / {b}");
+                assert_eq!((&x % &y).to_f64(), Some(a % b), "{a} % {b}");
+                assert_eq!(x.partial_cmp(&y), a.partial_cmp(&b));
+            }
+            let x = BigFloat::from_f64_prec(a.abs(), 53);
+            assert_eq!(x.sqrt().to_f64(), Some(a.abs().sqrt()));
+        }
+        let two = BigFloat::from_i64_prec(2, 300);
+        assert_eq!(two.sqrt().sq().with_prec(290), two);
+    }
+
+    #[test]
+    fn test_exp_log() {
+        let prec = 200;
+        let x = parse("0.7", prec);
+        assert!((x.exp().ln() - &x).abs() < BigFloat::one(prec).scale2(-195));
+        assert_eq!(format!("{:.39}", parse("-20.5", prec).exp()), "1.250152866386742628937553119231222182272e-9");
+        assert_eq!(format!("{:.39}", parse("1e-30", prec).exp_m1()), "1.000000000000000000000000000000500000000e-30");
+        assert_eq!(format!("{:.39}", parse("1e-30", prec).ln_1p()), "9.999999999999999999999999999995000000000e-31");
+        assert_eq!(format!("{:.39}", parse("3", prec).log10()), "4.771212547196624372950279032551153092001e-1");
+        assert_eq!(BigFloat::from_i64_prec(1024, 64).log2(), BigFloat::from_i64_prec(10, 64));
+        assert_eq!(parse("-0.5", 64).exp2().to_f64(), Some(std::f64::consts::FRAC_1_SQRT_2));
+        assert_eq!(parse("-2", 64).pow(&parse("3", 64)), BigFloat::from_i64_prec(-8, 64));
+        assert_eq!(parse("-27", 64).root(&parse("3", 64)).to_f64(), Some(-3.0));
+        assert_eq!(parse("2", 53).pow(&parse("0.5", 53)).to_f64(), Some(std::f64::consts::SQRT_2));
+        assert!(parse("-1", 53).ln().is_nan());
+        assert_eq!(BigFloat::zero(53).ln(), BigFloat::neg_infinity(53));
+    }
+
+    #[test]
+    fn test_rounding_and_casts() {
+        let xs = [2.5, -2.5, 0.5, -0.49, 3.0, -7.75, 1e20, 0.0];
+        for &a in &xs {
+            let x = BigFloat::from_f64_prec(a, 53);
+            assert_eq!(x.floor().to_f64(), Some(a.floor()));
+            assert_eq!(x.ceil().to_f64(), Some(a.ceil()));
+            assert_eq!(x.round().to_f64(), Some(a.round()));
+            assert_eq!(x.trunc().to_f64(), Some(a.trunc()));
+            assert_eq!(x.fract().to_f64(), Some(a.fract()));
+            assert_eq!(x.round_ties_even().to_f64(), Some(a.round_ties_even()));
+        }
+        assert_eq!(BigFloat::from_f64_prec(-300.7, 53).to_i8(), None);
+        assert_eq!(BigFloat::from_f64_prec(-100.7, 53).to_i8(), Some(-100));
+        assert_eq!(BigFloat::from_f64(0.5), Some(BigFloat::from_f64_prec(0.5, 53)));
+        assert_eq!(BigFloat::from_i64(-3).and_then(|x| x.to_i64()), Some(-3));
+        assert_eq!(parse("1e400", 64).to_f64(), Some(f64::INFINITY));
+        assert_eq!(parse("4.9406564584124654e-324", 64).to_f64(), Some(f64::from_bits(1)));
+        assert_eq!(parse("2.4703282292062328e-324", 64).to_f64(), Some(f64::from_bits(1)));
+        assert_eq!(BigFloat::from_f64_prec(f64::from_bits(1), 53).scale2(-1).to_f64(), Some(0.0));
+        assert_eq!(parse("0.1", 24).to_f32(), Some(0.1f32));
+        assert_eq!(parse("1.17549435e-38", 100).to_f32(), Some(f32::MIN_POSITIVE));
+        assert_eq!(format!("{}", parse("-1.5e-7", 53)), "-1.5e-7");
+        assert_eq!(BigFloat::from_str_prec("1.2.3", 53), Err(ParseBigFloatError::Invalid));
+    }
+
+    #[test]
+    fn test_parse_exponent_range() {
+        let out_of_range = Err(ParseBigFloatError::ExponentOutOfRange);
+        assert_eq!(BigFloat::from_str_prec("1e4294967296", 53), out_of_range);
+        assert_eq!(BigFloat::from_str_prec("1e-9223372036854775808", 53), out_of_range);
+        assert_eq!(BigFloat::from_str_prec(".5e-9223372036854775808", 53), out_of_range);
+        assert_eq!(BigFloat::from_str_prec("1e999999999", 53), out_of_range);
+        assert_eq!(BigFloat::from_str_prec("1e65537", 53), out_of_range);
+        assert_eq!(parse("1e65536", 53).to_f64(), Some(f64::INFINITY));
+        assert_eq!(parse("0.1e-65536", 53).to_f64(), Some(0.0));
+        assert!(BigFloat::from_str_prec("1e70000", 2048).is_ok());
+    }
+}
@@ -0,0 +1,1153 @@
+use std::cmp::Ordering;
+use std::fmt::{Binary, Display, Formatter, LowerHex, Octal, UpperHex};
+use std::ops::{Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign, Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, Shr, Sub, SubAssign};
+use std::str::FromStr;
+use thiserror::Error;
+use super::*;
+
+/// Arbitrary precision unsigned integer
+/// - little endian base 2^64 limbs without trailing zero limbs (zero has no limbs)
+/// - heap backed, so it is not `Copy` and can not be a `Unit`; it still provides the integer
+///   properties that do not need copy semantics (`OrderedReflexive`, `CastPrimitive`, bit ops)
+/// - subtraction below zero and division by zero panic like the primitive unsigned integers
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+/// Arbitrary precision signed integer (sign and magnitude)
+/// - bit ops behave as if the value was stored in infinite two's complement, like the primitives
+/// - division truncates towards zero like the primitives
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    mag: BigUint,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    #[error("cannot parse integer from empty string")]
+    Empty,
+    #[error("invalid digit found in string")]
+    InvalidDigit,
+    #[error("radix must be in 2..=36, got {0}")]
+    InvalidRadix(u32),
+}
+
+// Limb arithmetic =================================================================================
+
+fn normalize(limbs: &mut Vec<u64>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn cmp_limbs(a: &[u64], b: &[u64]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (s, c1) = x.overflowing_add(b.get(i).copied().unwrap_or(0));
+        let (s, c2) = s.overflowing_add(carry as u64);
+        out.push(s);
+        carry = c1 || c2;
+    }
+    if carry {
+        out.push(1);
+    }
+    out
+}
+
+/// a - b, requires a >= b
+fn sub_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (d, b1) = x.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        out.push(d);
+        borrow = b1 || b2;
+    }
+    debug_assert!(!borrow);
+    normalize(&mut out);
+    out
+}
+
+fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut out = vec![0u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u128 * y as u128 + out[i + j] as u128 + carry;
+            out[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        out[i + b.len()] = carry as u64;
+    }
+    normalize(&mut out);
+    out
+}
+
+fn div_rem_small(a: &[u64], d: u64) -> (Vec<u64>, u64) {
+    let mut q = vec![0u64; a.len()];
+    let mut r = 0u128;
+    for i in (0..a.len()).rev() {
+        let n = (r << 64) | a[i] as u128;
+        q[i] = (n / d as u128) as u64;
+        r = n % d as u128;
+    }
+    normalize(&mut q);
+    (q, r as u64)
+}
+
+fn shl_limbs(a: &[u64], bits: usize) -> Vec<u64> {
+    if a.is_empty() {
+        return Vec::new();
+    }
+    let (limbs, bits) = (bits / 64, (bits % 64) as u32);
+    let mut out = vec![0u64; limbs];
+    if bits == 0 {
+        out.extend_from_slice(a);
+    } else {
+        let mut carry = 0u64;
+        for &x in a {
+            out.push((x << bits) | carry);
+            carry = x >> (64 - bits);
+        }
+        out.push(carry);
+    }
+    normalize(&mut out);
+    out
+}
+
+fn shr_limbs(a: &[u64], bits: usize) -> Vec<u64> {
+    let (limbs, bits) = (bits / 64, (bits % 64) as u32);
+    if limbs >= a.len() {
+        return Vec::new();
+    }
+    let a = &a[limbs..];
+    let mut out: Vec<u64> = if bits == 0 {
+        a.to_vec()
+    } else {
+        (0..a.len())
+            .map(|i| (a[i] >> bits) | a.get(i + 1).map_or(0, |&hi| hi << (64 - bits)))
+            .collect()
+    };
+    normalize(&mut out);
+    out
+}
+
+/// Knuth's algorithm D (TAOCP 4.3.1), requires a non-zero divisor
+fn div_rem_limbs(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!b.is_empty(), "attempt to divide by zero");
+    if cmp_limbs(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (q, r) = div_rem_small(a, b[0]);
+        let mut r = vec![r];
+        normalize(&mut r);
+        return (q, r);
+    }
+    // normalize so the top divisor limb has its high bit set, which keeps the quotient estimate within 2 of the truth
+    let shift = b[b.len() - 1].leading_zeros() as usize;
+    let b = shl_limbs(b, shift);
+    let mut u = shl_limbs(a, shift);
+    u.resize(a.len() + 1, 0);
+    let n = b.len();
+    let m = u.len() - n;
+    let mut q = vec![0u64; m];
+    let (b_hi, b_lo) = (b[n - 1] as u128, b[n - 2] as u128);
+    for j in (0..m).rev() {
+        let num = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+        let mut qhat = num / b_hi;
+        let mut rhat = num % b_hi;
+        while qhat > u64::MAX as u128 || qhat * b_lo > ((rhat << 64) | u[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += b_hi;
+            if rhat > u64::MAX as u128 {
+                break;
+            }
+        }
+        // u[j..j+n+1] -= qhat * b
+        let mut borrow = 0i128;
+        let mut carry = 0u128;
+        for i in 0..n {
+            let p = qhat * b[i] as u128 + carry;
+            carry = p >> 64;
+            let t = u[i + j] as i128 - (p as u64) as i128 + borrow;
+            u[i + j] = t as u64;
+            borrow = t >> 64;
+        }
+        let t = u[j + n] as i128 - carry as i128 + borrow;
+        u[j + n] = t as u64;
+        if t < 0 {
+            // estimate was one too large, add the divisor back
+            qhat -= 1;
+            let mut carry = 0u128;
+            for i in 0..n {
+                let s = u[i + j] as u128 + b[i] as u128 + carry;
+                u[i + j] = s as u64;
+                carry = s >> 64;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
+        }
+        q[j] = qhat as u64;
+    }
+    normalize(&mut q);
+    u.truncate(n);
+    normalize(&mut u);
+    (q, shr_limbs(&u, shift))
+}
+
+/// Correctly rounded conversion, the bits below the top 64 are folded into a sticky bit
+fn limbs_to_f64(a: &[u64]) -> f64 {
+    let (top, shift) = limbs_top_sticky(a);
+    top as f64 * 2f64.powi(shift)
+}
+
+fn limbs_to_f32(a: &[u64]) -> f32 {
+    let (top, shift) = limbs_top_sticky(a);
+    top as f32 * 2f32.powi(shift)
+}
+
+/// (top 64 bits with the rest or-ed into the lowest bit, exponent of the lowest bit)
+fn limbs_top_sticky(a: &[u64]) -> (u64, i32) {
+    let bits = limbs_bits(a);
+    if bits <= 64 {
+        return (a.first().copied().unwrap_or(0), 0);
+    }
+    let shift = bits - 64;
+    let top = shr_limbs(a, shift as usize);
+    let sticky = shl_limbs(&top, shift as usize) != a;
+    (top[0] | sticky as u64, shift as i32)
+}
+
+fn limbs_bits(a: &[u64]) -> u64 {
+    a.last().map_or(0, |hi| a.len() as u64 * 64 - hi.leading_zeros() as u64)
+}
+
+fn digit_value(c: u8, radix: u32) -> Result<u64, ParseBigIntError> {
+    (c as char).to_digit(radix).map(|d| d as u64).ok_or(ParseBigIntError::InvalidDigit)
+}
+
+// BigUint =========================================================================================
+
+impl BigUint {
+    pub const ZERO: Self = BigUint { limbs: Vec::new() };
+
+    pub fn one() -> Self {
+        BigUint { limbs: vec![1] }
+    }
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+    /// Number of bits needed to represent the value (0 for zero)
+    pub fn bits(&self) -> u64 {
+        limbs_bits(&self.limbs)
+    }
+    /// Whether bit `i` is set
+    pub fn bit(&self, i: u64) -> bool {
+        self.limbs.get((i / 64) as usize).is_some_and(|limb| (limb >> (i % 64)) & 1 == 1)
+    }
+    /// Number of trailing zero bits, `None` for zero
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        let i = self.limbs.iter().position(|&limb| limb != 0)?;
+        Some(i as u64 * 64 + self.limbs[i].trailing_zeros() as u64)
+    }
+    /// Little endian base 2^64 digits
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+    pub fn from_limbs(limbs: Vec<u64>) -> Self {
+        let mut limbs = limbs;
+        normalize(&mut limbs);
+        BigUint { limbs }
+    }
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        match cmp_limbs(&self.limbs, &rhs.limbs) {
+            Ordering::Less => None,
+            _ => Some(BigUint { limbs: sub_limbs(&self.limbs, &rhs.limbs) }),
+        }
+    }
+    /// (quotient, remainder), panics on division by zero
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = div_rem_limbs(&self.limbs, &rhs.limbs);
+        (BigUint { limbs: q }, BigUint { limbs: r })
+    }
+    /// self^exp by repeated squaring
+    pub fn pow(&self, exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut exp = exp;
+        let mut acc = BigUint::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = &acc * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        acc
+    }
+    /// floor(sqrt(self)) via Newton's iteration
+    pub fn sqrt(&self) -> Self {
+        if self.is_zero() {
+            return BigUint::ZERO;
+        }
+        // 2^ceil(bits/2) is an upper bound, the iteration then decreases monotonically
+        let mut x = BigUint::one() << self.bits().div_ceil(2) as usize;
+        loop {
+            let y = (&x + &(self / &x)) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseBigIntError::InvalidRadix(radix));
+        }
+        let digits = s.strip_prefix('+').unwrap_or(s).as_bytes();
+        if digits.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+        // consume as many digits as fit in a limb at once
+        let (chunk, chunk_base) = radix_chunk(radix);
+        let mut limbs = Vec::new();
+        for part in digits.chunks(chunk) {
+            let mut value = 0u64;
+            for &c in part {
+                value = value * radix as u64 + digit_value(c, radix)?;
+            }
+            let scale = if part.len() == chunk { chunk_base } else { (radix as u64).pow(part.len() as u32) };
+            limbs = mul_small_add(&limbs, scale, value);
+        }
+        Ok(BigUint { limbs })
+    }
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36, got {radix}");
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        let (chunk, chunk_base) = radix_chunk(radix);
+        let mut chunks = Vec::new();
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (q, r) = div_rem_small(&rest, chunk_base);
+            chunks.push(r);
+            rest = q;
+        }
+        let mut out = String::with_capacity(chunks.len() * chunk);
+        for (i, &c) in chunks.iter().rev().enumerate() {
+            let mut digits = Vec::with_capacity(chunk);
+            let mut c = c;
+            while c > 0 {
+                digits.push(std::char::from_digit((c % radix as u64) as u32, radix).unwrap());
+                c /= radix as u64;
+            }
+            // every chunk but the leading one is zero padded to full width
+            if i > 0 {
+                digits.resize(chunk, '0');
+            }
+            out.extend(digits.iter().rev());
+        }
+        out
+    }
+}
+
+/// (digits per limb, radix^digits)
+fn radix_chunk(radix: u32) -> (usize, u64) {
+    let mut chunk = 1;
+    let mut base = radix as u64;
+    while let Some(next) = base.checked_mul(radix as u64) {
+        base = next;
+        chunk += 1;
+    }
+    (chunk, base)
+}
+
+/// a * m + c
+fn mul_small_add(a: &[u64], m: u64, c: u64) -> Vec<u64> {
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = c as u128;
+    for &x in a {
+        let t = x as u128 * m as u128 + carry;
+        out.push(t as u64);
+        carry = t >> 64;
+    }
+    out.push(carry as u64);
+    normalize(&mut out);
+    out
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_limbs(&self.limbs, &other.limbs)
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+/// (fmt trait, radix, prefix, uppercase)
+macro_rules! impl_bigint_fmt {
+    ($T:ident, $is_nonneg:expr, $mag:expr) => {
+        impl_bigint_fmt!(@impl $T, Display, 10, "", false, $is_nonneg, $mag);
+        impl_bigint_fmt!(@impl $T, LowerHex, 16, "0x", false, $is_nonneg, $mag);
+        impl_bigint_fmt!(@impl $T, UpperHex, 16, "0x", true, $is_nonneg, $mag);
+        impl_bigint_fmt!(@impl $T, Octal, 8, "0o", false, $is_nonneg, $mag);
+        impl_bigint_fmt!(@impl $T, Binary, 2, "0b", false, $is_nonneg, $mag);
+        impl std::fmt::Debug for $T {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(self, f)
+            }
+        }
+    };
+    (@impl $T:ident, $trait:ident, $radix:expr, $prefix:expr, $upper:expr, $is_nonneg:expr, $mag:expr) => {
+        impl $trait for $T {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                let digits = $mag(self).to_str_radix($radix);
+                let digits = if $upper { digits.to_uppercase() } else { digits };
+                f.pad_integral($is_nonneg(self), $prefix, &digits)
+            }
+        }
+    };
+}
+
+impl_bigint_fmt!(BigUint, |_: &BigUint| true, |x: &BigUint| -> BigUint { x.clone() });
+
+// BigInt ==========================================================================================
+
+impl BigInt {
+    pub const ZERO: Self = BigInt { negative: false, mag: BigUint::ZERO };
+
+    pub fn one() -> Self {
+        BigInt::from(BigUint::one())
+    }
+    /// Builds from a sign and magnitude, zero is always non-negative
+    pub fn from_parts(negative: bool, mag: BigUint) -> Self {
+        BigInt { negative: negative && !mag.is_zero(), mag }
+    }
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_zero()
+    }
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+    pub fn is_positive(&self) -> bool {
+        !self.negative && !self.mag.is_zero()
+    }
+    pub fn magnitude(&self) -> &BigUint {
+        &self.mag
+    }
+    pub fn to_biguint(&self) -> Option<BigUint> {
+        if self.negative { None } else { Some(self.mag.clone()) }
+    }
+    pub fn abs(&self) -> Self {
+        BigInt::from(self.mag.clone())
+    }
+    /// -1, 0 or 1
+    pub fn signum(&self) -> Self {
+        match (self.negative, self.is_zero()) {
+            (_, true) => BigInt::ZERO,
+            (true, _) => -BigInt::one(),
+            _ => BigInt::one(),
+        }
+    }
+    /// Number of bits of the magnitude
+    pub fn bits(&self) -> u64 {
+        self.mag.bits()
+    }
+    /// (quotient, remainder) truncated towards zero, panics on division by zero
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = self.mag.div_rem(&rhs.mag);
+        (BigInt::from_parts(self.negative != rhs.negative, q), BigInt::from_parts(self.negative, r))
+    }
+    pub fn pow(&self, exp: u32) -> Self {
+        BigInt::from_parts(self.negative && exp % 2 == 1, self.mag.pow(exp))
+    }
+    /// floor(sqrt(self)), panics on negative values like the primitive `isqrt`
+    pub fn sqrt(&self) -> Self {
+        assert!(!self.negative, "argument of integer square root cannot be negative");
+        BigInt::from(self.mag.sqrt())
+    }
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        match s.strip_prefix('-') {
+            Some(rest) if !rest.starts_with('+') => Ok(BigInt::from_parts(true, BigUint::from_str_radix(rest, radix)?)),
+            Some(_) => Err(ParseBigIntError::InvalidDigit),
+            None => Ok(BigInt::from(BigUint::from_str_radix(s, radix)?)),
+        }
+    }
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.mag.to_str_radix(radix);
+        if self.negative { format!("-{digits}") } else { digits }
+    }
+    /// Infinite two's complement limbs, sign extended to `len` limbs
+    fn to_twos(&self, len: usize) -> Vec<u64> {
+        let mut limbs = self.mag.limbs.clone();
+        limbs.resize(len, 0);
+        if self.negative {
+            let mut carry = true;
+            for limb in limbs.iter_mut() {
+                let (v, c) = (!*limb).overflowing_add(carry as u64);
+                *limb = v;
+                carry = c;
+            }
+        }
+        limbs
+    }
+    /// Applies a limb-wise bit op on the two's complement forms
+    fn zip_twos(&self, rhs: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        // one extra limb guarantees room for the sign bit
+        let len = self.mag.limbs.len().max(rhs.mag.limbs.len()) + 1;
+        let (a, b) = (self.to_twos(len), rhs.to_twos(len));
+        BigInt::from_twos(a.iter().zip(b.iter()).map(|(&x, &y)| op(x, y)).collect())
+    }
+    fn from_twos(mut limbs: Vec<u64>) -> Self {
+        let negative = limbs.last().is_some_and(|hi| hi >> 63 == 1);
+        if negative {
+            let mut carry = true;
+            for limb in limbs.iter_mut() {
+                let (v, c) = (!*limb).overflowing_add(carry as u64);
+                *limb = v;
+                carry = c;
+            }
+        }
+        BigInt::from_parts(negative, BigUint::from_limbs(limbs))
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(mag: BigUint) -> Self {
+        BigInt { negative: false, mag }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.mag.cmp(&other.mag),
+            (true, true) => other.mag.cmp(&self.mag),
+        }
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl_bigint_fmt!(BigInt, |x: &BigInt| !x.negative, |x: &BigInt| -> BigUint { x.mag.clone() });
+
+// std::ops implementations ========================================================================
+
+/// Derives the owned and assigning variants from the `&T op &T` implementation
+/// (type, trait, method, assign_trait, assign_method)
+macro_rules! forward_bigint_binop {
+    ($T:ident, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl $trait<$T> for $T {
+            type Output = $T;
+            fn $method(self, rhs: $T) -> $T {
+                (&self).$method(&rhs)
+            }
+        }
+        impl $trait<&$T> for $T {
+            type Output = $T;
+            fn $method(self, rhs: &$T) -> $T {
+                (&self).$method(rhs)
+            }
+        }
+        impl $trait<$T> for &$T {
+            type Output = $T;
+            fn $method(self, rhs: $T) -> $T {
+                self.$method(&rhs)
+            }
+        }
+        impl $assign_trait<$T> for $T {
+            fn $assign_method(&mut self, rhs: $T) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+        impl $assign_trait<&$T> for $T {
+            fn $assign_method(&mut self, rhs: &$T) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+    };
+}
+
+/// Shifts by a usize, plus by the integer itself as required by `Bitwise`
+/// (type, trait, method)
+macro_rules! forward_bigint_shift {
+    ($T:ident, $trait:ident, $method:ident) => {
+        impl $trait<usize> for $T {
+            type Output = $T;
+            fn $method(self, rhs: usize) -> $T {
+                (&self).$method(rhs)
+            }
+        }
+        impl $trait<$T> for $T {
+            type Output = $T;
+            fn $method(self, rhs: $T) -> $T {
+                let rhs = rhs.to_usize().expect("shift amount must be a non-negative usize");
+                (&self).$method(rhs)
+            }
+        }
+    };
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn add(self, rhs: &BigUint) -> BigUint {
+        BigUint { limbs: add_limbs(&self.limbs, &rhs.limbs) }
+    }
+}
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn sub(self, rhs: &BigUint) -> BigUint {
+        self.checked_sub(rhs).expect("attempt to subtract with overflow")
+    }
+}
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        BigUint { limbs: mul_limbs(&self.limbs, &rhs.limbs) }
+    }
+}
+impl Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn div(self, rhs: &BigUint) -> BigUint {
+        self.div_rem(rhs).0
+    }
+}
+impl Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn rem(self, rhs: &BigUint) -> BigUint {
+        self.div_rem(rhs).1
+    }
+}
+impl BitAnd<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn bitand(self, rhs: &BigUint) -> BigUint {
+        BigUint::from_limbs(self.limbs.iter().zip(rhs.limbs.iter()).map(|(a, b)| a & b).collect())
+    }
+}
+impl BitOr<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn bitor(self, rhs: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(rhs.limbs.len());
+        let limbs = (0..len)
+            .map(|i| self.limbs.get(i).copied().unwrap_or(0) | rhs.limbs.get(i).copied().unwrap_or(0))
+            .collect();
+        BigUint::from_limbs(limbs)
+    }
+}
+impl BitXor<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn bitxor(self, rhs: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(rhs.limbs.len());
+        let limbs = (0..len)
+            .map(|i| self.limbs.get(i).copied().unwrap_or(0) ^ rhs.limbs.get(i).copied().unwrap_or(0))
+            .collect();
+        BigUint::from_limbs(limbs)
+    }
+}
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+    fn shl(self, rhs: usize) -> BigUint {
+        BigUint { limbs: shl_limbs(&self.limbs, rhs) }
+    }
+}
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+    fn shr(self, rhs: usize) -> BigUint {
+        BigUint { limbs: shr_limbs(&self.limbs, rhs) }
+    }
+}
+
+forward_bigint_binop!(BigUint, Add, add, AddAssign, add_assign);
+forward_bigint_binop!(BigUint, Sub, sub, SubAssign, sub_assign);
+forward_bigint_binop!(BigUint, Mul, mul, MulAssign, mul_assign);
+forward_bigint_binop!(BigUint, Div, div, DivAssign, div_assign);
+forward_bigint_binop!(BigUint, Rem, rem, RemAssign, rem_assign);
+forward_bigint_binop!(BigUint, BitAnd, bitand, BitAndAssign, bitand_assign);
+forward_bigint_binop!(BigUint, BitOr, bitor, BitOrAssign, bitor_assign);
+forward_bigint_binop!(BigUint, BitXor, bitxor, BitXorAssign, bitxor_assign);
+forward_bigint_shift!(BigUint, Shl, shl);
+forward_bigint_shift!(BigUint, Shr, shr);
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn add(self, rhs: &BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, &self.mag + &rhs.mag);
+        }
+        match self.mag.cmp(&rhs.mag) {
+            Ordering::Less => BigInt::from_parts(rhs.negative, &rhs.mag - &self.mag),
+            _ => BigInt::from_parts(self.negative, &self.mag - &rhs.mag),
+        }
+    }
+}
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != rhs.negative, &self.mag * &rhs.mag)
+    }
+}
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn div(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).0
+    }
+}
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn rem(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).1
+    }
+}
+
+/// (trait, method, op)
+macro_rules! impl_bigint_bitop {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl $trait<&BigInt> for &BigInt {
+            type Output = BigInt;
+            fn $method(self, rhs: &BigInt) -> BigInt {
+                self.zip_twos(rhs, |x, y| x $op y)
+            }
+        }
+    };
+}
+
+impl_bigint_bitop!(BitAnd, bitand, &);
+impl_bigint_bitop!(BitOr, bitor, |);
+impl_bigint_bitop!(BitXor, bitxor, ^);
+
+impl Shl<usize> for &BigInt {
+    type Output = BigInt;
+    fn shl(self, rhs: usize) -> BigInt {
+        BigInt::from_parts(self.negative, &self.mag << rhs)
+    }
+}
+impl Shr<usize> for &BigInt {
+    type Output = BigInt;
+    /// Arithmetic shift, rounds towards negative infinity
+    fn shr(self, rhs: usize) -> BigInt {
+        if self.negative {
+            let mag = &(&(&self.mag - &BigUint::one()) >> rhs) + &BigUint::one();
+            BigInt::from_parts(true, mag)
+        } else {
+            BigInt::from(&self.mag >> rhs)
+        }
+    }
+}
+impl Neg for &BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.mag.clone())
+    }
+}
+impl Neg for BigInt {
+    type Output = BigInt;
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.mag)
+    }
+}
+impl Not for &BigInt {
+    type Output = BigInt;
+    /// !x == -x - 1
+    fn not(self) -> BigInt {
+        -(self + &BigInt::one())
+    }
+}
+impl Not for BigInt {
+    type Output = BigInt;
+    fn not(self) -> BigInt {
+        !&self
+    }
+}
+
+forward_bigint_binop!(BigInt, Add, add, AddAssign, add_assign);
+forward_bigint_binop!(BigInt, Sub, sub, SubAssign, sub_assign);
+forward_bigint_binop!(BigInt, Mul, mul, MulAssign, mul_assign);
+forward_bigint_binop!(BigInt, Div, div, DivAssign, div_assign);
+forward_bigint_binop!(BigInt, Rem, rem, RemAssign, rem_assign);
+forward_bigint_binop!(BigInt, BitAnd, bitand, BitAndAssign, bitand_assign);
+forward_bigint_binop!(BigInt, BitOr, bitor, BitOrAssign, bitor_assign);
+forward_bigint_binop!(BigInt, BitXor, bitxor, BitXorAssign, bitxor_assign);
+forward_bigint_shift!(BigInt, Shl, shl);
+forward_bigint_shift!(BigInt, Shr, shr);
+
+// Properties ======================================================================================
+
+/// (type)
+macro_rules! impl_bigint_properties {
+    ($T:ident) => {
+        impl Ordered for $T {
+            fn _min(self, other: Self) -> Self {
+                Ord::min(self, other)
+            }
+            fn _max(self, other: Self) -> Self {
+                Ord::max(self, other)
+            }
+            fn _clamp(self, min: Self, max: Self) -> Self {
+                Ord::clamp(self, min, max)
+            }
+        }
+        impl OrderedReflexive for $T {}
+    };
+}
+
+impl_bigint_properties!(BigUint);
+impl_bigint_properties!(BigInt);
+impl Bitwise for BigInt {}
+
+// Casting implementations =========================================================================
+
+macro_rules! impl_biguint_from {
+    ($( $SrcT:ident ),*) => {$(
+        impl From<$SrcT> for BigUint {
+            fn from(n: $SrcT) -> Self {
+                let n = n as u128;
+                BigUint::from_limbs(vec![n as u64, (n >> 64) as u64])
+            }
+        }
+        impl From<$SrcT> for BigInt {
+            fn from(n: $SrcT) -> Self {
+                BigInt::from(BigUint::from(n))
+            }
+        }
+    )*}
+}
+
+macro_rules! impl_bigint_from_signed {
+    ($( $SrcT:ident ),*) => {$(
+        impl From<$SrcT> for BigInt {
+            fn from(n: $SrcT) -> Self {
+                BigInt::from_parts(n < 0, BigUint::from(n.unsigned_abs()))
+            }
+        }
+    )*}
+}
+
+impl_biguint_from!(u8, u16, u32, u64, u128, usize);
+impl_bigint_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl BigUint {
+    fn to_u128_checked(&self) -> Option<u128> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u128),
+            2 => Some(self.limbs[0] as u128 | (self.limbs[1] as u128) << 64),
+            _ => None,
+        }
+    }
+    /// Truncates towards zero, `None` for NaN, infinities and values below zero
+    fn from_float_bits(n: f64) -> Option<Self> {
+        if !n.is_finite() || n <= -1.0 {
+            return None;
+        }
+        let n = n.trunc().abs();
+        if n == 0.0 {
+            return Some(BigUint::ZERO);
+        }
+        let bits = n.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i64 - 1075;
+        let man = (bits & ((1 << 52) - 1)) | (1 << 52);
+        let man = BigUint::from(man);
+        Some(if exp >= 0 { man << exp as usize } else { man >> (-exp) as usize })
+    }
+}
+
+macro_rules! impl_biguint_to_primitive {
+    ($( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            self.to_u128_checked()?.$method()
+        }
+    )*}
+}
+
+macro_rules! impl_bigint_to_primitive {
+    ($( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            if !self.negative {
+                return self.mag.$method();
+            }
+            let mag = self.mag.to_u128_checked()?;
+            if mag > 1 << 127 {
+                return None;
+            }
+            (mag as i128).wrapping_neg().$method()
+        }
+    )*}
+}
+
+macro_rules! impl_bigint_from_primitive {
+    ($T:ident : $( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            $T::try_from_int(n)
+        }
+    )*}
+}
+
+impl BigUint {
+    fn try_from_int<I: ToPrimitive>(n: I) -> Option<Self> {
+        n.to_u128().map(BigUint::from)
+    }
+}
+
+impl BigInt {
+    fn try_from_int<I: ToPrimitive>(n: I) -> Option<Self> {
+        match n.to_i128() {
+            Some(n) => Some(BigInt::from(n)),
+            None => n.to_u128().map(BigInt::from),
+        }
+    }
+}
+
+impl ToPrimitive for BigUint {
+    impl_biguint_to_primitive! {
+        fn to_isize -> isize;
+        fn to_i8 -> i8;
+        fn to_i16 -> i16;
+        fn to_i32 -> i32;
+        fn to_i64 -> i64;
+        fn to_i128 -> i128;
+        fn to_usize -> usize;
+        fn to_u8 -> u8;
+        fn to_u16 -> u16;
+        fn to_u32 -> u32;
+        fn to_u64 -> u64;
+        fn to_u128 -> u128;
+    }
+    /// Correctly rounded, saturates to infinity
+    fn to_f32(&self) -> Option<f32> {
+        Some(limbs_to_f32(&self.limbs))
+    }
+    /// Correctly rounded, saturates to infinity
+    fn to_f64(&self) -> Option<f64> {
+        Some(limbs_to_f64(&self.limbs))
+    }
+}
+
+impl ToPrimitive for BigInt {
+    impl_bigint_to_primitive! {
+        fn to_isize -> isize;
+        fn to_i8 -> i8;
+        fn to_i16 -> i16;
+        fn to_i32 -> i32;
+        fn to_i64 -> i64;
+        fn to_i128 -> i128;
+        fn to_usize -> usize;
+        fn to_u8 -> u8;
+        fn to_u16 -> u16;
+        fn to_u32 -> u32;
+        fn to_u64 -> u64;
+        fn to_u128 -> u128;
+    }
+    fn to_f32(&self) -> Option<f32> {
+        let v = limbs_to_f32(&self.mag.limbs);
+        Some(if self.negative { -v } else { v })
+    }
+    fn to_f64(&self) -> Option<f64> {
+        let v = limbs_to_f64(&self.mag.limbs);
+        Some(if self.negative { -v } else { v })
+    }
+}
+
+impl FromPrimitive for BigUint {
+    impl_bigint_from_primitive! { BigUint:
+        fn from_isize(isize);
+        fn from_i8(i8);
+        fn from_i16(i16);
+        fn from_i32(i32);
+        fn from_i64(i64);
+        fn from_i128(i128);
+        fn from_usize(usize);
+        fn from_u8(u8);
+        fn from_u16(u16);
+        fn from_u32(u32);
+        fn from_u64(u64);
+        fn from_u128(u128);
+    }
+    fn from_f32(n: f32) -> Option<Self> {
+        BigUint::from_float_bits(n as f64)
+    }
+    fn from_f64(n: f64) -> Option<Self> {
+        BigUint::from_float_bits(n)
+    }
+}
+
+impl FromPrimitive for BigInt {
+    impl_bigint_from_primitive! { BigInt:
+        fn from_isize(isize);
+        fn from_i8(i8);
+        fn from_i16(i16);
+        fn from_i32(i32);
+        fn from_i64(i64);
+        fn from_i128(i128);
+        fn from_usize(usize);
+        fn from_u8(u8);
+        fn from_u16(u16);
+        fn from_u32(u32);
+        fn from_u64(u64);
+        fn from_u128(u128);
+    }
+    fn from_f32(n: f32) -> Option<Self> {
+        Self::from_f64(n as f64)
+    }
+    /// Truncates towards zero, `None` for NaN and infinities
+    fn from_f64(n: f64) -> Option<Self> {
+        let mag = BigUint::from_float_bits(n.abs())?;
+        Some(BigInt::from_parts(n < 0.0, mag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// xorshift, deterministic operands for comparing against the primitives
+    fn operands() -> Vec<i128> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut out = vec![0, 1, -1, i64::MAX as i128, i64::MIN as i128, u64::MAX as i128, -(u64::MAX as i128)];
+        for _ in 0..200 {
+            let v = ((next() as i128) << 64 | next() as i128) >> (next() % 120);
+            out.push(v);
+        }
+        out
+    }
+
+    #[test]
+    fn test_arithmetic_matches_i128() {
+        let ops = operands();
+        for &a in &ops {
+            for &b in ops.iter().step_by(7) {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                if let Some(s) = a.checked_add(b) {
+                    assert_eq!((&x + &y).to_i128(), Some(s), "{a} + {b}");
+                }
+                if let Some(p) = a.checked_mul(b) {
+                    assert_eq!((&x * &y).to_i128(), Some(p), "{a} * {b}");
+                }
+                if let Some(s) = a.checked_sub(b) {
+                    assert_eq!((&x - &y).to_i128(), Some(s), "{a} - {b}");
+                }
+                if b != 0 {
+                    assert_eq!((&x / &y).to_i128(), Some(a / b), "{a} / {b}");
+                    assert_eq!((&x % &y).to_i128(), Some(a % b), "{a} % {b}");
+                }
+                assert_eq!((&x & &y).to_i128(), Some(a & b));
+                assert_eq!((&x | &y).to_i128(), Some(a | b));
+                assert_eq!((&x ^ &y).to_i128(), Some(a ^ b));
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+            }
+            let x = BigInt::from(a);
+            assert_eq!((!&x).to_i128(), Some(!a));
+            assert_eq!((&x >> 7).to_i128(), Some(a >> 7));
+        }
+    }
+
+    #[test]
+    fn test_long_division() {
+        // (2^200 + 12345) = q * (2^70 + 3) + r
+        let a = (BigUint::one() << 200) + BigUint::from(12345u32);
+        let b = (BigUint::one() << 70) + BigUint::from(3u32);
+        let (q, r) = a.div_rem(&b);
+        assert!(r < b);
+        assert_eq!(&q * &b + r, a);
+        let f = (1..=30u32).fold(BigUint::one(), |acc, n| acc * BigUint::from(n));
+        assert_eq!(f.to_string(), "265252859812191058636308480000000");
+        assert_eq!(&f / &(1..=28u32).fold(BigUint::one(), |acc, n| acc * BigUint::from(n)), BigUint::from(870u32));
+    }
+
+    #[test]
+    fn test_pow_sqrt() {
+        let x = BigInt::from(-3).pow(41);
+        assert_eq!(x.to_string(), "-36472996377170786403");
+        let y = BigUint::from(10u32).pow(40);
+        assert_eq!(y.sqrt(), BigUint::from(10u32).pow(20));
+        assert_eq!((&y - &BigUint::one()).sqrt(), BigUint::from(10u32).pow(20) - BigUint::one());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let s = "-123456789012345678901234567890123456789";
+        let x: BigInt = s.parse().unwrap();
+        assert_eq!(x.to_string(), s);
+        assert_eq!(format!("{:x}", BigUint::from(u128::MAX)), "ffffffffffffffffffffffffffffffff");
+        assert_eq!(format!("{:#X}", BigInt::from(-255)), "-0xFF");
+        assert_eq!(format!("{:b}", BigInt::from(10)), "1010");
+        assert_eq!(format!("{:>6}", BigInt::from(-42)), "   -42");
+        let h = BigInt::from_str_radix("-deadbeefcafebabe0123456789", 16).unwrap();
+        assert_eq!(BigInt::from_str_radix(&h.to_str_radix(16), 16).unwrap(), h);
+        assert_eq!(BigInt::from_str_radix(&h.to_str_radix(2), 2).unwrap(), h);
+        assert_eq!(BigUint::from_str_radix("", 10), Err(ParseBigIntError::Empty));
+        assert_eq!(BigUint::from_str_radix("12a", 10), Err(ParseBigIntError::InvalidDigit));
+        assert_eq!(BigUint::from_str_radix("-1", 10), Err(ParseBigIntError::InvalidDigit));
+        assert_eq!(BigInt::from_str_radix("-+1", 10), Err(ParseBigIntError::InvalidDigit));
+    }
+
+    #[test]
+    fn test_casts() {
+        let big = BigUint::one() << 100;
+        assert_eq!(big.to_u64(), None);
+        assert_eq!(big.to_f64(), Some(2f64.powi(100)));
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!((-BigInt::from(i128::MIN)).to_i128(), None);
+        assert_eq!(BigInt::from(-1).to_u8(), None);
+        assert_eq!(BigInt::from(-128).to_i8(), Some(-128));
+        assert_eq!(BigInt::from_f64(-1e20).unwrap().to_string(), "-100000000000000000000");
+        assert_eq!(BigUint::from_f64(-0.5), Some(BigUint::ZERO));
+        assert_eq!(BigUint::from_f64(f64::NAN), None);
+        // the sticky bit pushes the exact tie 2^80 + 2^27 up to the even neighbour 2^80 + 2^28
+        let x = (BigUint::one() << 80) + (BigUint::one() << 27) + BigUint::one();
+        assert_eq!(x.to_f64(), Some(2f64.powi(80) + 2f64.powi(28)));
+        assert_eq!(BigUint::from_u128(u128::MAX).unwrap().to_u128(), Some(u128::MAX));
+    }
+}
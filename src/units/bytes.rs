@@ -0,0 +1,204 @@
+use std::io::{self, Read, Write};
+use super::*;
+
+// Bulk byte serialization of units, bit-exact
+// - `n` values occupy `n * _BYTES` bytes, each value in the requested byte order
+// - `PlainBytes` units (seen through `_PLAIN_BYTES`) are copied as a whole when that order is the target's own
+// - the slice functions panic when the byte buffer is too short, like `copy_from_slice`
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl ByteOrder {
+    const NATIVE: Self = if cfg!(target_endian = "little") { ByteOrder::Little } else { ByteOrder::Big };
+
+    /// Proof that a slice of T is already laid out in this order
+    fn plain<T: PhysicalRepr>(self) -> Option<PlainBytesProof<T>> {
+        if self == Self::NATIVE { T::_PLAIN_BYTES } else { None }
+    }
+}
+
+/// Memory of a slice of plain values
+fn plain_bytes<T: PhysicalRepr>(values: &[T], _proof: PlainBytesProof<T>) -> &[u8] {
+    debug_assert_eq!(size_of::<T>(), T::_BYTES);
+    // SAFETY: `PlainBytes` units have no padding, so every byte of the slice is initialized
+    unsafe { std::slice::from_raw_parts(values.as_ptr().cast(), size_of_val(values)) }
+}
+
+/// Mutable memory of a slice of plain values
+fn plain_bytes_mut<T: PhysicalRepr>(values: &mut [T], _proof: PlainBytesProof<T>) -> &mut [u8] {
+    debug_assert_eq!(size_of::<T>(), T::_BYTES);
+    // SAFETY: as above, and any bytes written are a valid value of a `PlainBytes` unit
+    unsafe { std::slice::from_raw_parts_mut(values.as_mut_ptr().cast(), size_of_val(values)) }
+}
+
+fn encode<T: PhysicalRepr>(src: &[T], dst: &mut [u8], order: ByteOrder) -> usize {
+    let len = src.len() * T::_BYTES;
+    assert!(dst.len() >= len, "{} values need {len} bytes, the buffer has {}", src.len(), dst.len());
+    let dst = &mut dst[..len];
+    if let Some(proof) = order.plain() {
+        dst.copy_from_slice(plain_bytes(src, proof));
+        return len;
+    }
+    for (bytes, &x) in dst.chunks_exact_mut(T::_BYTES).zip(src) {
+        let repr = match order {
+            ByteOrder::Little => x._to_le_bytes(),
+            ByteOrder::Big => x._to_be_bytes(),
+        };
+        bytes.copy_from_slice(repr.as_ref());
+    }
+    len
+}
+
+fn decode<T: PhysicalRepr>(src: &[u8], dst: &mut [T], order: ByteOrder) -> usize {
+    let len = dst.len() * T::_BYTES;
+    assert!(src.len() >= len, "{} values need {len} bytes, the buffer has {}", dst.len(), src.len());
+    let src = &src[..len];
+    if let Some(proof) = order.plain() {
+        plain_bytes_mut(dst, proof).copy_from_slice(src);
+        return len;
+    }
+    for (x, bytes) in dst.iter_mut().zip(src.chunks_exact(T::_BYTES)) {
+        let Ok(repr) = T::BytesRepr::try_from(bytes) else {
+            unreachable!("chunks are exactly `_BYTES` long")
+        };
+        *x = match order {
+            ByteOrder::Little => T::_from_le_bytes(repr),
+            ByteOrder::Big => T::_from_be_bytes(repr),
+        };
+    }
+    len
+}
+
+/// Size of the staging buffer when bytes have to be reordered for io
+const STAGING_BYTES: usize = 4096;
+
+fn write<T: PhysicalRepr>(w: &mut impl Write, src: &[T], order: ByteOrder) -> io::Result<()> {
+    if let Some(proof) = order.plain() {
+        return w.write_all(plain_bytes(src, proof));
+    }
+    let per_chunk = (STAGING_BYTES / T::_BYTES).max(1);
+    let mut staging = vec![0; per_chunk * T::_BYTES];
+    for values in src.chunks(per_chunk) {
+        let len = encode(values, &mut staging, order);
+        w.write_all(&staging[..len])?;
+    }
+    Ok(())
+}
+
+fn read<T: PhysicalRepr>(r: &mut impl Read, dst: &mut [T], order: ByteOrder) -> io::Result<()> {
+    if let Some(proof) = order.plain() {
+        return r.read_exact(plain_bytes_mut(dst, proof));
+    }
+    let per_chunk = (STAGING_BYTES / T::_BYTES).max(1);
+    let mut staging = vec![0; per_chunk * T::_BYTES];
+    for values in dst.chunks_mut(per_chunk) {
+        let staging = &mut staging[..values.len() * T::_BYTES];
+        r.read_exact(staging)?;
+        decode(staging, values, order);
+    }
+    Ok(())
+}
+
+/// Writes `src` little endian to the start of `dst`, returns the number of bytes written
+pub fn encode_le<T: PhysicalRepr>(src: &[T], dst: &mut [u8]) -> usize {
+    encode(src, dst, ByteOrder::Little)
+}
+
+/// Writes `src` big endian to the start of `dst`, returns the number of bytes written
+pub fn encode_be<T: PhysicalRepr>(src: &[T], dst: &mut [u8]) -> usize {
+    encode(src, dst, ByteOrder::Big)
+}
+
+/// Fills `dst` from little endian bytes at the start of `src`, returns the number of bytes read
+pub fn decode_le<T: PhysicalRepr>(src: &[u8], dst: &mut [T]) -> usize {
+    decode(src, dst, ByteOrder::Little)
+}
+
+/// Fills `dst` from big endian bytes at the start of `src`, returns the number of bytes read
+pub fn decode_be<T: PhysicalRepr>(src: &[u8], dst: &mut [T]) -> usize {
+    decode(src, dst, ByteOrder::Big)
+}
+
+/// Writes all of `src` little endian
+pub fn write_le<T: PhysicalRepr>(w: &mut impl Write, src: &[T]) -> io::Result<()> {
+    write(w, src, ByteOrder::Little)
+}
+
+/// Writes all of `src` big endian
+pub fn write_be<T: PhysicalRepr>(w: &mut impl Write, src: &[T]) -> io::Result<()> {
+    write(w, src, ByteOrder::Big)
+}
+
+/// Fills `dst` from little endian bytes, like `read_exact` the contents of `dst` are unspecified on error
+pub fn read_le<T: PhysicalRepr>(r: &mut impl Read, dst: &mut [T]) -> io::Result<()> {
+    read(r, dst, ByteOrder::Little)
+}
+
+/// Fills `dst` from big endian bytes, like `read_exact` the contents of `dst` are unspecified on error
+pub fn read_be<T: PhysicalRepr>(r: &mut impl Read, dst: &mut [T]) -> io::Result<()> {
+    read(r, dst, ByteOrder::Big)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use super::*;
+
+    fn assert_roundtrip<T: PhysicalRepr + PartialEq>(values: &[T]) {
+        let mut bytes = vec![0; values.len() * T::_BYTES + 3];
+        let mut back = vec![T::_from_bits(<T::BitsRepr as Zero>::_ZERO); values.len()];
+        assert_eq!(encode_le(values, &mut bytes), values.len() * T::_BYTES);
+        decode_le(&bytes, &mut back);
+        assert_eq!(back, values);
+        back.fill(T::_from_bits(<T::BitsRepr as Zero>::_ZERO));
+        encode_be(values, &mut bytes);
+        decode_be(&bytes, &mut back);
+        assert_eq!(back, values);
+    }
+
+    #[test]
+    fn test_slices() {
+        let mut bytes = [0; 8];
+        encode_le(&[0x0102i16, -2], &mut bytes);
+        assert_eq!(bytes[..4], [0x02, 0x01, 0xfe, 0xff]);
+        encode_be(&[1.0f32], &mut bytes);
+        assert_eq!(bytes[..4], [0x3f, 0x80, 0, 0]);
+        let mut pcm = [Q15::_ZERO; 2];
+        assert_eq!(decode_be(&[0x40, 0x00, 0x80, 0x00, 0xaa], &mut pcm), 4);
+        assert_eq!(pcm, [Q15::from_raw(1 << 14), Q15::_MIN]);
+        let mut be = [0; 8];
+        encode_be(&[Complex::new(1.0f32, -1.0)], &mut be);
+        assert_eq!(be, [0xbf, 0x80, 0, 0, 0x3f, 0x80, 0, 0]);
+        assert!(f32::_PLAIN_BYTES.is_some() && Saturating::<i16>::_PLAIN_BYTES.is_some());
+        assert!(Rational::<i16>::_PLAIN_BYTES.is_none() && Dual::<f32>::_PLAIN_BYTES.is_none());
+        assert_roundtrip(&[0u8, 1, 255]);
+        assert_roundtrip(&[i128::MIN, -1, 7]);
+        assert_roundtrip(&[F16::from_f32(1.5), F16::_NEG_INFINITY]);
+        assert_roundtrip(&[Complex::new(1.0f64, -2.0), Complex::new(0.5, 0.25)]);
+        assert_roundtrip(&[Rational::new(-3i16, 4), Rational::new(5, 1)]);
+        assert_roundtrip(&[f32x4::from_array([1.0, 2.0, 3.0, 4.0])]);
+        assert_roundtrip(&[Saturating(-5i32)]);
+    }
+
+    #[test]
+    fn test_io() {
+        let samples: Vec<f32> = (0..3000).map(|i| i as f32 * 0.25 - 100.0).collect();
+        for big_endian in [false, true] {
+            let mut file = Vec::new();
+            if big_endian { write_be(&mut file, &samples) } else { write_le(&mut file, &samples) }.unwrap();
+            assert_eq!(file.len(), samples.len() * 4);
+            assert_eq!(file[..4], if big_endian { (-100f32).to_be_bytes() } else { (-100f32).to_le_bytes() });
+            let mut back = vec![0f32; samples.len()];
+            let mut cursor = Cursor::new(&file);
+            if big_endian { read_be(&mut cursor, &mut back) } else { read_le(&mut cursor, &mut back) }.unwrap();
+            assert_eq!(back, samples);
+            let mut too_many = vec![0f32; samples.len() + 1];
+            let err = read_le(&mut Cursor::new(&file), &mut too_many).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+}
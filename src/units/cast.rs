@@ -1,4 +1,7 @@
-﻿pub trait FromPrimitive: Copy {
+﻿use std::cmp::Ordering;
+use super::*;
+
+pub trait FromPrimitive: Sized {
     fn from_i64(n: i64) -> Option<Self>;
     fn from_u64(n: u64) -> Option<Self>;
     fn from_isize(n: isize) -> Option<Self>;
@@ -13,11 +16,9 @@
     fn from_u128(n: u128) -> Option<Self>;
     fn from_f32(n: f32) -> Option<Self>;
     fn from_f64(n: f64) -> Option<Self>;
-    // fn from_str(n: &str) -> Option<Self>;
-    // fn from(n: impl ToPrimitive) -> Option<Self>;
 }
 
-pub trait ToPrimitive: Copy {
+pub trait ToPrimitive {
     fn to_i64(&self) -> Option<i64>;
     fn to_u64(&self) -> Option<u64>;
     fn to_isize(&self) -> Option<isize>;
@@ -32,8 +33,6 @@ pub trait ToPrimitive: Copy {
     fn to_u128(&self) -> Option<u128>;
     fn to_f32(&self) -> Option<f32>;
     fn to_f64(&self) -> Option<f64>;
-    // fn to_str(&self) -> Option<String>;
-    // fn to(&self) -> Option<impl FromPrimitive>;
 }
 
 pub trait CastPrimitive: FromPrimitive + ToPrimitive {}
@@ -368,4 +367,232 @@ impl_from_primitive!(u32, to_u32);
 impl_from_primitive!(u64, to_u64);
 impl_from_primitive!(u128, to_u128);
 impl_from_primitive!(f32, to_f32);
-impl_from_primitive!(f64, to_f64);
\ No newline at end of file
+impl_from_primitive!(f64, to_f64);
+
+// Casting between units ===========================================================================
+
+/// Exact value of a scalar unit, every integer, float and fixed-point value is `mag * 2^exp` with a sign
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastValue {
+    Finite { negative: bool, mag: u128, exp: i32 },
+    Infinite { negative: bool },
+    Nan,
+}
+
+/// x * 2^n for a result that is representable
+fn ldexp(x: f64, n: i32) -> f64 {
+    x * 2f64.powi(n / 2) * 2f64.powi(n - n / 2)
+}
+
+/// `mag * 2^exp` as a multiple of 2^lsb, rounded by `rounding` (applied to the signed value), returns
+/// the low 128 bits of the multiple and whether it did not fit
+fn round_to_grid(negative: bool, mag: u128, exp: i32, lsb: i32, rounding: Rounding) -> (u128, bool) {
+    if exp >= lsb {
+        let shift = (exp - lsb) as u32;
+        return match shift {
+            _ if mag == 0 => (0, false),
+            0..128 => (mag << shift, mag.leading_zeros() < shift),
+            _ => (0, true),
+        };
+    }
+    let shift = (lsb - exp) as u32;
+    let (q, rest) = if shift < 128 { (mag >> shift, mag & ((1 << shift) - 1)) } else { (0, mag) };
+    let to_half = if shift <= 128 { rest.cmp(&(1 << (shift - 1))) } else { Ordering::Less };
+    let up = match rounding {
+        Rounding::Truncate => false,
+        Rounding::Floor => negative && rest != 0,
+        Rounding::Nearest => to_half != Ordering::Less,
+        Rounding::Convergent => to_half == Ordering::Greater || (to_half == Ordering::Equal && q & 1 == 1),
+    };
+    if up { q.overflowing_add(1) } else { (q, false) }
+}
+
+impl CastValue {
+    /// Exact value of a float
+    pub fn from_f64(x: f64) -> Self {
+        let negative = x.is_sign_negative();
+        if x.is_nan() {
+            return CastValue::Nan;
+        }
+        if x.is_infinite() {
+            return CastValue::Infinite { negative };
+        }
+        let bits = x.to_bits();
+        let (biased, fraction) = ((bits >> 52) & 0x7ff, bits & ((1 << 52) - 1));
+        let (mag, exp) = if biased == 0 { (fraction, -1074) } else { (fraction | 1 << 52, biased as i32 - 1075) };
+        CastValue::Finite { negative, mag: mag as u128, exp }
+    }
+    /// Rounded into a binary float format with `digits` significant bits and normal exponents in
+    /// `min_exp..=max_exp` (as `f64::MANTISSA_DIGITS` etc.), the result is exact in f64
+    /// - overflow is `None` when checked, the largest finite value when saturating and infinity when wrapping
+    pub fn to_float(self, digits: u32, min_exp: i32, max_exp: i32, rounding: Rounding, overflow: Option<Overflow>) -> Option<f64> {
+        let (negative, mag, exp) = match self {
+            CastValue::Finite { negative, mag, exp } => (negative, mag, exp),
+            CastValue::Infinite { negative } => return Some(if negative { f64::NEG_INFINITY } else { f64::INFINITY }),
+            CastValue::Nan => return Some(f64::NAN),
+        };
+        let sign = if negative { -1.0 } else { 1.0 };
+        if mag == 0 {
+            return Some(sign * 0.0);
+        }
+        // mag * 2^exp is in [2^(top-1), 2^top), keep `digits` bits but no more than a subnormal has
+        let top = exp + (128 - mag.leading_zeros()) as i32;
+        let lsb = (top - digits as i32).max(min_exp - digits as i32);
+        let (q, _) = round_to_grid(negative, mag, exp, lsb, rounding);
+        if q != 0 && (127 - q.leading_zeros()) as i32 + lsb >= max_exp {
+            let largest = ldexp(((1u64 << digits) - 1) as f64, max_exp - digits as i32);
+            return overflow.map(|overflow| match overflow {
+                Overflow::Saturate => sign * largest,
+                Overflow::Wrap => sign * f64::INFINITY,
+            });
+        }
+        Some(sign * ldexp(q as f64, lsb))
+    }
+    /// Rounded into `bits` bits of (signed) two's complement storage with `frac` fraction bits, as the
+    /// low bits of the result
+    /// - overflow is `None` when checked, clamps when saturating and keeps the low bits when wrapping
+    /// - NaN is `None` when checked and zero otherwise, infinities saturate unless checked
+    pub fn to_storage(self, bits: u32, signed: bool, frac: u32, rounding: Rounding, overflow: Option<Overflow>) -> Option<u128> {
+        let max = u128::MAX >> (128 - bits + signed as u32);
+        let min_mag = if signed { max + 1 } else { 0 };
+        let saturated = |negative: bool| if negative { min_mag.wrapping_neg() } else { max };
+        let (negative, mag, exp) = match self {
+            CastValue::Finite { negative, mag, exp } => (negative, mag, exp),
+            CastValue::Infinite { negative } => return overflow.map(|_| saturated(negative)),
+            CastValue::Nan => return overflow.map(|_| 0),
+        };
+        let (m, wrapped) = round_to_grid(negative, mag, exp, -(frac as i32), rounding);
+        let raw = if negative { m.wrapping_neg() } else { m };
+        if !wrapped && m <= if negative { min_mag } else { max } {
+            return Some(raw);
+        }
+        overflow.map(|overflow| match overflow {
+            Overflow::Saturate => saturated(negative),
+            Overflow::Wrap => raw,
+        })
+    }
+}
+
+/// Value preserving conversion between any two scalar units: integers, floats, halves and fixed point
+/// - `cast` is checked, `None` when the value is out of range (or NaN for a unit without NaN), values
+///   between two representable ones round the target's way: integers truncate like `as`, floats round
+///   to nearest even and fixed point to nearest
+/// - `cast_saturating` clamps to the range and `cast_wrapping` keeps the low order bits (floats
+///   overflow to infinity), both map NaN to zero for units without NaN (integers, fixed point) and
+///   keep it for floats and halves
+/// - e.g. `Q15::from_raw(pcm).cast::<f32>()` is i16 PCM to float and `x.cast_saturating::<Q15>()` back
+pub trait UnitCast: Copy {
+    /// Rounding of `cast`, `cast_saturating` and `cast_wrapping` into this unit
+    const _CAST_ROUNDING: Rounding;
+    fn _to_cast_value(self) -> CastValue;
+    /// Checked when `overflow` is `None`
+    fn _from_cast_value(v: CastValue, rounding: Rounding, overflow: Option<Overflow>) -> Option<Self>;
+
+    fn cast<U: UnitCast>(self) -> Option<U> {
+        U::_from_cast_value(self._to_cast_value(), U::_CAST_ROUNDING, None)
+    }
+    fn cast_saturating<U: UnitCast>(self) -> U {
+        U::_from_cast_value(self._to_cast_value(), U::_CAST_ROUNDING, Some(Overflow::Saturate)).unwrap()
+    }
+    fn cast_wrapping<U: UnitCast>(self) -> U {
+        U::_from_cast_value(self._to_cast_value(), U::_CAST_ROUNDING, Some(Overflow::Wrap)).unwrap()
+    }
+    /// Checked, with the given rounding
+    fn cast_rounding<U: UnitCast>(self, mode: Rounding) -> Option<U> {
+        U::_from_cast_value(self._to_cast_value(), mode, None)
+    }
+}
+
+/// (type, magnitude, signed)
+macro_rules! impl_unit_cast_int {
+    ($T:ident, $magnitude:path, $signed:literal) => {
+        impl UnitCast for $T {
+            const _CAST_ROUNDING: Rounding = Rounding::Truncate;
+            #[inline]
+            fn _to_cast_value(self) -> CastValue {
+                CastValue::Finite { negative: $signed && self.leading_zeros() == 0, mag: $magnitude(self) as u128, exp: 0 }
+            }
+            #[inline]
+            fn _from_cast_value(v: CastValue, rounding: Rounding, overflow: Option<Overflow>) -> Option<Self> {
+                v.to_storage($T::BITS, $signed, 0, rounding, overflow).map(|raw| raw as $T)
+            }
+        }
+    };
+}
+
+impl_unit_cast_int!(u8, std::convert::identity, false);
+impl_unit_cast_int!(u16, std::convert::identity, false);
+impl_unit_cast_int!(u32, std::convert::identity, false);
+impl_unit_cast_int!(u64, std::convert::identity, false);
+impl_unit_cast_int!(u128, std::convert::identity, false);
+impl_unit_cast_int!(usize, std::convert::identity, false);
+impl_unit_cast_int!(i8, i8::unsigned_abs, true);
+impl_unit_cast_int!(i16, i16::unsigned_abs, true);
+impl_unit_cast_int!(i32, i32::unsigned_abs, true);
+impl_unit_cast_int!(i64, i64::unsigned_abs, true);
+impl_unit_cast_int!(i128, i128::unsigned_abs, true);
+impl_unit_cast_int!(isize, isize::unsigned_abs, true);
+
+macro_rules! impl_unit_cast_float {
+    ($T:ident) => {
+        impl UnitCast for $T {
+            const _CAST_ROUNDING: Rounding = Rounding::Convergent;
+            #[inline]
+            fn _to_cast_value(self) -> CastValue {
+                CastValue::from_f64(self as f64)
+            }
+            #[inline]
+            fn _from_cast_value(v: CastValue, rounding: Rounding, overflow: Option<Overflow>) -> Option<Self> {
+                v.to_float($T::MANTISSA_DIGITS, $T::MIN_EXP, $T::MAX_EXP, rounding, overflow).map(|x| x as $T)
+            }
+        }
+    };
+}
+
+impl_unit_cast_float!(f32);
+impl_unit_cast_float!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_cast() {
+        let pcm = [i16::MIN, -1, 0, 1, 16384, i16::MAX];
+        let float = pcm.map(|x| Q15::from_raw(x).cast::<f32>().unwrap());
+        assert_eq!(float, [-1.0, -1.0 / 32768.0, 0.0, 1.0 / 32768.0, 0.5, 32767.0 / 32768.0]);
+        assert_eq!(float.map(|x| x.cast_saturating::<Q15>().to_raw()), pcm);
+        assert_eq!(1.0f32.cast::<Q15>(), None);
+        assert_eq!(1.0f32.cast_saturating::<Q15>(), Q15::_MAX);
+        assert_eq!(1.0f32.cast_wrapping::<Q15>(), Q15::_MIN);
+        assert_eq!(f32::NAN.cast_saturating::<Q15>(), Q15::_ZERO);
+        assert!(f64::NAN.cast_saturating::<f32>().is_nan());
+        assert!(f32::NAN.cast_wrapping::<F16>()._is_nan());
+        assert_eq!((-2.5f64).cast::<i8>(), Some(-2));
+        assert_eq!((-2.5f64).cast_rounding::<i8>(Rounding::Floor), Some(-3));
+        assert_eq!((-2.5f64).cast_rounding::<i8>(Rounding::Nearest), Some(-3));
+        assert_eq!((-2.5f64).cast_rounding::<i8>(Rounding::Convergent), Some(-2));
+        assert_eq!(300i32.cast::<u8>(), None);
+        assert_eq!(300i32.cast_saturating::<u8>(), 255);
+        assert_eq!(300i32.cast_wrapping::<u8>(), 44);
+        assert_eq!((-1i8).cast_saturating::<u128>(), 0);
+        assert_eq!((-1i8).cast_wrapping::<u128>(), u128::MAX);
+        assert_eq!(u128::MAX.cast::<f32>(), None);
+        assert_eq!(u128::MAX.cast_wrapping::<f32>(), f32::INFINITY);
+        assert_eq!(u128::MAX.cast::<f64>(), Some(2f64.powi(128)));
+        assert_eq!(f64::INFINITY.cast::<i64>(), None);
+        assert_eq!(f64::NEG_INFINITY.cast_wrapping::<i64>(), i64::MIN);
+        assert_eq!(1e300f64.cast::<f32>(), None);
+        assert_eq!(1e300f64.cast_saturating::<f32>(), f32::MAX);
+        assert_eq!(5e-324f64.cast::<f32>(), Some(0.0));
+        assert_eq!(16777217u32.cast_rounding::<f32>(Rounding::Nearest), Some(16777218.0));
+        assert_eq!(0.1f64.cast::<F16>(), Some(F16::from_f64(0.1)));
+        assert_eq!(F16::_MAX.cast::<u16>(), Some(65504));
+        assert_eq!(70000u32.cast::<F16>(), None);
+        assert_eq!(BF16::from_f32(-0.0).cast::<f64>().map(f64::is_sign_negative), Some(true));
+        assert_eq!(Q31::from_raw(1 << 30).cast::<Q15>(), Some(Q15::from_raw(1 << 14)));
+        assert_eq!(Q31::from_raw(1).cast::<Q15>(), Some(Q15::_ZERO));
+        assert_eq!(Saturating(200u8).cast::<i8>(), None);
+        assert_eq!(Saturating(-3i16).cast_saturating::<Saturating<u8>>(), Saturating(0));
+    }
+}
@@ -0,0 +1,464 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use super::*;
+
+/// Complex number over a floating point unit, stored as `re + im * i`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Hash)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T: Float> Complex<T> {
+    /// The imaginary unit
+    pub const I: Self = Complex { re: T::_ZERO, im: T::_ONE };
+
+    pub const fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+    /// Complex conjugate (re - im * i)
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+    /// Squared magnitude |z|^2, cheaper than `norm` when only comparing magnitudes
+    pub fn norm_sqr(self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+    /// Magnitude |z|, scaled to avoid intermediate overflow/underflow
+    pub fn norm(self) -> T {
+        let a = self.re._abs();
+        let b = self.im._abs();
+        let (big, small) = if a >= b { (a, b) } else { (b, a) };
+        if big._is_zero() || big == T::_INFINITY {
+            return big;
+        }
+        let r = small / big;
+        big * (T::_ONE + r * r)._sqrt()
+    }
+    /// Multiply both parts by a real scalar
+    pub fn scale(self, k: T) -> Self {
+        Complex::new(self.re * k, self.im * k)
+    }
+    /// Divide both parts by a real scalar
+    pub fn unscale(self, k: T) -> Self {
+        Complex::new(self.re / k, self.im / k)
+    }
+    pub fn is_real(self) -> bool {
+        self.im._is_zero()
+    }
+}
+
+impl<T: Float> From<T> for Complex<T> {
+    fn from(re: T) -> Self {
+        Complex::new(re, T::_ZERO)
+    }
+}
+
+// Unit implementations ============================================================================
+
+impl<T: Float> Unit for Complex<T> where Self: PhysicalRepr {}
+impl<T: Float> UnitOps for Complex<T> {}
+impl<T: Float> Zero for Complex<T> {
+    const _ZERO: Self = Complex { re: T::_ZERO, im: T::_ZERO };
+}
+impl<T: Float> One for Complex<T> {
+    const _ONE: Self = Complex { re: T::_ONE, im: T::_ZERO };
+}
+impl<T: Float> Inv for Complex<T> {
+    fn _inv(self) -> Self {
+        self._recip()
+    }
+}
+impl<T: Float> Symbolic for Complex<T> {
+    type Base = T;
+    fn _dismantle(self) -> Option<Self::Base> {
+        if self.im._is_zero() { Some(self.re) } else { None }
+    }
+}
+
+// std::ops implementations ========================================================================
+
+impl<T: Float> Add for Complex<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: Float> Sub for Complex<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Float> Mul for Complex<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T: Float> Div for Complex<T> {
+    type Output = Self;
+    /// Smith's algorithm, avoids overflow of |rhs|^2 for large divisors
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs.re._abs() >= rhs.im._abs() {
+            let r = rhs.im / rhs.re;
+            let d = rhs.re + rhs.im * r;
+            Complex::new((self.re + self.im * r) / d, (self.im - self.re * r) / d)
+        } else {
+            let r = rhs.re / rhs.im;
+            let d = rhs.re * r + rhs.im;
+            Complex::new((self.re * r + self.im) / d, (self.im * r - self.re) / d)
+        }
+    }
+}
+
+impl<T: Float> Rem for Complex<T> {
+    type Output = Self;
+    /// Remainder w.r.t the gaussian integer quotient truncated towards zero
+    fn rem(self, rhs: Self) -> Self::Output {
+        let q = self / rhs;
+        self - rhs * Complex::new(q.re._trunc(), q.im._trunc())
+    }
+}
+
+impl<T: Float> Neg for Complex<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+/// (trait, method, assign_trait, assign_method)
+macro_rules! impl_complex_assign_and_scalar {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        // Complex += Complex
+        impl<T: Float> $assign_trait for Complex<T> {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        // Complex + T
+        impl<T: Float> $trait<T> for Complex<T> {
+            type Output = Self;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$method(Complex::from(rhs))
+            }
+        }
+
+        // Complex += T
+        impl<T: Float> $assign_trait<T> for Complex<T> {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = (*self).$method(Complex::from(rhs));
+            }
+        }
+    };
+}
+
+impl_complex_assign_and_scalar!(Add, add, AddAssign, add_assign);
+impl_complex_assign_and_scalar!(Sub, sub, SubAssign, sub_assign);
+impl_complex_assign_and_scalar!(Mul, mul, MulAssign, mul_assign);
+impl_complex_assign_and_scalar!(Div, div, DivAssign, div_assign);
+
+impl<T: Float> ExpBasic for Complex<T> where Self: Unit {
+    type Output = Complex<T>;
+    fn _sq(self) -> <Self as ExpBasic>::Output {
+        self * self
+    }
+    /// Principal square root (branch cut along the negative real axis)
+    fn _sqrt(self) -> <Self as ExpBasic>::Output {
+        if self._is_zero() {
+            return self;
+        }
+        let two = T::_ONE + T::_ONE;
+        let t = ((self.re._abs() + self.norm()) / two)._sqrt();
+        if self.re._is_positive() {
+            Complex::new(t, self.im / (two * t))
+        } else {
+            let im = if self.im._is_negative() { -t } else { t };
+            Complex::new(self.im._abs() / (two * t), im)
+        }
+    }
+}
+
+impl<T: Float> Complex<T> {
+    /// Phase angle in (-pi, pi]
+    pub fn arg(self) -> T {
+        self.im._atan2(self.re)
+    }
+    /// (magnitude, phase)
+    pub fn to_polar(self) -> (T, T) {
+        (self.norm(), self.arg())
+    }
+    /// r * e^(i * theta)
+    pub fn from_polar(r: T, theta: T) -> Self {
+        Self::cis(theta).scale(r)
+    }
+    /// cos(theta) + i * sin(theta) = e^(i * theta)
+    pub fn cis(theta: T) -> Self {
+        let (sin, cos) = theta._sin_cos();
+        Complex::new(cos, sin)
+    }
+    /// self * i
+    fn mul_i(self) -> Self {
+        Complex::new(-self.im, self.re)
+    }
+    /// self * -i
+    fn mul_neg_i(self) -> Self {
+        Complex::new(self.im, -self.re)
+    }
+}
+
+impl<T: Float> ExpPowDynamic<Self> for Complex<T> where Self: Unit {
+    fn _pow(self, rhs: Self) -> <Self as ExpBasic>::Output {
+        if self._is_zero() {
+            return if rhs._is_zero() { Self::_ONE } else { Self::_ZERO };
+        }
+        (rhs * self._ln())._exp()
+    }
+}
+
+impl<T: Float> ExpPowDynamic<T> for Complex<T> where Self: Unit {
+    fn _pow(self, rhs: T) -> <Self as ExpBasic>::Output {
+        if self._is_zero() {
+            return if rhs._is_zero() { Self::_ONE } else { Self::_ZERO };
+        }
+        let (r, theta) = self.to_polar();
+        Self::from_polar(r._pow(rhs), theta * rhs)
+    }
+}
+
+impl<T: Float> ExpRootDynamic<Self> for Complex<T> where Self: Unit {
+    fn _root(self, n: Self) -> <Self as ExpBasic>::Output {
+        self._pow(n._recip())
+    }
+}
+
+impl<T: Float> ExpFloat for Complex<T> where Self: Unit {
+    fn _exp(self) -> <Self as ExpBasic>::Output {
+        Self::from_polar(self.re._exp(), self.im)
+    }
+    fn _exp2(self) -> <Self as ExpBasic>::Output {
+        self.scale((T::_ONE + T::_ONE)._ln())._exp()
+    }
+    fn _exp_m1(self) -> <Self as ExpBasic>::Output {
+        // cos(im) - 1 = -2sin^2(im/2) keeps precision for small arguments
+        let two = T::_ONE + T::_ONE;
+        let (sin, cos) = self.im._sin_cos();
+        let half_sin = (self.im / two)._sin();
+        Complex::new(
+            self.re._exp_m1() * cos - two * half_sin * half_sin,
+            self.re._exp() * sin,
+        )
+    }
+    fn _log(self, base: Self) -> <Self as ExpBasic>::Output {
+        self._ln() / base._ln()
+    }
+    fn _log2(self) -> <Self as ExpBasic>::Output {
+        self._ln().scale(T::_E._log2())
+    }
+    fn _log10(self) -> <Self as ExpBasic>::Output {
+        self._ln().scale(T::_E._log10())
+    }
+    fn _ln(self) -> <Self as ExpBasic>::Output {
+        Complex::new(self.norm()._ln(), self.arg())
+    }
+    fn _ln_1p(self) -> <Self as ExpBasic>::Output {
+        (self + Self::_ONE)._ln()
+    }
+}
+
+impl<T: Float> Trig for Complex<T> where Self: Unit {
+    fn _sin(self) -> Self {
+        let (sin, cos) = self.re._sin_cos();
+        Complex::new(sin * self.im._cosh(), cos * self.im._sinh())
+    }
+    fn _cos(self) -> Self {
+        let (sin, cos) = self.re._sin_cos();
+        Complex::new(cos * self.im._cosh(), -sin * self.im._sinh())
+    }
+    fn _tan(self) -> Self {
+        let (re, im) = (self.re + self.re, self.im + self.im);
+        let d = re._cos() + im._cosh();
+        Complex::new(re._sin() / d, im._sinh() / d)
+    }
+    /// -i * ln(i * z + sqrt(1 - z^2))
+    fn _asin(self) -> Self {
+        (self.mul_i() + (Self::_ONE - self * self)._sqrt())._ln().mul_neg_i()
+    }
+    /// -i * ln(z + i * sqrt(1 - z^2))
+    fn _acos(self) -> Self {
+        (self + (Self::_ONE - self * self)._sqrt().mul_i())._ln().mul_neg_i()
+    }
+    /// i/2 * (ln(1 - i * z) - ln(1 + i * z))
+    fn _atan(self) -> Self {
+        let iz = self.mul_i();
+        ((Self::_ONE - iz)._ln() - (Self::_ONE + iz)._ln()).mul_i().unscale(T::_ONE + T::_ONE)
+    }
+    /// -i * ln((x + i * y) / sqrt(x^2 + y^2)), agrees with the real atan2 on the real line
+    fn _atan2(self, other: Self) -> Self {
+        let z = other + self.mul_i();
+        (z / (other * other + self * self)._sqrt())._ln().mul_neg_i()
+    }
+    fn _sinh(self) -> Self {
+        let (sin, cos) = self.im._sin_cos();
+        Complex::new(self.re._sinh() * cos, self.re._cosh() * sin)
+    }
+    fn _cosh(self) -> Self {
+        let (sin, cos) = self.im._sin_cos();
+        Complex::new(self.re._cosh() * cos, self.re._sinh() * sin)
+    }
+    fn _tanh(self) -> Self {
+        let (re, im) = (self.re + self.re, self.im + self.im);
+        let d = re._cosh() + im._cos();
+        Complex::new(re._sinh() / d, im._sin() / d)
+    }
+    /// ln(z + sqrt(z^2 + 1))
+    fn _asinh(self) -> Self {
+        (self + (self * self + Self::_ONE)._sqrt())._ln()
+    }
+    /// ln(z + sqrt(z + 1) * sqrt(z - 1))
+    fn _acosh(self) -> Self {
+        (self + (self + Self::_ONE)._sqrt() * (self - Self::_ONE)._sqrt())._ln()
+    }
+    /// 1/2 * (ln(1 + z) - ln(1 - z))
+    fn _atanh(self) -> Self {
+        ((Self::_ONE + self)._ln() - (Self::_ONE - self)._ln()).unscale(T::_ONE + T::_ONE)
+    }
+    fn _hypot(self, other: Self) -> Self {
+        (self * self + other * other)._sqrt()
+    }
+    fn _to_degrees(self) -> Self {
+        self.scale(T::_ONE._to_degrees())
+    }
+    fn _to_radians(self) -> Self {
+        self.scale(T::_ONE._to_radians())
+    }
+}
+
+// Concrete implementations ========================================================================
+
+macro_rules! impl_complex {
+    ($SrcT:ident, $SrcReprT:ident) => {
+        // SAFETY: `repr(C)` over two floats of the same type, so there is no padding, and on little
+        // endian targets the native bytes hold `re` in the low half like `_to_ne_bytes`
+        #[cfg(target_endian = "little")]
+        unsafe impl PlainBytes for Complex<$SrcT> {}
+        impl PhysicalRepr for Complex<$SrcT> {
+            const _BITS: u32 = 2 * $SrcT::_BITS;
+            const _BYTES: usize = 2 * $SrcT::_BYTES;
+            /// `re` comes first in memory, which is the low half only on little endian targets
+            #[cfg(target_endian = "little")]
+            const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = Some(PlainBytesProof::PROOF);
+            /// `re` is packed into the low half and `im` into the high half
+            type BitsRepr = $SrcReprT;
+            type BytesRepr = [u8; 2 * size_of::<$SrcT>()];
+            fn _from_bits(v: Self::BitsRepr) -> Self {
+                let re = $SrcT::_from_bits(v as <$SrcT as PhysicalRepr>::BitsRepr);
+                let im = $SrcT::_from_bits((v >> $SrcT::_BITS) as <$SrcT as PhysicalRepr>::BitsRepr);
+                Complex::new(re, im)
+            }
+            fn _to_bits(self) -> Self::BitsRepr {
+                (self.re._to_bits() as $SrcReprT) | ((self.im._to_bits() as $SrcReprT) << $SrcT::_BITS)
+            }
+            fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_be_bytes(bytes))
+            }
+            fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_le_bytes(bytes))
+            }
+            fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_ne_bytes(bytes))
+            }
+            fn _to_be_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_be_bytes()
+            }
+            fn _to_le_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_le_bytes()
+            }
+            fn _to_ne_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_ne_bytes()
+            }
+        }
+    };
+}
+
+impl_complex!(f32, u64);
+impl_complex!(f64, u128);
+impl_complex!(F16, u32);
+impl_complex!(BF16, u32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Complex<f64>, b: Complex<f64>) -> bool {
+        (a - b).norm() < 1e-12
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(3.0, -4.0);
+        assert_eq!(a + b, Complex::new(4.0, -2.0));
+        assert_eq!(a * b, Complex::new(11.0, 2.0));
+        assert!(close((a * b) / b, a));
+        assert_eq!(a * Complex::<f64>::_ONE, a);
+        assert!(close(a._inv()._inv(), a));
+        assert_eq!(Complex::<f64>::I * Complex::I, -Complex::<f64>::_ONE);
+    }
+
+    #[test]
+    fn test_dismantle() {
+        assert_eq!(Complex::new(2.5f32, 0.0)._dismantle(), Some(2.5));
+        assert_eq!(Complex::new(2.5f32, 1.0)._dismantle(), None);
+    }
+
+    #[test]
+    fn test_repr_roundtrip() {
+        let z = Complex::new(1.5f32, -0.25);
+        assert_eq!(Complex::<f32>::_from_bits(z._to_bits()), z);
+        assert_eq!(Complex::<f32>::_from_le_bytes(z._to_le_bytes()), z);
+        assert_eq!(Complex::<f64>::_from_be_bytes(Complex::new(1.5, -0.25)._to_be_bytes()), Complex::new(1.5, -0.25));
+        assert_eq!(&z._to_le_bytes()[..4], &1.5f32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_polar_and_exp() {
+        let z = Complex::<f64>::from_polar(2.0, f64::_PI / 3.0);
+        assert!((z.norm() - 2.0).abs() < 1e-12);
+        assert!((z.arg() - f64::_PI / 3.0).abs() < 1e-12);
+        assert!(close(Complex::new(0.0, f64::_PI)._exp(), Complex::new(-1.0, 0.0)));
+        let w = Complex::new(0.3, -1.7);
+        assert!(close(w._ln()._exp(), w));
+        assert!(close(w._sqrt()._sq(), w));
+        assert!(close(w._pow(Complex::new(2.0, 0.0)), w * w));
+        assert!(close(w._pow(3.0), w * w * w));
+        assert!(close(Complex::new(-4.0, 0.0)._sqrt(), Complex::new(0.0, 2.0)));
+    }
+
+    #[test]
+    fn test_trig() {
+        let z = Complex::new(0.4, -0.9);
+        assert!(close(z._sin()._asin(), z));
+        assert!(close(z._cos()._acos(), z));
+        assert!(close(z._tan()._atan(), z));
+        assert!(close(z._sinh()._asinh(), z));
+        assert!(close(z._cosh()._acosh(), z));
+        assert!(close(z._tanh()._atanh(), z));
+        let (sin, cos) = z._sin_cos();
+        assert!(close(sin * sin + cos * cos, Complex::_ONE));
+        assert!(close(z._tan(), sin / cos));
+        let real = Complex::new(0.7, 0.0);
+        assert!(close(real._sin(), Complex::from(0.7f64.sin())));
+        let (y, x) = (Complex::from(-1.0), Complex::from(-2.0));
+        assert!(close(y._atan2(x), Complex::from((-1.0f64).atan2(-2.0))));
+        assert!(close(Complex::from(3.0)._hypot(Complex::from(4.0)), Complex::from(5.0)));
+    }
+}
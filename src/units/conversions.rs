@@ -0,0 +1,102 @@
+use super::*;
+
+/// Level, pitch and frequency conversions common in audio code, available on every `Float`
+/// - levels: decibels of amplitude (20 log10), so 0 dB is unity gain and -inf dB is silence
+/// - pitch: MIDI note numbers tuned to A4 = 69 = 440 Hz in 12 tone equal temperament, cents are
+///   hundredths of a semitone
+/// - frequency: angular frequency in radians per sample, given the sample rate in Hz
+pub trait Conversions: Float {
+    /// 10^(self / 20), -inf dB maps to a gain of exactly zero
+    fn _db_to_gain(self) -> Self {
+        if self == Self::_NEG_INFINITY {
+            return Self::_ZERO;
+        }
+        (self * lit(LOG2_10 / 20.0))._exp2()
+    }
+    /// 20 log10(abs(self)), the sign is ignored and a zero gain maps to -inf dB
+    fn _gain_to_db(self) -> Self {
+        self._abs()._log10() * lit(20.0)
+    }
+    /// 440 * 2^((self - 69) / 12)
+    fn _midi_to_hz(self) -> Self {
+        ((self - lit(69.0)) / lit(12.0))._exp2() * lit(440.0)
+    }
+    /// 69 + 12 log2(self / 440)
+    fn _hz_to_midi(self) -> Self {
+        (self / lit(440.0))._log2() * lit(12.0) + lit(69.0)
+    }
+    /// 2 pi self / sample_rate
+    fn _hz_to_omega(self, sample_rate: Self) -> Self {
+        self * Self::_TAU / sample_rate
+    }
+    /// self sample_rate / 2 pi
+    fn _omega_to_hz(self, sample_rate: Self) -> Self {
+        self * sample_rate / Self::_TAU
+    }
+    /// Interval from `reference` up to self in cents, 1200 log2(self / reference)
+    fn _cents(self, reference: Self) -> Self {
+        (self / reference)._log2() * lit(1200.0)
+    }
+    /// Frequency ratio of an interval in cents, 2^(self / 1200)
+    fn _cents_to_ratio(self) -> Self {
+        (self / lit(1200.0))._exp2()
+    }
+}
+
+impl<T: Float> Conversions for T {}
+
+const LOG2_10: f64 = std::f64::consts::LOG2_10;
+
+/// A constant of the conversions in the unit
+#[inline]
+fn lit<T: Float>(v: f64) -> T {
+    T::from_f64(v).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roundtrip<T: Float>(xs: impl Iterator<Item = f64>, there: fn(T) -> T, back: fn(T) -> T, tol: f64) {
+        for x in xs {
+            let y = back(there(lit(x))).to_f64().unwrap();
+            assert!((y - x).abs() <= tol * x.abs().max(1.0), "{x} came back as {y}");
+        }
+    }
+
+    #[test]
+    fn test_anchors() {
+        assert_eq!(0f64._db_to_gain(), 1.0);
+        assert_eq!(f64::NEG_INFINITY._db_to_gain(), 0.0);
+        assert_eq!(0f32._gain_to_db(), f32::NEG_INFINITY);
+        assert_eq!(1f64._gain_to_db(), 0.0);
+        assert_eq!((-10f64)._gain_to_db(), 20.0);
+        assert_eq!(100f32._gain_to_db(), 40.0);
+        assert!((20f64._db_to_gain() - 10.0).abs() < 1e-14);
+        assert!((6.0206f64._db_to_gain() - 2.0).abs() < 1e-5);
+        assert_eq!(69f64._midi_to_hz(), 440.0);
+        assert_eq!(81f32._midi_to_hz(), 880.0);
+        assert_eq!(57f64._midi_to_hz(), 220.0);
+        assert_eq!(440f64._hz_to_midi(), 69.0);
+        assert_eq!(1760f32._hz_to_midi(), 93.0);
+        assert_eq!(12000f64._hz_to_omega(48000.0), std::f64::consts::FRAC_PI_2);
+        assert_eq!(880f64._cents(440.0), 1200.0);
+        assert_eq!(1200f32._cents_to_ratio(), 2.0);
+    }
+
+    #[test]
+    fn test_inverses() {
+        let steps = |lo: f64, hi: f64| (0..=1000).map(move |i| lo + (hi - lo) * i as f64 / 1000.0);
+        assert_roundtrip::<f64>(steps(-140.0, 40.0), f64::_db_to_gain, f64::_gain_to_db, 1e-13);
+        assert_roundtrip::<f64>(steps(1e-7, 10.0), f64::_gain_to_db, f64::_db_to_gain, 1e-13);
+        assert_roundtrip::<f64>(steps(0.0, 127.0), f64::_midi_to_hz, f64::_hz_to_midi, 1e-13);
+        assert_roundtrip::<f64>(steps(20.0, 20000.0), f64::_hz_to_midi, f64::_midi_to_hz, 1e-13);
+        assert_roundtrip::<f64>(steps(-2400.0, 2400.0), f64::_cents_to_ratio, |r| r._cents(1.0), 1e-13);
+        assert_roundtrip::<f32>(steps(-100.0, 24.0), f32::_db_to_gain, f32::_gain_to_db, 1e-5);
+        assert_roundtrip::<f32>(steps(0.0, 127.0), f32::_midi_to_hz, f32::_hz_to_midi, 1e-5);
+        assert_roundtrip::<f64>(steps(0.0, 24000.0), |f| f._hz_to_omega(48000.0), |w| w._omega_to_hz(48000.0), 1e-15);
+        let v = f32x4::from_array([0.0, -6.0, -12.0, f32::NEG_INFINITY])._db_to_gain();
+        assert_eq!(v.to_array(), [0.0, -6.0, -12.0, f32::NEG_INFINITY].map(f32::_db_to_gain));
+        assert_eq!(v[3], 0.0);
+    }
+}
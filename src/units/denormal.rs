@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+use super::*;
+
+/// Protection against subnormal (denormal) values, which are slow on most CPUs and tend to show up in
+/// the decaying tails of recursive filters
+/// - `_flush_denormal` per value, e.g. on a feedback path
+/// - `DenormalGuard` flushes in hardware for a whole processing block where supported
+pub trait Denormal: Float {
+    /// Subnormal values become zero of the same sign
+    fn _flush_denormal(self) -> Self {
+        if self._is_subnormal() { Self::_from_bits(self._to_bits() & Self::_SIGN_MASK) } else { self }
+    }
+}
+
+impl Denormal for f32 {}
+impl Denormal for f64 {}
+impl Denormal for F16 {}
+impl Denormal for BF16 {}
+
+/// The value and every partial are flushed on their own
+impl<T: Denormal, const N: usize> Denormal for DualN<T, N> {
+    fn _flush_denormal(self) -> Self {
+        DualN::new(self.val._flush_denormal(), self.grad.map(T::_flush_denormal))
+    }
+}
+
+/// Flushes every value of `buf`
+pub fn flush_denormals<T: Denormal>(buf: &mut [T]) {
+    buf.iter_mut().for_each(|x| *x = x._flush_denormal());
+}
+
+#[cfg(target_arch = "x86_64")]
+mod mxcsr {
+    use std::arch::asm;
+
+    /// Flush to zero, results that would be subnormal are zero
+    pub const FTZ: u32 = 1 << 15;
+    /// Denormals are zero, subnormal operands read as zero
+    pub const DAZ: u32 = 1 << 6;
+
+    pub fn get() -> u32 {
+        let mut csr = 0u32;
+        // SAFETY: stores the control register into a local
+        unsafe { asm!("stmxcsr [{}]", in(reg) &mut csr, options(nostack, preserves_flags)) };
+        csr
+    }
+
+    /// # Safety
+    /// `csr` must be a value read by `get` with at most the FTZ / DAZ bits changed, and the caller
+    /// upholds the contract of `DenormalGuard::new` for as long as the changed mode is active
+    pub unsafe fn set(csr: u32) {
+        // SAFETY: every x86_64 CPU supports FTZ and DAZ so the value is valid, the floating point
+        // environment the compiler assumes is the caller's responsibility
+        unsafe { asm!("ldmxcsr [{}]", in(reg) &csr, options(nostack, readonly, preserves_flags)) };
+    }
+}
+
+/// Flush to zero and denormals are zero on the current thread while alive, the previous mode is
+/// restored on drop
+/// - x86_64: sets FTZ and DAZ in MXCSR, which covers all SSE/AVX float arithmetic
+/// - elsewhere a no-op (see `DenormalGuard::IS_SUPPORTED`), flush feedback paths with `_flush_denormal`
+/// - the mode is per thread, so the guard is neither `Send` nor `Sync`
+///
+/// The compiler assumes the default floating point environment, so with the mode changed any
+/// arithmetic it evaluated at compile time (constant folding, `const` items) may disagree with the
+/// same arithmetic done at run time, and any code relying on exact IEEE subnormal results silently
+/// misbehaves. This is why `_mm_setcsr` is deprecated and why `new` is `unsafe`: keep the guard
+/// scoped to numeric kernels that tolerate flushed results.
+#[must_use = "denormals are only flushed while the guard is alive"]
+#[derive(Debug)]
+pub struct DenormalGuard {
+    #[cfg(target_arch = "x86_64")]
+    saved: u32,
+    _thread: PhantomData<*const ()>,
+}
+
+impl DenormalGuard {
+    /// Whether the guard changes the floating point mode on this target
+    pub const IS_SUPPORTED: bool = cfg!(target_arch = "x86_64");
+
+    /// # Safety
+    /// While the guard is alive the current thread must only run code that tolerates subnormal
+    /// values being read and produced as zero, including code the compiler may have constant
+    /// folded under the default floating point environment
+    pub unsafe fn new() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            let saved = mxcsr::get();
+            // SAFETY: only FTZ / DAZ are added to a value read by `get`, the caller accepts the
+            // changed mode
+            unsafe { mxcsr::set(saved | mxcsr::FTZ | mxcsr::DAZ) };
+            DenormalGuard { saved, _thread: PhantomData }
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        DenormalGuard { _thread: PhantomData }
+    }
+}
+
+impl Drop for DenormalGuard {
+    fn drop(&mut self) {
+        // SAFETY: restores the mode that was active before the guard was created
+        #[cfg(target_arch = "x86_64")]
+        unsafe { mxcsr::set(self.saved) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+    use super::*;
+
+    #[test]
+    fn test_flush_denormal() {
+        let tiny = f64::MIN_POSITIVE / 4.0;
+        assert!(tiny._is_subnormal());
+        assert_eq!(tiny._flush_denormal().to_bits(), 0);
+        assert_eq!((-tiny)._flush_denormal().to_bits(), (-0.0f64).to_bits());
+        assert_eq!(f64::MIN_POSITIVE._flush_denormal(), f64::MIN_POSITIVE);
+        assert_eq!(F16::from_bits(0x83ff)._flush_denormal().to_bits(), 0x8000);
+        assert!(f32::NAN._flush_denormal().is_nan());
+        let d = Dual::new(1.0f32, [1e-40])._flush_denormal();
+        assert_eq!((d.val, d.grad), (1.0, [0.0]));
+        let v = f32x4::from_array([1e-40, 1.0, -1e-41, 0.0])._flush_denormal();
+        assert_eq!(v.to_array(), [0.0, 1.0, -0.0, 0.0]);
+        let mut tail = [1e-310f64, 0.5, -1e-320];
+        flush_denormals(&mut tail);
+        assert_eq!(tail, [0.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_guard() {
+        let tiny = black_box(f32::MIN_POSITIVE / 4.0);
+        {
+            // SAFETY: the block only runs the flushing checks below
+            let _guard = unsafe { DenormalGuard::new() };
+            let nested = unsafe { DenormalGuard::new() };
+            drop(nested);
+            if DenormalGuard::IS_SUPPORTED {
+                assert_eq!(black_box(f32::MIN_POSITIVE) / black_box(4.0), 0.0);
+                assert_eq!(tiny * black_box(1.0), 0.0);
+            }
+        }
+        assert_eq!(black_box(f32::MIN_POSITIVE) / black_box(4.0), tiny);
+    }
+}
@@ -0,0 +1,673 @@
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, BitAnd, BitOr, BitXor, Div, DivAssign, Mul, MulAssign, Neg, Not, Rem, Shl, Shr, Sub, SubAssign};
+use super::*;
+
+/// Dual number over a floating point unit carrying `N` partial derivatives, `val + sum(grad[i] * eps_i)`
+/// with eps_i * eps_j = 0, so evaluating any function written against the unit traits also evaluates
+/// its exact gradient (forward mode automatic differentiation)
+/// - comparisons and equality only look at `val`, so branches in the differentiated code follow the value
+/// - it is a `Unit` (and `Float`) for every `N`, its bits are a `DualBits`
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct DualN<T, const N: usize> {
+    pub val: T,
+    pub grad: [T; N],
+}
+
+/// Dual number with a single derivative
+pub type Dual<T> = DualN<T, 1>;
+
+impl<T: Float, const N: usize> DualN<T, N> {
+    pub const fn new(val: T, grad: [T; N]) -> Self {
+        DualN { val, grad }
+    }
+    /// Constant w.r.t every variable (all partials are zero)
+    pub const fn constant(val: T) -> Self {
+        DualN { val, grad: [T::_ZERO; N] }
+    }
+    /// The i-th independent variable, seeded with d/dx_i = 1
+    pub fn var(val: T, i: usize) -> Self {
+        let mut grad = [T::_ZERO; N];
+        grad[i] = T::_ONE;
+        DualN { val, grad }
+    }
+    /// Evaluates `f` at `x` with every argument seeded as an independent variable, returns (f(x), ∇f(x))
+    pub fn gradient(f: impl Fn([Self; N]) -> Self, x: [T; N]) -> (T, [T; N]) {
+        let mut i = 0;
+        let args = x.map(|v| {
+            i += 1;
+            Self::var(v, i - 1)
+        });
+        let y = f(args);
+        (y.val, y.grad)
+    }
+    /// Chain rule, f(self) given f(val) and f'(val)
+    fn chain(self, f: T, df: T) -> Self {
+        DualN { val: f, grad: self.grad.map(|g| g * df) }
+    }
+    /// self * k + rhs * m on the partials, the value is given
+    fn combine(self, k: T, rhs: Self, m: T, val: T) -> Self {
+        let mut grad = self.grad;
+        for (g, r) in grad.iter_mut().zip(rhs.grad) {
+            *g = *g * k + r * m;
+        }
+        DualN { val, grad }
+    }
+    fn is_constant(&self) -> bool {
+        self.grad.iter().all(|g| g._is_zero())
+    }
+}
+
+impl<T: Float> Dual<T> {
+    /// Derivative of `f` at `x`
+    pub fn derivative(f: impl Fn(Self) -> Self, x: T) -> T {
+        f(Self::var(x, 0)).grad[0]
+    }
+}
+
+impl<T: Float, const N: usize> From<T> for DualN<T, N> {
+    fn from(val: T) -> Self {
+        Self::constant(val)
+    }
+}
+
+impl<T: Float, const N: usize> PartialEq for DualN<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.val == other.val
+    }
+}
+
+impl<T: Float, const N: usize> PartialOrd for DualN<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.val.partial_cmp(&other.val)
+    }
+}
+
+// Unit implementations ============================================================================
+
+impl<T: Float, const N: usize> Unit for DualN<T, N> {}
+impl<T: Float, const N: usize> UnitOps for DualN<T, N> {}
+impl<T: Float, const N: usize> Zero for DualN<T, N> {
+    const _ZERO: Self = Self::constant(T::_ZERO);
+}
+impl<T: Float, const N: usize> One for DualN<T, N> {
+    const _ONE: Self = Self::constant(T::_ONE);
+}
+impl<T: Float, const N: usize> Inv for DualN<T, N> {
+    fn _inv(self) -> Self {
+        self._recip()
+    }
+}
+impl<T: Float, const N: usize> Symbolic for DualN<T, N> {
+    type Base = T;
+    fn _dismantle(self) -> Option<Self::Base> {
+        if self.is_constant() { Some(self.val) } else { None }
+    }
+}
+
+impl<T: Float, const N: usize> Ordered for DualN<T, N> {
+    /// Picks the operand whose value `T::_min` picks, NaN is ignored
+    fn _min(self, other: Self) -> Self {
+        if self.val._min(other.val) == self.val { self } else { other }
+    }
+    fn _max(self, other: Self) -> Self {
+        if self.val._max(other.val) == self.val { self } else { other }
+    }
+    fn _clamp(self, min: Self, max: Self) -> Self {
+        self._max(min)._min(max)
+    }
+}
+
+// std::ops implementations ========================================================================
+
+impl<T: Float, const N: usize> Add for DualN<T, N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.combine(T::_ONE, rhs, T::_ONE, self.val + rhs.val)
+    }
+}
+
+impl<T: Float, const N: usize> Sub for DualN<T, N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.combine(T::_ONE, rhs, -T::_ONE, self.val - rhs.val)
+    }
+}
+
+impl<T: Float, const N: usize> Mul for DualN<T, N> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.combine(rhs.val, rhs, self.val, self.val * rhs.val)
+    }
+}
+
+impl<T: Float, const N: usize> Div for DualN<T, N> {
+    type Output = Self;
+    /// (a' - q b') / b with q = a / b
+    fn div(self, rhs: Self) -> Self::Output {
+        let q = self.val / rhs.val;
+        let inv = rhs.val._recip();
+        self.combine(inv, rhs, -q * inv, q)
+    }
+}
+
+impl<T: Float, const N: usize> Rem for DualN<T, N> {
+    type Output = Self;
+    /// a - trunc(a / b) * b, the truncated quotient is locally constant
+    fn rem(self, rhs: Self) -> Self::Output {
+        let q = (self.val / rhs.val)._trunc();
+        self.combine(T::_ONE, rhs, -q, self.val % rhs.val)
+    }
+}
+
+impl<T: Float, const N: usize> Neg for DualN<T, N> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        DualN { val: -self.val, grad: self.grad.map(|g| -g) }
+    }
+}
+
+/// (trait, method, assign_trait, assign_method)
+macro_rules! impl_dual_assign_and_scalar {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        // DualN += DualN
+        impl<T: Float, const N: usize> $assign_trait for DualN<T, N> {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        // DualN + T
+        impl<T: Float, const N: usize> $trait<T> for DualN<T, N> {
+            type Output = Self;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$method(Self::constant(rhs))
+            }
+        }
+
+        // DualN += T
+        impl<T: Float, const N: usize> $assign_trait<T> for DualN<T, N> {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = (*self).$method(Self::constant(rhs));
+            }
+        }
+    };
+}
+
+impl_dual_assign_and_scalar!(Add, add, AddAssign, add_assign);
+impl_dual_assign_and_scalar!(Sub, sub, SubAssign, sub_assign);
+impl_dual_assign_and_scalar!(Mul, mul, MulAssign, mul_assign);
+impl_dual_assign_and_scalar!(Div, div, DivAssign, div_assign);
+
+// Differentiable functions ========================================================================
+
+impl<T: Float, const N: usize> ExpBasic for DualN<T, N> {
+    type Output = Self;
+    fn _sq(self) -> <Self as ExpBasic>::Output {
+        self * self
+    }
+    fn _sqrt(self) -> <Self as ExpBasic>::Output {
+        let s = self.val._sqrt();
+        self.chain(s, (s + s)._recip())
+    }
+}
+
+impl<T: Float, const N: usize> ExpPowDynamic<Self> for DualN<T, N> {
+    /// d(x^y) = y x^(y-1) dx + x^y ln(x) dy, the ln(x) term is skipped for constant exponents
+    fn _pow(self, rhs: Self) -> <Self as ExpBasic>::Output {
+        let val = self.val._pow(rhs.val);
+        let dx = rhs.val * self.val._pow(rhs.val - T::_ONE);
+        if rhs.is_constant() {
+            return self.chain(val, dx);
+        }
+        self.combine(dx, rhs, val * self.val._ln(), val)
+    }
+}
+
+impl<T: Float, const N: usize> ExpRootDynamic<Self> for DualN<T, N> {
+    fn _root(self, n: Self) -> <Self as ExpBasic>::Output {
+        self._pow(n._recip())
+    }
+}
+
+impl<T: Float, const N: usize> ExpFloat for DualN<T, N> {
+    fn _exp(self) -> <Self as ExpBasic>::Output {
+        let e = self.val._exp();
+        self.chain(e, e)
+    }
+    fn _exp2(self) -> <Self as ExpBasic>::Output {
+        let e = self.val._exp2();
+        self.chain(e, e / T::_E._log2())
+    }
+    fn _exp_m1(self) -> <Self as ExpBasic>::Output {
+        self.chain(self.val._exp_m1(), self.val._exp())
+    }
+    fn _log(self, base: Self) -> <Self as ExpBasic>::Output {
+        self._ln() / base._ln()
+    }
+    fn _log2(self) -> <Self as ExpBasic>::Output {
+        self.chain(self.val._log2(), T::_E._log2() / self.val)
+    }
+    fn _log10(self) -> <Self as ExpBasic>::Output {
+        self.chain(self.val._log10(), T::_E._log10() / self.val)
+    }
+    fn _ln(self) -> <Self as ExpBasic>::Output {
+        self.chain(self.val._ln(), self.val._recip())
+    }
+    fn _ln_1p(self) -> <Self as ExpBasic>::Output {
+        self.chain(self.val._ln_1p(), (T::_ONE + self.val)._recip())
+    }
+}
+
+impl<T: Float, const N: usize> Trig for DualN<T, N> {
+    fn _sin(self) -> Self {
+        let (sin, cos) = self.val._sin_cos();
+        self.chain(sin, cos)
+    }
+    fn _cos(self) -> Self {
+        let (sin, cos) = self.val._sin_cos();
+        self.chain(cos, -sin)
+    }
+    fn _sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = self.val._sin_cos();
+        (self.chain(sin, cos), self.chain(cos, -sin))
+    }
+    fn _tan(self) -> Self {
+        let t = self.val._tan();
+        self.chain(t, T::_ONE + t * t)
+    }
+    fn _asin(self) -> Self {
+        self.chain(self.val._asin(), (T::_ONE - self.val * self.val)._sqrt()._recip())
+    }
+    fn _acos(self) -> Self {
+        self.chain(self.val._acos(), -(T::_ONE - self.val * self.val)._sqrt()._recip())
+    }
+    fn _atan(self) -> Self {
+        self.chain(self.val._atan(), (T::_ONE + self.val * self.val)._recip())
+    }
+    /// d atan2(y, x) = (x dy - y dx) / (x^2 + y^2)
+    fn _atan2(self, other: Self) -> Self {
+        let r2 = self.val * self.val + other.val * other.val;
+        self.combine(other.val / r2, other, -self.val / r2, self.val._atan2(other.val))
+    }
+    fn _sinh(self) -> Self {
+        self.chain(self.val._sinh(), self.val._cosh())
+    }
+    fn _cosh(self) -> Self {
+        self.chain(self.val._cosh(), self.val._sinh())
+    }
+    fn _tanh(self) -> Self {
+        let t = self.val._tanh();
+        self.chain(t, T::_ONE - t * t)
+    }
+    fn _asinh(self) -> Self {
+        self.chain(self.val._asinh(), (self.val * self.val + T::_ONE)._sqrt()._recip())
+    }
+    fn _acosh(self) -> Self {
+        self.chain(self.val._acosh(), (self.val * self.val - T::_ONE)._sqrt()._recip())
+    }
+    fn _atanh(self) -> Self {
+        self.chain(self.val._atanh(), (T::_ONE - self.val * self.val)._recip())
+    }
+    fn _hypot(self, other: Self) -> Self {
+        let h = self.val._hypot(other.val);
+        self.combine(self.val / h, other, other.val / h, h)
+    }
+    fn _to_degrees(self) -> Self {
+        self.chain(self.val._to_degrees(), T::_ONE._to_degrees())
+    }
+    fn _to_radians(self) -> Self {
+        self.chain(self.val._to_radians(), T::_ONE._to_radians())
+    }
+}
+
+// Casting implementations =========================================================================
+
+macro_rules! impl_dual_to_primitive {
+    ($( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            self.val.$method()
+        }
+    )*}
+}
+
+macro_rules! impl_dual_from_primitive {
+    ($( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            T::$method(n).map(Self::constant)
+        }
+    )*}
+}
+
+/// Casts the value, dropping the partials
+impl<T: Float, const N: usize> ToPrimitive for DualN<T, N> {
+    impl_dual_to_primitive! {
+        fn to_isize -> isize;
+        fn to_i8 -> i8;
+        fn to_i16 -> i16;
+        fn to_i32 -> i32;
+        fn to_i64 -> i64;
+        fn to_i128 -> i128;
+        fn to_usize -> usize;
+        fn to_u8 -> u8;
+        fn to_u16 -> u16;
+        fn to_u32 -> u32;
+        fn to_u64 -> u64;
+        fn to_u128 -> u128;
+        fn to_f32 -> f32;
+        fn to_f64 -> f64;
+    }
+}
+
+/// Casts to a constant
+impl<T: Float, const N: usize> FromPrimitive for DualN<T, N> {
+    impl_dual_from_primitive! {
+        fn from_isize(isize);
+        fn from_i8(i8);
+        fn from_i16(i16);
+        fn from_i32(i32);
+        fn from_i64(i64);
+        fn from_i128(i128);
+        fn from_usize(usize);
+        fn from_u8(u8);
+        fn from_u16(u16);
+        fn from_u32(u32);
+        fn from_u64(u64);
+        fn from_u128(u128);
+        fn from_f32(f32);
+        fn from_f64(f64);
+    }
+}
+
+// Bit representation ==============================================================================
+
+/// Bits of a `DualN`, the bits of `val` and of every partial side by side
+/// - operations act lane by lane like the SIMD bit vectors
+/// - bytes are the lanes in order, each lane in the requested byte order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DualBits<B, const N: usize> {
+    pub val: B,
+    pub grad: [B; N],
+}
+
+impl<B: Copy, const N: usize> DualBits<B, N> {
+    fn map(self, rhs: Self, f: impl Fn(B, B) -> B) -> Self {
+        DualBits { val: f(self.val, rhs.val), grad: std::array::from_fn(|i| f(self.grad[i], rhs.grad[i])) }
+    }
+}
+
+impl<B: PhysicalRepr, const N: usize> DualBits<B, N> {
+    /// Panics unless `bytes` holds exactly `N + 1` lanes
+    fn from_lane_bytes(bytes: &[u8], f: impl Fn(B::BytesRepr) -> B) -> Self {
+        assert_eq!(bytes.len(), (N + 1) * B::_BYTES, "dual bits need exactly {} bytes", (N + 1) * B::_BYTES);
+        let lane = |i: usize| match B::BytesRepr::try_from(&bytes[i * B::_BYTES..(i + 1) * B::_BYTES]) {
+            Ok(lane) => f(lane),
+            Err(_) => unreachable!("lanes are exactly `_BYTES` long"),
+        };
+        DualBits { val: lane(0), grad: std::array::from_fn(|i| lane(i + 1)) }
+    }
+    fn to_lane_bytes(self, f: impl Fn(B) -> B::BytesRepr) -> Vec<u8> {
+        std::iter::once(self.val).chain(self.grad).flat_map(|lane| f(lane).as_ref().to_vec()).collect()
+    }
+}
+
+/// (trait, method)
+macro_rules! impl_dual_bits_binop {
+    ($($trait:ident, $method:ident;)*) => {$(
+        impl<B: Copy + $trait<Output = B>, const N: usize> $trait for DualBits<B, N> {
+            type Output = Self;
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.map(rhs, B::$method)
+            }
+        }
+    )*}
+}
+
+impl_dual_bits_binop! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+    Rem, rem;
+    BitAnd, bitand;
+    BitOr, bitor;
+    BitXor, bitxor;
+    Shl, shl;
+    Shr, shr;
+}
+
+impl<B: Copy + Not<Output = B>, const N: usize> Not for DualBits<B, N> {
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        DualBits { val: !self.val, grad: self.grad.map(|g| !g) }
+    }
+}
+
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> Unit for DualBits<B, N> {}
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> UnitOps for DualBits<B, N> {}
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> Bitwise for DualBits<B, N> {}
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> Zero for DualBits<B, N> {
+    const _ZERO: Self = DualBits { val: B::_ZERO, grad: [B::_ZERO; N] };
+}
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> One for DualBits<B, N> {
+    const _ONE: Self = DualBits { val: B::_ONE, grad: [B::_ONE; N] };
+}
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> Bounded for DualBits<B, N> {
+    const _MIN: Self = DualBits { val: B::_MIN, grad: [B::_MIN; N] };
+    const _MAX: Self = DualBits { val: B::_MAX, grad: [B::_MAX; N] };
+}
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> Symbolic for DualBits<B, N> {
+    type Base = Self;
+}
+impl<B: Unit + Bitwise + Bounded + Eq, const N: usize> PhysicalRepr for DualBits<B, N> {
+    const _BITS: u32 = (N as u32 + 1) * B::_BITS;
+    const _BYTES: usize = (N + 1) * B::_BYTES;
+    type BitsRepr = Self;
+    type BytesRepr = Vec<u8>;
+    fn _from_bits(v: Self::BitsRepr) -> Self {
+        v
+    }
+    fn _to_bits(self) -> Self::BitsRepr {
+        self
+    }
+    fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+        Self::from_lane_bytes(&bytes, B::_from_be_bytes)
+    }
+    fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+        Self::from_lane_bytes(&bytes, B::_from_le_bytes)
+    }
+    fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+        Self::from_lane_bytes(&bytes, B::_from_ne_bytes)
+    }
+    fn _to_be_bytes(self) -> Self::BytesRepr {
+        self.to_lane_bytes(B::_to_be_bytes)
+    }
+    fn _to_le_bytes(self) -> Self::BytesRepr {
+        self.to_lane_bytes(B::_to_le_bytes)
+    }
+    fn _to_ne_bytes(self) -> Self::BytesRepr {
+        self.to_lane_bytes(B::_to_ne_bytes)
+    }
+}
+
+// Float implementations ===========================================================================
+
+/// `val` then the partials, see `DualBits`
+impl<T: Float, const N: usize> PhysicalRepr for DualN<T, N> {
+    const _BITS: u32 = (N as u32 + 1) * T::_BITS;
+    const _BYTES: usize = (N + 1) * T::_BYTES;
+    type BitsRepr = DualBits<T::BitsRepr, N>;
+    type BytesRepr = Vec<u8>;
+    fn _from_bits(v: Self::BitsRepr) -> Self {
+        DualN::new(T::_from_bits(v.val), v.grad.map(T::_from_bits))
+    }
+    fn _to_bits(self) -> Self::BitsRepr {
+        DualBits { val: self.val._to_bits(), grad: self.grad.map(T::_to_bits) }
+    }
+    fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+        Self::_from_bits(DualBits::_from_be_bytes(bytes))
+    }
+    fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+        Self::_from_bits(DualBits::_from_le_bytes(bytes))
+    }
+    fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+        Self::_from_bits(DualBits::_from_ne_bytes(bytes))
+    }
+    fn _to_be_bytes(self) -> Self::BytesRepr {
+        self._to_bits()._to_be_bytes()
+    }
+    fn _to_le_bytes(self) -> Self::BytesRepr {
+        self._to_bits()._to_le_bytes()
+    }
+    fn _to_ne_bytes(self) -> Self::BytesRepr {
+        self._to_bits()._to_ne_bytes()
+    }
+}
+
+impl<T: Float, const N: usize> Bounded for DualN<T, N> {
+    const _MIN: Self = Self::constant(T::_MIN);
+    const _MAX: Self = Self::constant(T::_MAX);
+}
+
+/// Masks select the bits of `val`
+const fn val_mask<B: Unit, const N: usize>(mask: B) -> DualBits<B, N> {
+    DualBits { val: mask, grad: [B::_ZERO; N] }
+}
+
+impl<T: Float, const N: usize> Signed for DualN<T, N> {
+    const _SIGN_MASK: Self::BitsRepr = val_mask(T::_SIGN_MASK);
+    const _NEG_ONE: Self = Self::constant(T::_NEG_ONE);
+    fn _abs(self) -> Self {
+        self.chain(self.val._abs(), self.val._signum())
+    }
+    fn _signum(self) -> Self {
+        Self::constant(self.val._signum())
+    }
+    fn _is_positive(self) -> bool {
+        self.val._is_positive()
+    }
+    fn _is_negative(self) -> bool {
+        self.val._is_negative()
+    }
+}
+
+impl<T: Float, const N: usize> BoundedSigned for DualN<T, N> {
+    const _MIN_POSITIVE: Self = Self::constant(T::_MIN_POSITIVE);
+}
+
+/// Constants and masks are those of `val`, the rounding functions have a zero derivative
+impl<T: Float, const N: usize> Float for DualN<T, N> {
+    const _NAN: Self = Self::constant(T::_NAN);
+    const _INFINITY: Self = Self::constant(T::_INFINITY);
+    const _NEG_INFINITY: Self = Self::constant(T::_NEG_INFINITY);
+    const _EPSILON: Self = Self::constant(T::_EPSILON);
+    const _SIG_BITS: u32 = T::_SIG_BITS;
+    const _EXP_BITS: u32 = T::_EXP_BITS;
+    const _SIG_MASK: Self::BitsRepr = val_mask(T::_SIG_MASK);
+    const _EXP_MASK: Self::BitsRepr = val_mask(T::_EXP_MASK);
+    const _DIGITS: u32 = T::_DIGITS;
+    const _MANTISSA_DIGITS: u32 = T::_MANTISSA_DIGITS;
+    const _MIN_EXP: i32 = T::_MIN_EXP;
+    const _MAX_EXP: i32 = T::_MAX_EXP;
+    const _MIN_10_EXP: i32 = T::_MIN_10_EXP;
+    const _MAX_10_EXP: i32 = T::_MAX_10_EXP;
+    const _PI: Self = Self::constant(T::_PI);
+    const _E: Self = Self::constant(T::_E);
+    const _TAU: Self = Self::constant(T::_TAU);
+    fn _floor(self) -> Self {
+        Self::constant(self.val._floor())
+    }
+    fn _ceil(self) -> Self {
+        Self::constant(self.val._ceil())
+    }
+    fn _round(self) -> Self {
+        Self::constant(self.val._round())
+    }
+    fn _trunc(self) -> Self {
+        Self::constant(self.val._trunc())
+    }
+    fn _fract(self) -> Self {
+        DualN { val: self.val._fract(), grad: self.grad }
+    }
+    /// Steps `val`, the partials are kept
+    fn _next_up(self) -> Self {
+        DualN { val: self.val._next_up(), grad: self.grad }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12 * b.abs().max(1.0)
+    }
+
+    /// Knows nothing about dual numbers, the derivative comes from evaluating it on `DualN`
+    fn generic<T: Float>(x: T) -> T {
+        x._sin() * x._exp() + x._sqrt()._ln() - x._pow(T::_ONE + T::_ONE + T::_ONE)._atan()
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let x = 0.7f64;
+        let exact = x.cos() * x.exp() + x.sin() * x.exp() + 0.5 / x - 3.0 * x * x / (1.0 + x.powi(6));
+        assert!(close(Dual::derivative(generic, x), exact));
+        assert!(close(Dual::derivative(|x| x / (x + 1.0), 2.0), 1.0 / 9.0));
+        assert!(close(Dual::derivative(|x| x._tanh()._asinh(), 0.3), (1.0 - 0.3f64.tanh().powi(2)) / (0.3f64.tanh().powi(2) + 1.0).sqrt()));
+        assert!(close(Dual::derivative(|x| x._exp2()._log2(), 1.3), 1.0));
+        assert!(close(Dual::derivative(|x| x._exp_m1()._ln_1p(), 1e-3), 1.0));
+        assert!(close(Dual::derivative(|x| x._abs(), -2.0), -1.0));
+        assert_eq!(Dual::derivative(|x| x._floor(), 2.5), 0.0);
+    }
+
+    #[test]
+    fn test_gradient() {
+        // f(x, y, z) = x^y + atan2(y, x) + hypot(x, z)
+        let g = |[x, y, z]: [DualN<f32, 3>; 3]| x._pow(y) + y._atan2(x) + x._hypot(z);
+        let (v, grad) = DualN::gradient(g, [1.5f32, 2.0, 0.5]);
+        let (x, y, z) = (1.5f64, 2.0f64, 0.5f64);
+        let h = x.hypot(z);
+        assert!((v as f64 - (x.powf(y) + y.atan2(x) + h)).abs() < 1e-5);
+        let exact = [y * x.powf(y - 1.0) - y / (x * x + y * y) + x / h, x.powf(y) * x.ln() + x / (x * x + y * y), z / h];
+        for (g, e) in grad.iter().zip(exact) {
+            assert!((*g as f64 - e).abs() < 1e-5, "{g} vs {e}");
+        }
+    }
+
+    #[test]
+    fn test_unit_properties() {
+        let a = Dual::new(1.5f32, [-0.25]);
+        assert_eq!(Dual::<f32>::_from_bits(a._to_bits()).grad, a.grad);
+        assert_eq!(Dual::<f32>::_from_le_bytes(a._to_le_bytes()).grad, [-0.25]);
+        assert_eq!(&a._to_le_bytes()[..4], &1.5f32.to_le_bytes());
+        let b = DualN::<F16, 7>::var(F16::from_f32(2.0), 6);
+        assert_eq!(DualN::<F16, 7>::_from_bits(b._to_bits()).grad[6], F16::from_f32(1.0));
+        assert_eq!(Dual::var(2.0f64, 0)._dismantle(), None);
+        assert_eq!(Dual::<f64>::constant(2.0)._dismantle(), Some(2.0));
+        assert!(Dual::new(1.0f64, [5.0]) < Dual::new(2.0, [-5.0]));
+        assert_eq!(Dual::new(1.0f64, [5.0])._max(Dual::new(2.0, [-5.0])).grad, [-5.0]);
+    }
+
+    #[test]
+    fn test_any_size() {
+        let (v, grad) = DualN::<f64, 4>::gradient(|[a, b, c, d]| a * b._exp() + c._sin() * d, [2.0, 0.0, 0.0, 3.0]);
+        assert!(close(v, 2.0));
+        assert_eq!(grad, [1.0, 2.0, 3.0, 0.0]);
+        let a = DualN::new(-1.5f32, [0.25, f32::NAN]);
+        let bytes = a._to_be_bytes();
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(&bytes[4..8], &0.25f32.to_be_bytes());
+        let b = DualN::<f32, 2>::_from_be_bytes(bytes);
+        assert_eq!((b.val, b.grad[0]), (-1.5, 0.25));
+        assert!(b.grad[1].is_nan());
+        assert!(a._is_negative() && !a._is_nan());
+        assert_eq!(a._to_bits() & DualN::<f32, 2>::_SIGN_MASK, DualBits { val: 1 << 31, grad: [0, 0] });
+        assert_eq!(DualN::<f32, 2>::_INFINITY._next_up().val, f32::INFINITY);
+        assert_eq!(a._next_up().grad[0], 0.25);
+        let c = DualN::<F16, 5>::var(F16::from_f32(2.0), 4);
+        assert_eq!(DualN::<F16, 5>::_from_le_bytes(c._to_le_bytes()).grad[4], F16::from_f32(1.0));
+    }
+}
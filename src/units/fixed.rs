@@ -0,0 +1,564 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use super::*;
+
+/// Rounding applied whenever low order bits are discarded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// Round towards zero
+    Truncate,
+    /// Round towards negative infinity (plain arithmetic shift, "truncation" on most DSP hardware)
+    Floor,
+    /// Round to nearest, ties away from zero
+    #[default]
+    Nearest,
+    /// Round to nearest, ties to even (unbiased)
+    Convergent,
+}
+
+/// Behaviour when a result does not fit in the destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Overflow {
+    /// Clamp to the closest representable value
+    #[default]
+    Saturate,
+    /// Keep the low order bits (two's complement wrap around)
+    Wrap,
+}
+
+/// Signed fixed-point number in Q-format, `FRAC` of the storage bits are fractional
+/// - the represented value is `bits / 2^FRAC`
+/// - std::ops saturate on overflow and round to nearest, use the `*_with` methods to select other modes
+/// - division by zero saturates towards the sign of the dividend (0 / 0 == 0)
+/// - when `FRAC` leaves no integer bits (e.g. Q15), `_ONE` saturates to the largest value below 1
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed<I, const FRAC: u32> {
+    bits: I,
+}
+
+pub type Q7 = Fixed<i8, 7>;
+pub type Q15 = Fixed<i16, 15>;
+pub type Q31 = Fixed<i32, 31>;
+
+macro_rules! impl_fixed_to_primitive {
+    ($WideT:ident : $( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            // integer part, truncated towards zero like float to int casts
+            ((self.bits as $WideT) / ((1 as $WideT) << FRAC)).$method()
+        }
+    )*}
+}
+
+macro_rules! impl_fixed_from_primitive {
+    ($StoreT:ident : $( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            let n = n.to_i128()?;
+            let max = (Self::_MAX.bits >> FRAC) as i128;
+            let min = (Self::_MIN.bits >> FRAC) as i128;
+            if min <= n && n <= max {
+                Some(Self::from_raw((n as $StoreT) << FRAC))
+            } else {
+                None
+            }
+        }
+    )*}
+}
+
+macro_rules! impl_fixed {
+    ($SrcT:ident, $WideT:ident, $SrcReprT:ident) => {
+        impl<const FRAC: u32> Fixed<$SrcT, FRAC> {
+            pub const FRAC_BITS: u32 = FRAC;
+            pub const INT_BITS: u32 = $SrcT::BITS - 1 - FRAC;
+
+            /// Interpret raw storage bits as a fixed-point value
+            pub const fn from_raw(bits: $SrcT) -> Self {
+                Fixed { bits }
+            }
+            /// Raw storage bits
+            pub const fn to_raw(self) -> $SrcT {
+                self.bits
+            }
+            /// Quantize a float with the given rounding and overflow modes (NaN maps to zero)
+            pub fn from_float(v: f64, rounding: Rounding, overflow: Overflow) -> Self {
+                let scaled = v * 2f64.powi(FRAC as i32);
+                let rounded = match rounding {
+                    Rounding::Truncate => scaled.trunc(),
+                    Rounding::Floor => scaled.floor(),
+                    Rounding::Nearest => scaled.round(),
+                    Rounding::Convergent => scaled.round_ties_even(),
+                };
+                // `as` saturates (and maps NaN to 0), overflow is applied in i128 which holds every storage type
+                let n = rounded as i128;
+                match overflow {
+                    Overflow::Saturate => Self::from_raw(n.clamp($SrcT::MIN as i128, $SrcT::MAX as i128) as $SrcT),
+                    Overflow::Wrap => Self::from_raw(n as $SrcT),
+                }
+            }
+            /// Exact conversion to a float
+            pub fn to_float(self) -> f64 {
+                self.bits as f64 / 2f64.powi(FRAC as i32)
+            }
+            pub fn add_with(self, rhs: Self, overflow: Overflow) -> Self {
+                Self::narrow(self.bits as $WideT + rhs.bits as $WideT, overflow)
+            }
+            pub fn sub_with(self, rhs: Self, overflow: Overflow) -> Self {
+                Self::narrow(self.bits as $WideT - rhs.bits as $WideT, overflow)
+            }
+            /// The product is formed exactly in the widened type before being shifted back down
+            pub fn mul_with(self, rhs: Self, rounding: Rounding, overflow: Overflow) -> Self {
+                let product = self.bits as $WideT * rhs.bits as $WideT;
+                Self::narrow(Self::shr_round(product, FRAC, rounding), overflow)
+            }
+            pub fn div_with(self, rhs: Self, rounding: Rounding, overflow: Overflow) -> Self {
+                if rhs.bits == 0 {
+                    return match self.bits {
+                        0 => Self::_ZERO,
+                        n if n < 0 => Self::_MIN,
+                        _ => Self::_MAX,
+                    };
+                }
+                Self::narrow(self.div_wide(rhs, rounding), overflow)
+            }
+            /// Rounded quotient in the widened type, `rhs` must not be zero
+            fn div_wide(self, rhs: Self, rounding: Rounding) -> $WideT {
+                let n = (self.bits as $WideT) << FRAC;
+                let d = rhs.bits as $WideT;
+                let (q, r) = (n / d, n % d);
+                let away = if (n < 0) != (d < 0) { -1 } else { 1 };
+                let twice_r = 2 * r.abs();
+                match rounding {
+                    _ if r == 0 => q,
+                    Rounding::Truncate => q,
+                    Rounding::Floor => if away < 0 { q - 1 } else { q },
+                    Rounding::Nearest => if twice_r >= d.abs() { q + away } else { q },
+                    Rounding::Convergent => {
+                        if twice_r > d.abs() || (twice_r == d.abs() && q & 1 != 0) { q + away } else { q }
+                    }
+                }
+            }
+            /// Arithmetic right shift of a widened value with rounding of the discarded bits
+            fn shr_round(v: $WideT, shift: u32, rounding: Rounding) -> $WideT {
+                if shift == 0 {
+                    return v;
+                }
+                let floor = v >> shift;
+                let rem = v & (((1 as $WideT) << shift) - 1);
+                let half = (1 as $WideT) << (shift - 1);
+                let round_up = match rounding {
+                    Rounding::Truncate => v < 0 && rem != 0,
+                    Rounding::Floor => false,
+                    Rounding::Nearest => rem > half || (rem == half && v >= 0),
+                    Rounding::Convergent => rem > half || (rem == half && floor & 1 != 0),
+                };
+                if round_up { floor + 1 } else { floor }
+            }
+            fn narrow(v: $WideT, overflow: Overflow) -> Self {
+                match overflow {
+                    Overflow::Saturate => Self::from_raw(v.clamp($SrcT::MIN as $WideT, $SrcT::MAX as $WideT) as $SrcT),
+                    Overflow::Wrap => Self::from_raw(v as $SrcT),
+                }
+            }
+            fn narrow_checked(v: $WideT) -> Option<Self> {
+                $SrcT::try_from(v).ok().map(Self::from_raw)
+            }
+        }
+
+        impl<const FRAC: u32> Display for Fixed<$SrcT, FRAC> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(&self.to_float(), f)
+            }
+        }
+
+        // Unit implementations ====================================================================
+
+        impl<const FRAC: u32> Unit for Fixed<$SrcT, FRAC> {}
+        impl<const FRAC: u32> UnitOps for Fixed<$SrcT, FRAC> {}
+        impl<const FRAC: u32> Zero for Fixed<$SrcT, FRAC> {
+            const _ZERO: Self = Self::from_raw(0);
+        }
+        impl<const FRAC: u32> One for Fixed<$SrcT, FRAC> {
+            const _ONE: Self = if FRAC < $SrcT::BITS - 1 {
+                Self::from_raw(1 << FRAC)
+            } else {
+                Self::from_raw($SrcT::MAX)
+            };
+        }
+        impl<const FRAC: u32> Symbolic for Fixed<$SrcT, FRAC> {
+            type Base = Self;
+        }
+        // SAFETY: transparent over the storage integer
+        unsafe impl<const FRAC: u32> PlainBytes for Fixed<$SrcT, FRAC> {}
+        impl<const FRAC: u32> PhysicalRepr for Fixed<$SrcT, FRAC> {
+            const _BITS: u32 = $SrcT::_BITS;
+            const _BYTES: usize = $SrcT::_BYTES;
+            const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = Some(PlainBytesProof::PROOF);
+            type BitsRepr = $SrcReprT;
+            type BytesRepr = [u8; size_of::<$SrcT>()];
+            fn _from_bits(v: Self::BitsRepr) -> Self {
+                Self::from_raw(v as $SrcT)
+            }
+            fn _to_bits(self) -> Self::BitsRepr {
+                self.bits as $SrcReprT
+            }
+            fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::from_raw($SrcT::from_be_bytes(bytes))
+            }
+            fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::from_raw($SrcT::from_le_bytes(bytes))
+            }
+            fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::from_raw($SrcT::from_ne_bytes(bytes))
+            }
+            fn _to_be_bytes(self) -> Self::BytesRepr {
+                self.bits.to_be_bytes()
+            }
+            fn _to_le_bytes(self) -> Self::BytesRepr {
+                self.bits.to_le_bytes()
+            }
+            fn _to_ne_bytes(self) -> Self::BytesRepr {
+                self.bits.to_ne_bytes()
+            }
+        }
+
+        // Properties ==============================================================================
+
+        impl<const FRAC: u32> Ordered for Fixed<$SrcT, FRAC> {
+            fn _min(self, other: Self) -> Self {
+                Ord::min(self, other)
+            }
+            fn _max(self, other: Self) -> Self {
+                Ord::max(self, other)
+            }
+            fn _clamp(self, min: Self, max: Self) -> Self {
+                Ord::clamp(self, min, max)
+            }
+        }
+        impl<const FRAC: u32> OrderedReflexive for Fixed<$SrcT, FRAC> {}
+        impl<const FRAC: u32> Bounded for Fixed<$SrcT, FRAC> {
+            const _MIN: Self = Self::from_raw($SrcT::MIN);
+            const _MAX: Self = Self::from_raw($SrcT::MAX);
+        }
+        impl<const FRAC: u32> Signed for Fixed<$SrcT, FRAC> {
+            const _SIGN_MASK: Self::BitsRepr = $SrcT::_SIGN_MASK;
+            const _NEG_ONE: Self = Self::from_raw(-1 << FRAC);
+            fn _abs(self) -> Self {
+                Self::from_raw(self.bits.saturating_abs())
+            }
+            fn _signum(self) -> Self {
+                match self.bits {
+                    0 => Self::_ZERO,
+                    n if n < 0 => Self::_NEG_ONE,
+                    _ => Self::_ONE,
+                }
+            }
+            fn _is_positive(self) -> bool {
+                self.bits > 0
+            }
+            fn _is_negative(self) -> bool {
+                self.bits < 0
+            }
+        }
+        impl<const FRAC: u32> BoundedSigned for Fixed<$SrcT, FRAC> {
+            const _MIN_POSITIVE: Self = Self::from_raw(1);
+        }
+
+        // std::ops implementations ================================================================
+
+        impl<const FRAC: u32> Add for Fixed<$SrcT, FRAC> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                self.add_with(rhs, Overflow::Saturate)
+            }
+        }
+        impl<const FRAC: u32> Sub for Fixed<$SrcT, FRAC> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.sub_with(rhs, Overflow::Saturate)
+            }
+        }
+        impl<const FRAC: u32> Mul for Fixed<$SrcT, FRAC> {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self::Output {
+                self.mul_with(rhs, Rounding::Nearest, Overflow::Saturate)
+            }
+        }
+        impl<const FRAC: u32> Div for Fixed<$SrcT, FRAC> {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self::Output {
+                self.div_with(rhs, Rounding::Nearest, Overflow::Saturate)
+            }
+        }
+        impl<const FRAC: u32> Rem for Fixed<$SrcT, FRAC> {
+            type Output = Self;
+            /// Exact, both operands share the same scale (remainder by zero is zero)
+            fn rem(self, rhs: Self) -> Self::Output {
+                Self::from_raw(self.bits.checked_rem(rhs.bits).unwrap_or(0))
+            }
+        }
+        impl<const FRAC: u32> Neg for Fixed<$SrcT, FRAC> {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                Self::from_raw(self.bits.saturating_neg())
+            }
+        }
+
+        // Overflow aware arithmetic ===============================================================
+
+        /// Products and quotients round to nearest like the std::ops
+        impl<const FRAC: u32> CheckedOps for Fixed<$SrcT, FRAC> {
+            fn _checked_add(self, rhs: Self) -> Option<Self> {
+                Self::narrow_checked(self.bits as $WideT + rhs.bits as $WideT)
+            }
+            fn _checked_sub(self, rhs: Self) -> Option<Self> {
+                Self::narrow_checked(self.bits as $WideT - rhs.bits as $WideT)
+            }
+            fn _checked_mul(self, rhs: Self) -> Option<Self> {
+                Self::narrow_checked(Self::shr_round(self.bits as $WideT * rhs.bits as $WideT, FRAC, Rounding::Nearest))
+            }
+            fn _checked_div(self, rhs: Self) -> Option<Self> {
+                if rhs.bits == 0 {
+                    return None;
+                }
+                Self::narrow_checked(self.div_wide(rhs, Rounding::Nearest))
+            }
+            fn _checked_rem(self, rhs: Self) -> Option<Self> {
+                self.bits.checked_rem(rhs.bits).map(Self::from_raw)
+            }
+            fn _checked_neg(self) -> Option<Self> {
+                self.bits.checked_neg().map(Self::from_raw)
+            }
+        }
+        /// Division by zero saturates as in `div_with`
+        impl<const FRAC: u32> WrappingOps for Fixed<$SrcT, FRAC> {
+            fn _wrapping_add(self, rhs: Self) -> Self {
+                self.add_with(rhs, Overflow::Wrap)
+            }
+            fn _wrapping_sub(self, rhs: Self) -> Self {
+                self.sub_with(rhs, Overflow::Wrap)
+            }
+            fn _wrapping_mul(self, rhs: Self) -> Self {
+                self.mul_with(rhs, Rounding::Nearest, Overflow::Wrap)
+            }
+            fn _wrapping_div(self, rhs: Self) -> Self {
+                self.div_with(rhs, Rounding::Nearest, Overflow::Wrap)
+            }
+            fn _wrapping_rem(self, rhs: Self) -> Self {
+                self % rhs
+            }
+            fn _wrapping_neg(self) -> Self {
+                Self::from_raw(self.bits.wrapping_neg())
+            }
+        }
+        /// The same as the std::ops
+        impl<const FRAC: u32> SaturatingOps for Fixed<$SrcT, FRAC> {
+            fn _saturating_add(self, rhs: Self) -> Self {
+                self + rhs
+            }
+            fn _saturating_sub(self, rhs: Self) -> Self {
+                self - rhs
+            }
+            fn _saturating_mul(self, rhs: Self) -> Self {
+                self * rhs
+            }
+            fn _saturating_div(self, rhs: Self) -> Self {
+                self / rhs
+            }
+            fn _saturating_neg(self) -> Self {
+                -self
+            }
+        }
+        /// Division and remainder by zero report an overflow
+        impl<const FRAC: u32> OverflowingOps for Fixed<$SrcT, FRAC> {
+            fn _overflowing_add(self, rhs: Self) -> (Self, bool) {
+                (self._wrapping_add(rhs), self._checked_add(rhs).is_none())
+            }
+            fn _overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                (self._wrapping_sub(rhs), self._checked_sub(rhs).is_none())
+            }
+            fn _overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                (self._wrapping_mul(rhs), self._checked_mul(rhs).is_none())
+            }
+            fn _overflowing_div(self, rhs: Self) -> (Self, bool) {
+                (self._wrapping_div(rhs), self._checked_div(rhs).is_none())
+            }
+            fn _overflowing_rem(self, rhs: Self) -> (Self, bool) {
+                (self._wrapping_rem(rhs), self._checked_rem(rhs).is_none())
+            }
+            fn _overflowing_neg(self) -> (Self, bool) {
+                (self._wrapping_neg(), self._checked_neg().is_none())
+            }
+        }
+
+        // Casting implementations =================================================================
+
+        impl<const FRAC: u32> ToPrimitive for Fixed<$SrcT, FRAC> {
+            impl_fixed_to_primitive! { $WideT:
+                fn to_isize -> isize;
+                fn to_i8 -> i8;
+                fn to_i16 -> i16;
+                fn to_i32 -> i32;
+                fn to_i64 -> i64;
+                fn to_i128 -> i128;
+                fn to_usize -> usize;
+                fn to_u8 -> u8;
+                fn to_u16 -> u16;
+                fn to_u32 -> u32;
+                fn to_u64 -> u64;
+                fn to_u128 -> u128;
+            }
+            #[inline]
+            fn to_f32(&self) -> Option<f32> {
+                Some(self.to_float() as f32)
+            }
+            #[inline]
+            fn to_f64(&self) -> Option<f64> {
+                Some(self.to_float())
+            }
+        }
+
+        impl<const FRAC: u32> FromPrimitive for Fixed<$SrcT, FRAC> {
+            impl_fixed_from_primitive! { $SrcT:
+                fn from_isize(isize);
+                fn from_i8(i8);
+                fn from_i16(i16);
+                fn from_i32(i32);
+                fn from_i64(i64);
+                fn from_i128(i128);
+                fn from_usize(usize);
+                fn from_u8(u8);
+                fn from_u16(u16);
+                fn from_u32(u32);
+                fn from_u64(u64);
+                fn from_u128(u128);
+            }
+            /// Rounds to nearest, `None` when NaN or out of range
+            #[inline]
+            fn from_f32(n: f32) -> Option<Self> {
+                Self::from_f64(n as f64)
+            }
+            /// Rounds to nearest, `None` when NaN or out of range
+            #[inline]
+            fn from_f64(n: f64) -> Option<Self> {
+                let scaled = (n * 2f64.powi(FRAC as i32)).round();
+                if scaled >= $SrcT::MIN as f64 && scaled <= $SrcT::MAX as f64 {
+                    Some(Self::from_float(n, Rounding::Nearest, Overflow::Saturate))
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<const FRAC: u32> UnitCast for Fixed<$SrcT, FRAC> {
+            const _CAST_ROUNDING: Rounding = Rounding::Nearest;
+            #[inline]
+            fn _to_cast_value(self) -> CastValue {
+                CastValue::Finite { negative: self.bits < 0, mag: self.bits.unsigned_abs() as u128, exp: -(FRAC as i32) }
+            }
+            #[inline]
+            fn _from_cast_value(v: CastValue, rounding: Rounding, overflow: Option<Overflow>) -> Option<Self> {
+                v.to_storage($SrcT::BITS, true, FRAC, rounding, overflow).map(|raw| Self::from_raw(raw as $SrcT))
+            }
+        }
+    };
+}
+
+impl_fixed!(i8, i16, u8);
+impl_fixed!(i16, i32, u16);
+impl_fixed!(i32, i64, u32);
+impl_fixed!(i64, i128, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_q15_mul_is_exact_before_shift() {
+        let half = Q15::from_float(0.5, Rounding::Nearest, Overflow::Saturate);
+        let quarter = Q15::from_float(0.25, Rounding::Nearest, Overflow::Saturate);
+        assert_eq!(half * half, quarter);
+        // smallest values: 2^-15 * 2^-15 rounds to 0 but exactly half an lsb rounds up with Nearest
+        let lsb = Q15::_MIN_POSITIVE;
+        assert_eq!(lsb * lsb, Q15::_ZERO);
+        let a = Q15::from_raw(1 << 14);
+        let b = Q15::from_raw(1);
+        assert_eq!(a.mul_with(b, Rounding::Nearest, Overflow::Saturate), Q15::from_raw(1));
+        assert_eq!(a.mul_with(b, Rounding::Floor, Overflow::Saturate), Q15::from_raw(0));
+        assert_eq!(a.mul_with(b, Rounding::Convergent, Overflow::Saturate), Q15::from_raw(0));
+        assert_eq!(Q15::_MIN * Q15::_MIN, Q15::_MAX);
+        assert_eq!(Q15::_MIN.mul_with(Q15::_MIN, Rounding::Nearest, Overflow::Wrap), Q15::_MIN);
+    }
+
+    #[test]
+    fn test_overflow_modes() {
+        assert_eq!(Q15::_MAX + Q15::_MAX, Q15::_MAX);
+        assert_eq!(Q15::_MAX.add_with(Q15::_MIN_POSITIVE, Overflow::Wrap), Q15::_MIN);
+        assert_eq!(-Q15::_MIN, Q15::_MAX);
+        assert_eq!(Q15::_NEG_ONE, Q15::_MIN);
+        assert_eq!(Q15::_ONE, Q15::_MAX);
+    }
+
+    #[test]
+    fn test_from_float_out_of_range() {
+        let q15 = |v, overflow| Q15::from_float(v, Rounding::Nearest, overflow);
+        assert_eq!(q15(1e5, Overflow::Saturate), Q15::_MAX);
+        assert_eq!(q15(-1e5, Overflow::Saturate), Q15::_MIN);
+        assert_eq!(q15(f64::INFINITY, Overflow::Saturate), Q15::_MAX);
+        assert_eq!(Q7::from_float(300.0, Rounding::Nearest, Overflow::Saturate), Q7::_MAX);
+        assert_eq!(Q7::from_float(-300.0, Rounding::Nearest, Overflow::Saturate), Q7::_MIN);
+        assert_eq!(Q31::from_float(-1e12, Rounding::Floor, Overflow::Saturate), Q31::_MIN);
+        // wrapping keeps the low storage bits of the rounded value
+        assert_eq!(q15(1.5, Overflow::Wrap).to_raw(), -16384);
+        assert_eq!(q15(-1.5, Overflow::Wrap).to_raw(), 16384);
+        assert_eq!(q15(1e5 + 3.0 / 32768.0, Overflow::Wrap).to_raw(), 3);
+        assert_eq!(Q7::from_float(300.0 + 5.0 / 128.0, Rounding::Nearest, Overflow::Wrap).to_raw(), 5);
+        assert_eq!(Q7::from_float(-1.25, Rounding::Nearest, Overflow::Wrap).to_raw(), 96);
+    }
+
+    #[test]
+    fn test_overflow_aware_ops() {
+        let half = Q15::from_raw(1 << 14);
+        assert_eq!(half._checked_add(half), None);
+        assert_eq!(half._checked_mul(half), Some(Q15::from_raw(1 << 13)));
+        assert_eq!(half._checked_div(Q15::_ZERO), None);
+        assert_eq!(Q15::_MIN._checked_neg(), None);
+        assert_eq!(half._wrapping_add(half), Q15::_MIN);
+        assert_eq!(Q15::_MIN._wrapping_neg(), Q15::_MIN);
+        assert_eq!(half._saturating_add(half), Q15::_MAX);
+        assert_eq!(half._overflowing_sub(-half), (Q15::_MIN, true));
+        assert_eq!(half._overflowing_div(half), (Q15::_MIN, true));
+        assert_eq!(Q15::_MIN._overflowing_rem(Q15::from_raw(-1)), (Q15::_ZERO, true));
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        type Q4 = Fixed<i16, 4>;
+        let x = -2.5 / 16.0;
+        assert_eq!(Q4::from_float(x, Rounding::Truncate, Overflow::Saturate).to_raw(), -2);
+        assert_eq!(Q4::from_float(x, Rounding::Floor, Overflow::Saturate).to_raw(), -3);
+        assert_eq!(Q4::from_float(x, Rounding::Nearest, Overflow::Saturate).to_raw(), -3);
+        assert_eq!(Q4::from_float(x, Rounding::Convergent, Overflow::Saturate).to_raw(), -2);
+        let three = Q4::from_i32(3).unwrap();
+        let two = Q4::from_i32(2).unwrap();
+        assert_eq!((three / two).to_float(), 1.5);
+        assert_eq!(Q4::_ONE.div_with(three, Rounding::Floor, Overflow::Saturate).to_raw(), 5);
+        assert_eq!(Q4::_ONE.div_with(three, Rounding::Nearest, Overflow::Saturate).to_raw(), 5);
+        assert_eq!(Q4::_NEG_ONE.div_with(three, Rounding::Floor, Overflow::Saturate).to_raw(), -6);
+    }
+
+    #[test]
+    fn test_casts_and_repr() {
+        type Q8 = Fixed<i32, 8>;
+        let v = Q8::from_f64(-3.75).unwrap();
+        assert_eq!(v.to_i32(), Some(-3));
+        assert_eq!(v.to_u8(), None);
+        assert_eq!(v.to_f64(), Some(-3.75));
+        assert_eq!(Q8::from_i64(1 << 40), None);
+        assert_eq!(Q15::from_f64(1.0), None);
+        assert_eq!(Q8::_from_le_bytes(v._to_le_bytes()), v);
+        assert_eq!(Q8::_from_bits(v._to_bits()), v);
+        assert!(v._is_negative() && v._abs() == Q8::from_f64(3.75).unwrap());
+    }
+}
@@ -1,6 +1,9 @@
-﻿use super::*;
+﻿use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::num::FpCategory;
+use super::*;
 
-pub trait Float: Unit + Ordered + BoundedSigned + ExpFloat + CastPrimitive {
+pub trait Float: Unit + Inv + Ordered + BoundedSigned + ExpFloat + ExpBasic<Output = Self> + Trig + CastPrimitive {
     /// Special states
     const _NAN: Self;
     const _INFINITY: Self;
@@ -26,6 +29,115 @@ pub trait Float: Unit + Ordered + BoundedSigned + ExpFloat + CastPrimitive {
     fn _round(self) -> Self;
     fn _trunc(self) -> Self;
     fn _fract(self) -> Self;
+
+    /// Classification from the bit layout, units whose masks cover one component (dual numbers)
+    /// classify that component
+    fn _is_nan(self) -> bool {
+        let bits = self._to_bits();
+        bits & Self::_EXP_MASK == Self::_EXP_MASK && bits & Self::_SIG_MASK != <Self::BitsRepr as Zero>::_ZERO
+    }
+    fn _is_infinite(self) -> bool {
+        self._to_bits() & (Self::_EXP_MASK | Self::_SIG_MASK) == Self::_EXP_MASK
+    }
+    fn _is_finite(self) -> bool {
+        self._to_bits() & Self::_EXP_MASK != Self::_EXP_MASK
+    }
+    fn _is_subnormal(self) -> bool {
+        let bits = self._to_bits();
+        bits & Self::_EXP_MASK == <Self::BitsRepr as Zero>::_ZERO && bits & Self::_SIG_MASK != <Self::BitsRepr as Zero>::_ZERO
+    }
+    fn _is_normal(self) -> bool {
+        let exp = self._to_bits() & Self::_EXP_MASK;
+        exp != <Self::BitsRepr as Zero>::_ZERO && exp != Self::_EXP_MASK
+    }
+    fn _classify(self) -> FpCategory {
+        match self {
+            _ if self._is_nan() => FpCategory::Nan,
+            _ if self._is_infinite() => FpCategory::Infinite,
+            _ if self._is_subnormal() => FpCategory::Subnormal,
+            _ if self._is_normal() => FpCategory::Normal,
+            _ => FpCategory::Zero,
+        }
+    }
+    /// Smallest value above self, NaN and +inf stay as they are
+    fn _next_up(self) -> Self {
+        if self.partial_cmp(&Self::_INFINITY) != Some(Ordering::Less) {
+            return self;
+        }
+        let one = <Self::BitsRepr as One>::_ONE;
+        if self._is_zero() {
+            return Self::_from_bits(one);
+        }
+        let bits = self._to_bits();
+        if self > Self::_ZERO { Self::_from_bits(bits + one) } else { Self::_from_bits(bits - one) }
+    }
+    /// Largest value below self, NaN and -inf stay as they are
+    fn _next_down(self) -> Self {
+        -(-self)._next_up()
+    }
+    /// Number of representable values from self up to other (or down), +0 and -0 are the same and
+    /// NaN is `u64::MAX` away from everything
+    fn _ulp_distance(self, other: Self) -> u64 {
+        if self._is_nan() || other._is_nan() {
+            return u64::MAX;
+        }
+        let zero = <Self::BitsRepr as Zero>::_ZERO;
+        let magnitude = |x: Self| low_u64(x._to_bits() & (Self::_EXP_MASK | Self::_SIG_MASK));
+        let (a, b) = (magnitude(self), magnitude(other));
+        match (self._to_bits() & Self::_SIGN_MASK == zero) == (other._to_bits() & Self::_SIGN_MASK == zero) {
+            true => a.abs_diff(b),
+            false => a + b,
+        }
+    }
+    /// `|self - other| <= max(abs, rel * max(|self|, |other|))`, or at most `ulps` apart
+    /// - NaN is not approximately equal to anything, infinities only to themselves
+    fn _approx_eq(self, other: Self, rel: Self, abs: Self, ulps: u32) -> bool {
+        if self == other {
+            return !self._is_nan();
+        }
+        if !self._is_finite() || !other._is_finite() {
+            return false;
+        }
+        let diff = (self - other)._abs();
+        diff <= abs || diff <= rel * self._abs()._max(other._abs()) || self._ulp_distance(other) <= ulps as u64
+    }
+}
+
+/// Low 64 bits of a bit pattern
+fn low_u64<B: PhysicalRepr>(bits: B) -> u64 {
+    bits._to_le_bytes().as_ref().iter().take(8).rev().fold(0, |acc, &b| acc << 8 | b as u64)
+}
+
+/// First element where two slices are not `_approx_eq`, for `assert_approx_eq!`
+pub fn check_approx_eq<T: Float + Debug>(left: &[T], right: &[T], rel: T, abs: T, ulps: u32) -> Result<(), String> {
+    if left.len() != right.len() {
+        return Err(format!("lengths differ: {} != {}", left.len(), right.len()));
+    }
+    match left.iter().zip(right).position(|(&l, &r)| !l._approx_eq(r, rel, abs, ulps)) {
+        Some(i) => Err(format!("at index {i}: {:?} != {:?} ({} ulps apart)", left[i], right[i], left[i]._ulp_distance(right[i]))),
+        None => Ok(()),
+    }
+}
+
+/// Asserts two slices (arrays, vectors) of floats are element-wise `_approx_eq`
+/// - `assert_approx_eq!(left, right)` allows 4 ulps
+/// - `assert_approx_eq!(left, right, rel = 1e-6, abs = 1e-9, ulps = 0)` sets every tolerance
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let zero = <_ as $crate::units::Zero>::_ZERO;
+        $crate::assert_approx_eq!($left, $right, rel = zero, abs = zero, ulps = 4)
+    }};
+    ($left:expr, $right:expr, rel = $rel:expr, abs = $abs:expr, ulps = $ulps:expr $(,)?) => {{
+        match (&$left, &$right) {
+            (left, right) => {
+                let (left, right): (&[_], &[_]) = (::core::convert::AsRef::as_ref(left), ::core::convert::AsRef::as_ref(right));
+                if let Err(msg) = $crate::units::check_approx_eq(left, right, $rel, $abs, $ulps) {
+                    panic!("assertion `left ≈ right` failed, {msg}");
+                }
+            }
+        }
+    }};
 }
 
 macro_rules! impl_float {
@@ -109,6 +221,59 @@ macro_rules! impl_float {
                 $SrcT::ln_1p(self)
             }
         }
+        impl Trig for $SrcT {
+            fn _sin(self) -> Self {
+                $SrcT::sin(self)
+            }
+            fn _cos(self) -> Self {
+                $SrcT::cos(self)
+            }
+            fn _sin_cos(self) -> (Self, Self) {
+                $SrcT::sin_cos(self)
+            }
+            fn _tan(self) -> Self {
+                $SrcT::tan(self)
+            }
+            fn _asin(self) -> Self {
+                $SrcT::asin(self)
+            }
+            fn _acos(self) -> Self {
+                $SrcT::acos(self)
+            }
+            fn _atan(self) -> Self {
+                $SrcT::atan(self)
+            }
+            fn _atan2(self, other: Self) -> Self {
+                $SrcT::atan2(self, other)
+            }
+            fn _sinh(self) -> Self {
+                $SrcT::sinh(self)
+            }
+            fn _cosh(self) -> Self {
+                $SrcT::cosh(self)
+            }
+            fn _tanh(self) -> Self {
+                $SrcT::tanh(self)
+            }
+            fn _asinh(self) -> Self {
+                $SrcT::asinh(self)
+            }
+            fn _acosh(self) -> Self {
+                $SrcT::acosh(self)
+            }
+            fn _atanh(self) -> Self {
+                $SrcT::atanh(self)
+            }
+            fn _hypot(self, other: Self) -> Self {
+                $SrcT::hypot(self, other)
+            }
+            fn _to_degrees(self) -> Self {
+                $SrcT::to_degrees(self)
+            }
+            fn _to_radians(self) -> Self {
+                $SrcT::to_radians(self)
+            }
+        }
     }
 }
 
@@ -124,21 +289,24 @@ macro_rules! impl_basic_unit_bounds {
         }
         impl One for $SrcT {
             const _ONE: Self = 1 as $SrcT;
-            fn _recip(self) -> Self {
-                $SrcT::recip(self)
-            }
         }
         impl Inv for $SrcT {
             fn _inv(self) -> Self {
                 self._recip()
             }
+            fn _recip(self) -> Self {
+                $SrcT::recip(self)
+            }
         }
         impl Symbolic for $SrcT {
             type Base = $SrcT;
         }
+        // SAFETY: every bit pattern of a primitive float is a value (possibly NaN)
+        unsafe impl PlainBytes for $SrcT {}
         impl PhysicalRepr for $SrcT {
             const _BITS: u32 = size_of::<$SrcT>() as u32 * 8;
             const _BYTES: usize = size_of::<$SrcT>();
+            const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = Some(PlainBytesProof::PROOF);
             type BitsRepr = $SrcReprT;
             type BytesRepr = [u8; size_of::<$SrcT>()];
             fn _from_bits(v: Self::BitsRepr) -> Self {
@@ -220,4 +388,46 @@ macro_rules! impl_properties_signed {
 }
 
 impl_properties_signed!(f32);
-impl_properties_signed!(f64);
\ No newline at end of file
+impl_properties_signed!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let tiny = F16::from_bits(1);
+        assert_eq!([1.0f32._classify(), 0.0f32._classify(), f32::NAN._classify()], [FpCategory::Normal, FpCategory::Zero, FpCategory::Nan]);
+        assert_eq!([tiny._classify(), F16::_NEG_INFINITY._classify()], [FpCategory::Subnormal, FpCategory::Infinite]);
+        assert!(BF16::_NAN._is_nan() && !BF16::_INFINITY._is_finite() && BF16::_MAX._is_normal());
+        assert!(Dual::new(f64::NAN, [1.0])._is_nan() && Dual::new(1e-310, [f64::NAN])._is_subnormal());
+        assert!(f32x4::splat(1e-40)._is_subnormal() && !f32x4::from_array([1e-40, 1.0, 1.0, 1.0])._is_subnormal());
+        assert_eq!(F16::_ZERO._next_up(), tiny);
+        assert_eq!((-tiny)._next_up(), -F16::_ZERO);
+        assert_eq!(1.0f64._next_down()._next_up(), 1.0);
+        assert_eq!(f64::MAX._next_up(), f64::INFINITY);
+        assert_eq!(f32::NEG_INFINITY._next_down(), f32::NEG_INFINITY);
+        assert_eq!(f32::MIN_POSITIVE._next_down(), f32::MIN_POSITIVE - f32::from_bits(1));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        assert_eq!(1.0f32._ulp_distance(1.0f32._next_up()._next_up()), 2);
+        assert_eq!((-0.0f64)._ulp_distance(0.0), 0);
+        assert_eq!(f64::from_bits(1)._ulp_distance(-f64::from_bits(2)), 3);
+        assert_eq!(f32::NAN._ulp_distance(f32::NAN), u64::MAX);
+        assert_eq!(f32::MAX._ulp_distance(f32::INFINITY), 1);
+        assert!(1.0f64._approx_eq(1.0 + 1e-12, 1e-9, 0.0, 0));
+        assert!(!1.0f64._approx_eq(1.0 + 1e-12, 1e-13, 0.0, 0));
+        assert!(1e-20f32._approx_eq(0.0, 0.0, 1e-9, 0));
+        assert!(!f32::NAN._approx_eq(f32::NAN, 1.0, 1.0, 100));
+        assert!(f32::INFINITY._approx_eq(f32::INFINITY, 0.0, 0.0, 0) && !f32::INFINITY._approx_eq(f32::MAX, 0.0, 0.0, 4));
+        let sum: f32 = (0..10).map(|_| 0.1f32).sum();
+        assert_approx_eq!([sum, 0.3], [1.0, 0.1 + 0.2]);
+        assert_approx_eq!(vec![F16::from_f32(0.1)], [F16::from_f32(0.1)._next_up()]);
+        assert_approx_eq!([1.0, 2.0], [1.001f64, 2.002], rel = 1e-3, abs = 0.0, ulps = 0);
+        let mismatch = check_approx_eq(&[1.0f64, 2.0], &[1.0, 2.1], 1e-3, 0.0, 4);
+        assert!(mismatch.unwrap_err().starts_with("at index 1: 2.0 != 2.1"));
+        assert!(check_approx_eq(&[1.0f32], &[], 0.0, 0.0, 0).is_err());
+    }
+}
@@ -0,0 +1,688 @@
+use std::cmp::Ordering;
+use std::fmt::LowerExp;
+use thiserror::Error;
+use super::*;
+
+// Text form of units, exact in both directions
+// - parsing rounds the exact value of the literal once: to nearest even for floats, to nearest (ties
+//   away from zero) for fixed point, integers only accept integral literals
+// - formatting emits the shortest digits that parse back to the same value
+// - literals are `[+-]digits[.digits]` in any radix in 2..=36, in radix 10 also with an exponent
+//   (`1.5e-3`), `0x` / `0o` / `0b` prefixes and hexadecimal floats with a binary exponent (`0x1.8p-3`)
+// - `inf`, `infinity` and `nan` (any case) are the special float values in every radix
+// - composites: complex `re+imi`, rational `n/d`, interval `[lo, hi]`, dual `(val, [g0, g1])` and
+//   lane vectors `[a, b, c, d]`, a bare scalar parses as a real, point, constant or splat
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseUnitError {
+    #[error("cannot parse a unit from an empty string")]
+    Empty,
+    #[error("invalid unit literal")]
+    Invalid,
+    #[error("literal is out of the range of the unit")]
+    OutOfRange,
+    #[error("radix {0} is not in 2..=36")]
+    UnsupportedRadix(u32),
+}
+
+pub trait ParseUnit: Sized {
+    /// Decimal literal, or a prefixed binary, octal or hexadecimal one
+    fn parse_unit(s: &str) -> Result<Self, ParseUnitError> {
+        Self::parse_unit_radix(s, 10)
+    }
+    fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError>;
+}
+
+pub trait FormatUnit {
+    /// Shortest decimal form, very large and small floats use scientific notation
+    fn format_unit(&self) -> String {
+        self.format_unit_radix(10)
+    }
+    /// Shortest positional form in `radix` (decimal floats as `format_unit`), panics when `radix` is not in 2..=36
+    fn format_unit_radix(&self, radix: u32) -> String;
+    /// Shortest decimal scientific notation, e.g. `1.5e-3`
+    fn format_unit_exp(&self) -> String;
+}
+
+// Literals ========================================================================================
+
+/// Exact value of a literal
+enum Literal {
+    /// (negative, num, den)
+    Finite(bool, BigUint, BigUint),
+    Infinite(bool),
+    Nan,
+}
+
+/// Exponents are clamped to this (plus the digit count), beyond it every unit over- or underflows
+const EXP_LIMIT: i64 = 4096;
+
+fn check_radix(radix: u32) -> Result<(), ParseUnitError> {
+    if (2..=36).contains(&radix) { Ok(()) } else { Err(ParseUnitError::UnsupportedRadix(radix)) }
+}
+
+/// Decimal exponent with an optional sign, saturating
+fn parse_exponent(s: &str) -> Result<i64, ParseUnitError> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseUnitError::Invalid);
+    }
+    let magnitude = digits.bytes().fold(0i64, |acc, b| acc.saturating_mul(10).saturating_add((b - b'0') as i64));
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// `specials` accepts `inf`, `infinity` and `nan`, which take precedence over digits in radices that
+/// spell them, so only float targets enable it
+fn parse_literal(s: &str, radix: u32, specials: bool) -> Result<Literal, ParseUnitError> {
+    check_radix(radix)?;
+    if s.is_empty() {
+        return Err(ParseUnitError::Empty);
+    }
+    let (negative, body) = match s.strip_prefix('-') {
+        Some(body) => (true, body),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    match body.to_ascii_lowercase().as_str() {
+        "inf" | "infinity" if specials => return Ok(Literal::Infinite(negative)),
+        "nan" if specials => return Ok(Literal::Nan),
+        _ => {}
+    }
+    // (radix of the digits, rest, exponent marker and the base it scales by)
+    let (radix, body, exponent) = match body.get(..2).map(|p| p.to_ascii_lowercase()).as_deref() {
+        _ if radix != 10 => (radix, body, None),
+        Some("0x") => (16, &body[2..], Some(('p', 2u32))),
+        Some("0o") => (8, &body[2..], None),
+        Some("0b") => (2, &body[2..], None),
+        _ => (10, body, Some(('e', 10))),
+    };
+    let (mantissa, exp) = match exponent.and_then(|(marker, _)| body.find(|c: char| c.to_ascii_lowercase() == marker)) {
+        Some(i) => (&body[..i], parse_exponent(&body[i + 1..])?),
+        None => (body, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let valid = |digits: &str| digits.chars().all(|c| c.is_digit(radix));
+    if int.len() + frac.len() == 0 || !valid(int) || !valid(frac) {
+        return Err(ParseUnitError::Invalid);
+    }
+    let digits = [int, frac].concat();
+    let mut num = BigUint::from_str_radix(&digits, radix).map_err(|_| ParseUnitError::Invalid)?;
+    let mut den = BigUint::from(radix).pow(frac.len() as u32);
+    if let (Some((_, base)), false) = (exponent, num.is_zero() || exp == 0) {
+        let limit = EXP_LIMIT + 4 * digits.len() as i64;
+        let exp = exp.clamp(-limit, limit);
+        let scale = BigUint::from(base).pow(exp.unsigned_abs() as u32);
+        if exp > 0 { num *= scale } else { den *= scale }
+    }
+    Ok(Literal::Finite(negative, num, den))
+}
+
+/// num / den rounded to nearest, ties to even or away from zero
+fn round_div(num: &BigUint, den: &BigUint, ties_even: bool) -> BigUint {
+    let (q, r) = num.div_rem(den);
+    match (&r << 1).cmp(den) {
+        Ordering::Greater => q + BigUint::one(),
+        Ordering::Equal if !ties_even || q.bit(0) => q + BigUint::one(),
+        _ => q,
+    }
+}
+
+/// num / den rounded to nearest even in a binary float format (see `CastValue::to_float`), the result
+/// is exact in f64
+fn round_binary(negative: bool, num: &BigUint, den: &BigUint, digits: u32, min_exp: i32, max_exp: i32) -> f64 {
+    let exact = |mag: u128, exp: i64| {
+        let v = CastValue::Finite { negative, mag, exp: exp as i32 };
+        v.to_float(digits, min_exp, max_exp, Rounding::Convergent, Some(Overflow::Wrap)).unwrap()
+    };
+    if num.is_zero() {
+        return exact(0, 0);
+    }
+    let p = digits as i64;
+    // num / den is in [2^(e-1), 2^(e+1)), scaled by 2^shift it has p or p+1 integer bits, but never
+    // more fraction bits than the smallest subnormal
+    let e = num.bits() as i64 - den.bits() as i64;
+    let mut shift = (p - e).min(p - min_exp as i64);
+    let scaled = |shift: i64| match shift >= 0 {
+        true => (num << shift as usize, den.clone()),
+        false => (num.clone(), den << (-shift) as usize),
+    };
+    let (mut n, mut d) = scaled(shift);
+    if (&n / &d).bits() as i64 > p {
+        shift -= 1;
+        (n, d) = scaled(shift);
+    }
+    // at most p + 1 bits after rounding, exact from here on (or infinite)
+    exact(round_div(&n, &d, true).to_u128().unwrap(), -shift)
+}
+
+fn parse_float<T: Float>(s: &str, radix: u32) -> Result<T, ParseUnitError> {
+    Ok(match parse_literal(s, radix, true)? {
+        Literal::Finite(negative, num, den) => {
+            let v = round_binary(negative, &num, &den, T::_MANTISSA_DIGITS, T::_MIN_EXP, T::_MAX_EXP);
+            T::from_f64(v).unwrap()
+        }
+        Literal::Infinite(false) => T::_INFINITY,
+        Literal::Infinite(true) => T::_NEG_INFINITY,
+        Literal::Nan => T::_NAN,
+    })
+}
+
+/// Value of an integral literal
+fn parse_integer(s: &str, radix: u32) -> Result<BigInt, ParseUnitError> {
+    match parse_literal(s, radix, false)? {
+        Literal::Finite(negative, num, den) => {
+            let (q, r) = num.div_rem(&den);
+            if r.is_zero() { Ok(BigInt::from_parts(negative, q)) } else { Err(ParseUnitError::Invalid) }
+        }
+        _ => Err(ParseUnitError::Invalid),
+    }
+}
+
+/// Comma separated items of `s` enclosed in `open` and `close`, commas inside nested brackets are skipped
+fn split_list(s: &str, open: char, close: char) -> Option<Vec<&str>> {
+    let inner = s.strip_prefix(open)?.strip_suffix(close)?;
+    let (mut items, mut depth, mut start) = (Vec::new(), 0i32, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(inner[start..].trim());
+    Some(items)
+}
+
+fn parse_array<T: ParseUnit, const N: usize>(items: &[&str], radix: u32) -> Result<[T; N], ParseUnitError> {
+    let values = items.iter().map(|s| T::parse_unit_radix(s, radix)).collect::<Result<Vec<T>, _>>()?;
+    values.try_into().map_err(|_| ParseUnitError::Invalid)
+}
+
+// Formatting ======================================================================================
+
+fn assert_radix(radix: u32) {
+    assert!((2..=36).contains(&radix), "radix {radix} is not in 2..=36");
+}
+
+/// (negative, digits, exponent) of a number formatted with `{:e}`
+fn split_exp(s: &str) -> (bool, String, i32) {
+    let (negative, s) = s.strip_prefix('-').map_or((false, s), |s| (true, s));
+    let (mantissa, exp) = s.split_once('e').unwrap();
+    (negative, mantissa.replace('.', ""), exp.parse().unwrap())
+}
+
+/// d.ddde<exp>
+fn render_scientific(negative: bool, digits: &str, exp: i32) -> String {
+    let sign = if negative { "-" } else { "" };
+    let point = if digits.len() > 1 { "." } else { "" };
+    format!("{sign}{}{point}{}e{exp}", &digits[..1], &digits[1..])
+}
+
+/// Positional for decimal exponents in -6..21, scientific otherwise
+fn render_decimal(negative: bool, digits: &str, exp: i32) -> String {
+    if !(-6..21).contains(&exp) {
+        return render_scientific(negative, digits, exp);
+    }
+    let mut out = String::from(if negative { "-" } else { "" });
+    if exp < 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat((-exp - 1) as usize));
+        out.push_str(digits);
+    } else {
+        let int_len = exp as usize + 1;
+        if digits.len() <= int_len {
+            out.push_str(digits);
+            out.push_str(&"0".repeat(int_len - digits.len()));
+        } else {
+            out.push_str(&digits[..int_len]);
+            out.push('.');
+            out.push_str(&digits[int_len..]);
+        }
+    }
+    out
+}
+
+/// Scientific form of a positional decimal
+fn positional_to_scientific(s: &str) -> String {
+    let (negative, s) = s.strip_prefix('-').map_or((false, s), |s| (true, s));
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    let digits = [int, frac].concat();
+    match digits.find(|c| c != '0') {
+        Some(i) => render_scientific(negative, digits[i..].trim_end_matches('0'), int.len() as i32 - 1 - i as i32),
+        None => render_scientific(negative, "0", 0),
+    }
+}
+
+/// Shortest positional digits of num / den in `radix` that `accept` parses back, rounded to nearest
+/// at each length
+fn shortest_positional(negative: bool, num: &BigUint, den: &BigUint, radix: u32, accept: impl Fn(&str) -> bool) -> String {
+    let (radix_big, mut scale, mut frac_len) = (BigUint::from(radix), BigUint::one(), 0);
+    loop {
+        let mut digits = round_div(&(num * &scale), den, true).to_str_radix(radix);
+        if digits.len() <= frac_len {
+            digits.insert_str(0, &"0".repeat(frac_len + 1 - digits.len()));
+        }
+        if frac_len > 0 {
+            digits.insert(digits.len() - frac_len, '.');
+        }
+        if negative {
+            digits.insert(0, '-');
+        }
+        if accept(&digits) {
+            return digits;
+        }
+        scale = &scale * &radix_big;
+        frac_len += 1;
+    }
+}
+
+/// |x| as num / den for a finite x
+fn float_ratio(x: f64) -> (BigUint, BigUint) {
+    let CastValue::Finite { mag, exp, .. } = CastValue::from_f64(x) else {
+        unreachable!("{x} is not finite")
+    };
+    if exp >= 0 {
+        (BigUint::from(mag) << exp as usize, BigUint::one())
+    } else {
+        (BigUint::from(mag), BigUint::one() << (-exp) as usize)
+    }
+}
+
+/// Shortest round trip decimal of a finite f32 or f64
+fn shortest_native<T: Float + LowerExp>(x: T) -> (bool, String, i32) {
+    split_exp(&format!("{x:e}"))
+}
+
+/// Shortest round trip decimal of a finite half, the f32 digits are shortened until they parse back
+fn shortest_half<T: Float>(x: T) -> (bool, String, i32) {
+    let wide = x.to_f32().unwrap();
+    (0..)
+        .find_map(|precision| {
+            let s = format!("{wide:.precision$e}");
+            let y = parse_float::<T>(&s, 10).ok()?;
+            (y._to_bits() == x._to_bits()).then(|| split_exp(&s))
+        })
+        .unwrap()
+}
+
+fn format_special(x: f64) -> Option<String> {
+    match x {
+        _ if x.is_nan() => Some("NaN".into()),
+        f64::INFINITY => Some("inf".into()),
+        f64::NEG_INFINITY => Some("-inf".into()),
+        _ => None,
+    }
+}
+
+fn format_float<T: Float + ParseUnit>(x: T, radix: u32, shortest: fn(T) -> (bool, String, i32)) -> String {
+    assert_radix(radix);
+    let wide = x.to_f64().unwrap();
+    if let Some(special) = format_special(wide) {
+        return special;
+    }
+    if radix == 10 {
+        let (negative, digits, exp) = shortest(x);
+        return render_decimal(negative, &digits, exp);
+    }
+    let (num, den) = float_ratio(wide);
+    let accept = |s: &str| T::parse_unit_radix(s, radix).is_ok_and(|y| y._to_bits() == x._to_bits());
+    shortest_positional(wide.is_sign_negative(), &num, &den, radix, accept)
+}
+
+fn format_float_exp<T: Float>(x: T, shortest: fn(T) -> (bool, String, i32)) -> String {
+    format_special(x.to_f64().unwrap()).unwrap_or_else(|| {
+        let (negative, digits, exp) = shortest(x);
+        render_scientific(negative, &digits, exp)
+    })
+}
+
+fn format_list(items: impl IntoIterator<Item = String>) -> String {
+    format!("[{}]", items.into_iter().collect::<Vec<_>>().join(", "))
+}
+
+// Primitive implementations =======================================================================
+
+macro_rules! impl_integer_text {
+    ($($T:ident),*) => {$(
+        /// Integral literals only, e.g. `1.5e3` but not `1.5`
+        impl ParseUnit for $T {
+            fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+                let n = parse_integer(s, radix)?;
+                let n = if n.is_negative() { n.to_i128().and_then($T::from_i128) } else { n.to_u128().and_then($T::from_u128) };
+                n.ok_or(ParseUnitError::OutOfRange)
+            }
+        }
+
+        impl FormatUnit for $T {
+            fn format_unit_radix(&self, radix: u32) -> String {
+                assert_radix(radix);
+                BigInt::from(*self).to_str_radix(radix)
+            }
+            fn format_unit_exp(&self) -> String {
+                format!("{self:e}")
+            }
+        }
+    )*}
+}
+
+impl_integer_text!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_float_text {
+    ($($T:ident => $shortest:ident),*) => {$(
+        /// Correctly rounded, ties to even
+        impl ParseUnit for $T {
+            fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+                parse_float(s, radix)
+            }
+        }
+
+        impl FormatUnit for $T {
+            fn format_unit_radix(&self, radix: u32) -> String {
+                format_float(*self, radix, $shortest)
+            }
+            fn format_unit_exp(&self) -> String {
+                format_float_exp(*self, $shortest)
+            }
+        }
+    )*}
+}
+
+impl_float_text!(f32 => shortest_native, f64 => shortest_native, F16 => shortest_half, BF16 => shortest_half);
+
+macro_rules! impl_fixed_text {
+    ($($SrcT:ident),*) => {$(
+        /// Rounded to nearest, ties away from zero, `OutOfRange` instead of saturating
+        impl<const FRAC: u32> ParseUnit for Fixed<$SrcT, FRAC> {
+            fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+                let Literal::Finite(negative, num, den) = parse_literal(s, radix, false)? else {
+                    return Err(ParseUnitError::Invalid);
+                };
+                let bits = BigInt::from_parts(negative, round_div(&(num << FRAC as usize), &den, false));
+                bits.to_i128().and_then($SrcT::from_i128).map(Self::from_raw).ok_or(ParseUnitError::OutOfRange)
+            }
+        }
+
+        impl<const FRAC: u32> FormatUnit for Fixed<$SrcT, FRAC> {
+            fn format_unit_radix(&self, radix: u32) -> String {
+                assert_radix(radix);
+                let raw = self.to_raw();
+                let (num, den) = (BigUint::from(raw.unsigned_abs()), BigUint::one() << FRAC as usize);
+                shortest_positional(raw < 0, &num, &den, radix, |s| Self::parse_unit_radix(s, radix) == Ok(*self))
+            }
+            fn format_unit_exp(&self) -> String {
+                positional_to_scientific(&self.format_unit())
+            }
+        }
+    )*}
+}
+
+impl_fixed_text!(i8, i16, i32, i64);
+
+macro_rules! impl_simd_text {
+    ($($V:ident),*) => {$(
+        /// `[a, b, ...]` with every lane, or a scalar for all lanes
+        impl ParseUnit for $V {
+            fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+                match split_list(s, '[', ']') {
+                    Some(items) => parse_array(&items, radix).map($V::from_array),
+                    None => ParseUnit::parse_unit_radix(s, radix).map($V::splat),
+                }
+            }
+        }
+
+        impl FormatUnit for $V {
+            fn format_unit_radix(&self, radix: u32) -> String {
+                format_list(self.to_array().iter().map(|x| x.format_unit_radix(radix)))
+            }
+            fn format_unit_exp(&self) -> String {
+                format_list(self.to_array().iter().map(|x| x.format_unit_exp()))
+            }
+        }
+    )*}
+}
+
+impl_simd_text!(f32x4, f32x8, f64x2, f64x4, u32x4, u32x8, u64x2, u64x4);
+
+// Composite implementations =======================================================================
+
+/// `re`, `imi` or `re+imi` (or `re-imi`), a bare `i` is a unit imaginary part
+impl<T: Float + ParseUnit> ParseUnit for Complex<T> {
+    fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+        check_radix(radix)?;
+        let imaginary = |s: &str| match s {
+            "" | "+" => Ok(T::_ONE),
+            "-" => Ok(-T::_ONE),
+            _ => T::parse_unit_radix(s, radix),
+        };
+        let Some(body) = s.strip_suffix('i') else {
+            return T::parse_unit_radix(s, radix).map(|re| Complex::new(re, T::_ZERO));
+        };
+        // the imaginary part starts at the last sign both sides parse at (an exponent sign does not)
+        for i in (1..body.len()).rev().filter(|&i| matches!(body.as_bytes()[i], b'+' | b'-')) {
+            if let (Ok(re), Ok(im)) = (T::parse_unit_radix(&body[..i], radix), imaginary(&body[i..])) {
+                return Ok(Complex::new(re, im));
+            }
+        }
+        imaginary(body).map(|im| Complex::new(T::_ZERO, im))
+    }
+}
+
+impl<T: Float + FormatUnit> FormatUnit for Complex<T> {
+    fn format_unit_radix(&self, radix: u32) -> String {
+        let im = self.im.format_unit_radix(radix);
+        let sign = if im.starts_with('-') { "" } else { "+" };
+        format!("{}{sign}{im}i", self.re.format_unit_radix(radix))
+    }
+    fn format_unit_exp(&self) -> String {
+        let im = self.im.format_unit_exp();
+        let sign = if im.starts_with('-') { "" } else { "+" };
+        format!("{}{sign}{im}i", self.re.format_unit_exp())
+    }
+}
+
+/// `n/d` or an integer `n`, reduced to lowest terms
+impl<T: Integer + ParseUnit> ParseUnit for Rational<T> {
+    fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+        match s.split_once('/') {
+            Some((num, den)) => {
+                let (num, den) = (T::parse_unit_radix(num.trim(), radix)?, T::parse_unit_radix(den.trim(), radix)?);
+                Rational::checked_new(num, den).ok_or(ParseUnitError::Invalid)
+            }
+            None => T::parse_unit_radix(s, radix).map(Rational::from_integer),
+        }
+    }
+}
+
+impl<T: Integer + FormatUnit> FormatUnit for Rational<T> {
+    fn format_unit_radix(&self, radix: u32) -> String {
+        match self.denom() == T::_ONE {
+            true => self.numer().format_unit_radix(radix),
+            false => format!("{}/{}", self.numer().format_unit_radix(radix), self.denom().format_unit_radix(radix)),
+        }
+    }
+    fn format_unit_exp(&self) -> String {
+        match self.denom() == T::_ONE {
+            true => self.numer().format_unit_exp(),
+            false => format!("{}/{}", self.numer().format_unit_exp(), self.denom().format_unit_exp()),
+        }
+    }
+}
+
+/// `[lo, hi]`, or a scalar for the point interval
+impl<T: Float + ParseUnit> ParseUnit for Interval<T> {
+    fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+        let Some(items) = split_list(s, '[', ']') else {
+            return T::parse_unit_radix(s, radix).map(Interval::point);
+        };
+        let [lo, hi] = parse_array::<T, 2>(&items, radix)?;
+        match lo.partial_cmp(&hi) {
+            Some(Ordering::Greater) => Err(ParseUnitError::Invalid),
+            _ => Ok(Interval { lo, hi }),
+        }
+    }
+}
+
+impl<T: Float + FormatUnit> FormatUnit for Interval<T> {
+    fn format_unit_radix(&self, radix: u32) -> String {
+        format_list([self.lo.format_unit_radix(radix), self.hi.format_unit_radix(radix)])
+    }
+    fn format_unit_exp(&self) -> String {
+        format_list([self.lo.format_unit_exp(), self.hi.format_unit_exp()])
+    }
+}
+
+/// `(val, [g0, g1, ...])`, or a scalar for a constant
+impl<T: Float + ParseUnit, const N: usize> ParseUnit for DualN<T, N> {
+    fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+        let Some(items) = split_list(s, '(', ')') else {
+            return T::parse_unit_radix(s, radix).map(DualN::constant);
+        };
+        let [val, grad] = items[..] else {
+            return Err(ParseUnitError::Invalid);
+        };
+        let grad = split_list(grad, '[', ']').ok_or(ParseUnitError::Invalid)?;
+        Ok(DualN::new(T::parse_unit_radix(val, radix)?, parse_array(&grad, radix)?))
+    }
+}
+
+impl<T: Float + FormatUnit, const N: usize> FormatUnit for DualN<T, N> {
+    fn format_unit_radix(&self, radix: u32) -> String {
+        format!("({}, {})", self.val.format_unit_radix(radix), format_list(self.grad.iter().map(|g| g.format_unit_radix(radix))))
+    }
+    fn format_unit_exp(&self) -> String {
+        format!("({}, {})", self.val.format_unit_exp(), format_list(self.grad.iter().map(|g| g.format_unit_exp())))
+    }
+}
+
+impl<T: ParseUnit> ParseUnit for Saturating<T> {
+    fn parse_unit_radix(s: &str, radix: u32) -> Result<Self, ParseUnitError> {
+        T::parse_unit_radix(s, radix).map(Saturating)
+    }
+}
+
+impl<T: FormatUnit> FormatUnit for Saturating<T> {
+    fn format_unit_radix(&self, radix: u32) -> String {
+        self.0.format_unit_radix(radix)
+    }
+    fn format_unit_exp(&self) -> String {
+        self.0.format_unit_exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<T: ParseUnit + FormatUnit + PhysicalRepr>(x: T, radix: u32) -> bool {
+        let same = |s: String| T::parse_unit_radix(&s, radix).is_ok_and(|y| y._to_bits() == x._to_bits());
+        same(x.format_unit_radix(radix)) && (radix != 10 || same(x.format_unit_exp()))
+    }
+
+    #[test]
+    fn test_parse_literals() {
+        assert_eq!(f64::parse_unit("0.1"), Ok(0.1));
+        assert_eq!(f64::parse_unit("-1.5e-3"), Ok(-1.5e-3));
+        assert_eq!(f64::parse_unit("0x1.8p1"), Ok(3.0));
+        assert_eq!(f64::parse_unit("2.4703282292062328e-324"), Ok(5e-324));
+        assert_eq!(f64::parse_unit("2.4703282292062327e-324"), Ok(0.0));
+        assert_eq!(f64::parse_unit("1e400"), Ok(f64::INFINITY));
+        assert_eq!(f32::parse_unit("-Infinity"), Ok(f32::NEG_INFINITY));
+        assert!(f32::parse_unit("nan").unwrap().is_nan());
+        assert_eq!(f32::parse_unit("16777217"), Ok(16777216.0));
+        assert_eq!(f32::parse_unit_radix("-0.1", 2), Ok(-0.5));
+        assert_eq!(F16::parse_unit("65520"), Ok(F16::_INFINITY));
+        assert_eq!(F16::parse_unit("65519"), Ok(F16::_MAX));
+        assert_eq!(u8::parse_unit("0xff"), Ok(255));
+        assert_eq!(i32::parse_unit("-0b101"), Ok(-5));
+        assert_eq!(u64::parse_unit("1.5e3"), Ok(1500));
+        assert_eq!(u32::parse_unit_radix("zz", 36), Ok(1295));
+        assert_eq!(u8::parse_unit("256"), Err(ParseUnitError::OutOfRange));
+        assert_eq!(u8::parse_unit("-1"), Err(ParseUnitError::OutOfRange));
+        assert_eq!(i16::parse_unit("1.5"), Err(ParseUnitError::Invalid));
+        assert_eq!(i16::parse_unit("1e"), Err(ParseUnitError::Invalid));
+        assert_eq!(i16::parse_unit(""), Err(ParseUnitError::Empty));
+        assert_eq!(i16::parse_unit_radix("1", 37), Err(ParseUnitError::UnsupportedRadix(37)));
+        assert_eq!(Q15::parse_unit("0.5"), Ok(Q15::from_raw(1 << 14)));
+        assert_eq!(Q15::parse_unit("-1"), Ok(Q15::_MIN));
+        assert_eq!(Q15::parse_unit("1"), Err(ParseUnitError::OutOfRange));
+        assert_eq!(Complex::<f64>::parse_unit("1e+2-3i"), Ok(Complex::new(100.0, -3.0)));
+        assert_eq!(Complex::<f64>::parse_unit("-i"), Ok(Complex::new(0.0, -1.0)));
+        assert_eq!(Complex::<f64>::parse_unit("2.5e+1i"), Ok(Complex::new(0.0, 25.0)));
+        assert_eq!(Rational::<i32>::parse_unit("6/-4"), Ok(Rational::new(-3, 2)));
+        assert_eq!(Interval::<f64>::parse_unit("[-1, 2.5]"), Ok(Interval::new(-1.0, 2.5)));
+        assert_eq!(Interval::<f64>::parse_unit("[2, 1]"), Err(ParseUnitError::Invalid));
+        assert_eq!(f32x4::parse_unit("[1, 2, 3, 4]").map(f32x4::to_array), Ok([1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(f32x4::parse_unit("[1, 2, 3]").map(f32x4::to_array), Err(ParseUnitError::Invalid));
+        assert_eq!(Dual::<f64>::parse_unit("(3, [1])").map(|d| (d.val, d.grad)), Ok((3.0, [1.0])));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(0.1f64.format_unit(), "0.1");
+        assert_eq!((-1.5e-7f64).format_unit(), "-1.5e-7");
+        assert_eq!(1e21f64.format_unit(), "1e21");
+        assert_eq!(123456.0f32.format_unit(), "123456");
+        assert_eq!(0.1f32.format_unit_exp(), "1e-1");
+        assert_eq!((-0.0f64).format_unit(), "-0");
+        assert_eq!(F16::from_f32(0.1).format_unit(), "0.1");
+        assert_eq!(3.0f64.format_unit_radix(2), "11");
+        assert_eq!(0.75f32.format_unit_radix(16), "0.c");
+        assert_eq!(f64::NEG_INFINITY.format_unit(), "-inf");
+        assert_eq!((-255i32).format_unit_radix(16), "-ff");
+        assert_eq!(1200u16.format_unit_exp(), "1.2e3");
+        assert_eq!(Q15::from_raw(1 << 14).format_unit(), "0.5");
+        assert_eq!(Q15::from_raw(-3).format_unit_exp(), "-1e-4");
+        assert_eq!(Complex::new(1.0f64, -2.0).format_unit(), "1-2i");
+        assert_eq!(Rational::new(3u8, 6).format_unit(), "1/2");
+        assert_eq!(f64x2::splat(0.5).format_unit(), "[0.5, 0.5]");
+        assert_eq!(Dual::new(2.0f32, [1.0]).format_unit(), "(2, [1])");
+        assert_eq!(Saturating(7u8).format_unit_radix(2), "111");
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for bits in 0..=u16::MAX {
+            let (h, b) = (F16::from_bits(bits), BF16::from_bits(bits));
+            if !h.to_f32().is_nan() {
+                assert!(roundtrip(h, 10) && (bits % 61 != 0 || roundtrip(h, 3)), "{bits:#06x}");
+            }
+            if !b.to_f32().is_nan() {
+                assert!(roundtrip(b, 10), "{bits:#06x}");
+            }
+        }
+        let mut state = 0x2545f4914f6cdd1du64;
+        for _ in 0..2000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let (x, y) = (f64::from_bits(state), f32::from_bits(state as u32));
+            assert!(x.is_nan() || roundtrip(x, 10), "{x:e}");
+            assert!(y.is_nan() || (roundtrip(y, 10) && roundtrip(y, 7)), "{y:e}");
+            assert!(roundtrip(Q31::from_raw(state as i32), 10));
+            assert!(roundtrip(state as i64, 36));
+        }
+        assert!(roundtrip(f64::MIN_POSITIVE / 3.0, 16));
+        assert!(roundtrip(f64::MAX, 5));
+    }
+
+    #[test]
+    fn test_roundtrip_special_words() {
+        // `inf`, `nan` and `infinity` are plain digits in high radices
+        assert_eq!(24171u32.format_unit_radix(36), "inf");
+        assert_eq!(u32::parse_unit_radix("inf", 36), Ok(24171));
+        assert_eq!(i64::parse_unit_radix("-NaN", 36), Ok(-30191));
+        assert_eq!(u64::parse_unit_radix("infinity", 36), Ok(1461559270678));
+        assert_eq!(u32::parse_unit("inf"), Err(ParseUnitError::Invalid));
+        for n in (0..1u32 << 20).step_by(7).chain([24171, 30191]) {
+            assert!(roundtrip(n, 36) && roundtrip(n as i32 - (1 << 19), 24), "{n}");
+        }
+        assert!(roundtrip(Fixed::<i32, 0>::from_raw(24171), 36));
+        assert!(roundtrip(24171.0f64, 36) && roundtrip(f64::INFINITY, 36));
+    }
+}
@@ -0,0 +1,538 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use super::*;
+
+/// IEEE 754 binary16 half precision float (1 sign, 5 exponent, 10 significand bits)
+/// - storage only, arithmetic is carried out in f32 and rounded back (exact for + - * / and sqrt)
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+pub struct F16(u16);
+
+/// bfloat16 "brain" float (1 sign, 8 exponent, 7 significand bits), a truncated f32
+/// - storage only, arithmetic is carried out in f32 and rounded back (exact for + - * / and sqrt,
+///   except for subnormal results: those are already rounded in f32 and rounding again to BF16 can
+///   be off by one ulp)
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+pub struct BF16(u16);
+
+/// Rounds an f64 to the nearest (ties to even) binary interchange format with the given field widths
+/// - overflow rounds to infinity, underflow to a subnormal or signed zero
+/// - NaN stays NaN (quieted), keeping the upper payload bits
+const fn narrow_bits(x: f64, sig_bits: u32, exp_bits: u32) -> u16 {
+    let bits = x.to_bits();
+    let sign = ((bits >> 63) as u16) << 15;
+    let exp = ((bits >> 52) & 0x7ff) as i32;
+    let man = bits & ((1 << 52) - 1);
+    let max_exp = (1u64 << exp_bits) - 1;
+    let inf = sign | (max_exp << sig_bits) as u16;
+    if exp == 0x7ff {
+        return if man == 0 {
+            inf
+        } else {
+            inf | (1 << (sig_bits - 1)) | (man >> (52 - sig_bits)) as u16
+        };
+    }
+    if exp == 0 {
+        // f64 subnormals are far below the smallest half subnormal
+        return sign;
+    }
+    let e = exp - 1023 + (1 << (exp_bits - 1)) - 1;
+    if e >= max_exp as i32 {
+        return inf;
+    }
+    let m = man | (1 << 52);
+    // subnormal results drop an extra bit per binade below the minimum exponent
+    let shift = 52 - sig_bits + if e < 1 { (1 - e) as u32 } else { 0 };
+    if shift > 53 {
+        return sign;
+    }
+    let q = m >> shift;
+    let rem = m & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    let q = if rem > half || (rem == half && q & 1 == 1) { q + 1 } else { q };
+    if e < 1 {
+        // rounding up into the smallest normal produces its encoding
+        return sign | q as u16;
+    }
+    // adding (rather than or-ing) lets a rounding carry bump the exponent, up to infinity
+    let out = ((e as u64) << sig_bits) + (q - (1 << sig_bits));
+    if out >= max_exp << sig_bits {
+        inf
+    } else {
+        sign | out as u16
+    }
+}
+
+impl F16 {
+    pub const fn from_bits(bits: u16) -> Self {
+        F16(bits)
+    }
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+    /// Round to nearest, ties to even
+    pub const fn from_f32(x: f32) -> Self {
+        F16(narrow_bits(x as f64, 10, 5))
+    }
+    /// Round to nearest, ties to even (no double rounding through f32)
+    pub const fn from_f64(x: f64) -> Self {
+        F16(narrow_bits(x, 10, 5))
+    }
+    /// Exact
+    pub const fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let exp = ((self.0 >> 10) & 0x1f) as u32;
+        let man = (self.0 & 0x3ff) as u32;
+        if exp == 0x1f {
+            return f32::from_bits(sign | 0x7f80_0000 | (man << 13));
+        }
+        if exp == 0 {
+            // subnormal (or zero): man * 2^-24 is exact in f32
+            let v = man as f32 * (1.0 / 16_777_216.0);
+            return if sign != 0 { -v } else { v };
+        }
+        f32::from_bits(sign | ((exp + 112) << 23) | (man << 13))
+    }
+    /// Exact
+    pub const fn to_f64(self) -> f64 {
+        self.to_f32() as f64
+    }
+}
+
+impl BF16 {
+    pub const fn from_bits(bits: u16) -> Self {
+        BF16(bits)
+    }
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+    /// Round to nearest, ties to even
+    pub const fn from_f32(x: f32) -> Self {
+        BF16(narrow_bits(x as f64, 7, 8))
+    }
+    /// Round to nearest, ties to even (no double rounding through f32)
+    pub const fn from_f64(x: f64) -> Self {
+        BF16(narrow_bits(x, 7, 8))
+    }
+    /// Exact, bf16 is the upper half of an f32
+    pub const fn to_f32(self) -> f32 {
+        f32::from_bits((self.0 as u32) << 16)
+    }
+    /// Exact
+    pub const fn to_f64(self) -> f64 {
+        self.to_f32() as f64
+    }
+}
+
+/// (trait, method)
+macro_rules! impl_half_binop {
+    ($HalfT:ident, $trait:ident, $method:ident) => {
+        impl $trait for $HalfT {
+            type Output = Self;
+            fn $method(self, rhs: Self) -> Self::Output {
+                Self::from_f32(self.to_f32().$method(rhs.to_f32()))
+            }
+        }
+    };
+}
+
+/// (unit method, f32 method)
+macro_rules! impl_half_unary {
+    ($( fn $method:ident => $f32_method:ident ; )*) => {$(
+        fn $method(self) -> Self {
+            Self::from_f32(f32::$f32_method(self.to_f32()))
+        }
+    )*}
+}
+
+/// (unit method, f32 method)
+macro_rules! impl_half_binary {
+    ($( fn $method:ident => $f32_method:ident ; )*) => {$(
+        fn $method(self, other: Self) -> Self {
+            Self::from_f32(f32::$f32_method(self.to_f32(), other.to_f32()))
+        }
+    )*}
+}
+
+macro_rules! impl_half_to_primitive {
+    ($HalfT:ident : $( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            $HalfT::to_f32(*self).$method()
+        }
+    )*}
+}
+
+macro_rules! impl_half_from_primitive {
+    ($( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            // exact up to 2^53, wider integers are rounded to f64 first (can only double round for BF16)
+            Some(Self::from_f64(n as f64))
+        }
+    )*}
+}
+
+macro_rules! impl_half {
+    (
+        $HalfT:ident, sig_bits: $SigBits:expr, min_exp: $MinExp:expr, max_exp: $MaxExp:expr,
+        digits: $Digits:expr, min_10_exp: $Min10Exp:expr, max_10_exp: $Max10Exp:expr
+    ) => {
+        impl From<$HalfT> for f32 {
+            fn from(v: $HalfT) -> Self {
+                v.to_f32()
+            }
+        }
+        impl From<$HalfT> for f64 {
+            fn from(v: $HalfT) -> Self {
+                v.to_f64()
+            }
+        }
+
+        impl Debug for $HalfT {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Debug::fmt(&$HalfT::to_f32(*self), f)
+            }
+        }
+        impl Display for $HalfT {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(&$HalfT::to_f32(*self), f)
+            }
+        }
+
+        /// IEEE comparison semantics (NaN is unordered, -0 == +0)
+        impl PartialEq for $HalfT {
+            fn eq(&self, other: &Self) -> bool {
+                $HalfT::to_f32(*self) == $HalfT::to_f32(*other)
+            }
+        }
+        impl PartialOrd for $HalfT {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                $HalfT::to_f32(*self).partial_cmp(&$HalfT::to_f32(*other))
+            }
+        }
+
+        impl_half_binop!($HalfT, Add, add);
+        impl_half_binop!($HalfT, Sub, sub);
+        impl_half_binop!($HalfT, Mul, mul);
+        impl_half_binop!($HalfT, Div, div);
+        impl_half_binop!($HalfT, Rem, rem);
+        impl Neg for $HalfT {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                $HalfT(self.0 ^ 0x8000)
+            }
+        }
+
+        // Unit implementations ====================================================================
+
+        impl Unit for $HalfT {}
+        impl UnitOps for $HalfT {}
+        impl Zero for $HalfT {
+            const _ZERO: Self = $HalfT(0);
+        }
+        impl One for $HalfT {
+            const _ONE: Self = $HalfT::from_f32(1.0);
+        }
+        impl Inv for $HalfT {
+            fn _inv(self) -> Self {
+                self._recip()
+            }
+        }
+        impl Symbolic for $HalfT {
+            type Base = $HalfT;
+        }
+        // SAFETY: transparent over a `u16` and every bit pattern is a value
+        unsafe impl PlainBytes for $HalfT {}
+        impl PhysicalRepr for $HalfT {
+            const _BITS: u32 = 16;
+            const _BYTES: usize = 2;
+            const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = Some(PlainBytesProof::PROOF);
+            type BitsRepr = u16;
+            type BytesRepr = [u8; 2];
+            fn _from_bits(v: Self::BitsRepr) -> Self {
+                $HalfT(v)
+            }
+            fn _to_bits(self) -> Self::BitsRepr {
+                self.0
+            }
+            fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+                $HalfT(u16::from_be_bytes(bytes))
+            }
+            fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+                $HalfT(u16::from_le_bytes(bytes))
+            }
+            fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+                $HalfT(u16::from_ne_bytes(bytes))
+            }
+            fn _to_be_bytes(self) -> Self::BytesRepr {
+                self.0.to_be_bytes()
+            }
+            fn _to_le_bytes(self) -> Self::BytesRepr {
+                self.0.to_le_bytes()
+            }
+            fn _to_ne_bytes(self) -> Self::BytesRepr {
+                self.0.to_ne_bytes()
+            }
+        }
+
+        // Properties ==============================================================================
+
+        impl Ordered for $HalfT {
+            fn _min(self, other: Self) -> Self {
+                Self::from_f32(self.to_f32().min(other.to_f32()))
+            }
+            fn _max(self, other: Self) -> Self {
+                Self::from_f32(self.to_f32().max(other.to_f32()))
+            }
+            fn _clamp(self, min: Self, max: Self) -> Self {
+                Self::from_f32(self.to_f32().clamp(min.to_f32(), max.to_f32()))
+            }
+        }
+        impl Bounded for $HalfT {
+            const _MIN: Self = $HalfT(Self::_MAX.0 | 0x8000);
+            const _MAX: Self = $HalfT(Self::_EXP_MASK - (1 << $SigBits) | Self::_SIG_MASK);
+        }
+        impl Signed for $HalfT {
+            const _SIGN_MASK: Self::BitsRepr = 0x8000;
+            const _NEG_ONE: Self = $HalfT(Self::_ONE.0 | 0x8000);
+            fn _abs(self) -> Self {
+                $HalfT(self.0 & !Self::_SIGN_MASK)
+            }
+            fn _signum(self) -> Self {
+                Self::from_f32(self.to_f32().signum())
+            }
+            fn _is_positive(self) -> bool {
+                self.0 & Self::_SIGN_MASK == 0
+            }
+            fn _is_negative(self) -> bool {
+                self.0 & Self::_SIGN_MASK != 0
+            }
+        }
+        impl BoundedSigned for $HalfT {
+            const _MIN_POSITIVE: Self = $HalfT(1 << $SigBits);
+        }
+
+        // Float implementations ===================================================================
+
+        impl Float for $HalfT {
+            const _NAN: Self = $HalfT(Self::_EXP_MASK | (1 << ($SigBits - 1)));
+            const _INFINITY: Self = $HalfT(Self::_EXP_MASK);
+            const _NEG_INFINITY: Self = $HalfT(Self::_EXP_MASK | 0x8000);
+            const _EPSILON: Self = $HalfT::from_f64(1.0 / (1u32 << $SigBits) as f64);
+            const _SIG_BITS: u32 = $SigBits;
+            const _EXP_BITS: u32 = Self::_BITS - Self::_SIG_BITS - 1;
+            const _SIG_MASK: Self::BitsRepr = (1 << Self::_SIG_BITS) - 1;
+            const _EXP_MASK: Self::BitsRepr = !(Self::_SIG_MASK | Self::_SIGN_MASK);
+            const _DIGITS: u32 = $Digits;
+            const _MANTISSA_DIGITS: u32 = $SigBits + 1;
+            const _MIN_EXP: i32 = $MinExp;
+            const _MAX_EXP: i32 = $MaxExp;
+            const _MIN_10_EXP: i32 = $Min10Exp;
+            const _MAX_10_EXP: i32 = $Max10Exp;
+            const _PI: Self = $HalfT::from_f64(core::f64::consts::PI);
+            const _E: Self = $HalfT::from_f64(core::f64::consts::E);
+            const _TAU: Self = $HalfT::from_f64(core::f64::consts::TAU);
+            impl_half_unary! {
+                fn _floor => floor;
+                fn _ceil => ceil;
+                fn _round => round;
+                fn _trunc => trunc;
+                fn _fract => fract;
+            }
+        }
+        impl ExpBasic for $HalfT {
+            type Output = $HalfT;
+            fn _sq(self) -> <Self as ExpBasic>::Output {
+                self * self
+            }
+            fn _sqrt(self) -> <Self as ExpBasic>::Output {
+                Self::from_f32(self.to_f32().sqrt())
+            }
+        }
+        impl ExpPowDynamic<Self> for $HalfT {
+            fn _pow(self, rhs: Self) -> <Self as ExpBasic>::Output {
+                Self::from_f32(self.to_f32().powf(rhs.to_f32()))
+            }
+        }
+        impl ExpRootDynamic<Self> for $HalfT {
+            fn _root(self, n: Self) -> <Self as ExpBasic>::Output {
+                Self::from_f32(self.to_f32().powf(n.to_f32().recip()))
+            }
+        }
+        impl ExpFloat for $HalfT {
+            impl_half_unary! {
+                fn _exp => exp;
+                fn _exp2 => exp2;
+                fn _exp_m1 => exp_m1;
+                fn _log2 => log2;
+                fn _log10 => log10;
+                fn _ln => ln;
+                fn _ln_1p => ln_1p;
+            }
+            fn _log(self, base: Self) -> <Self as ExpBasic>::Output {
+                Self::from_f32(self.to_f32().log(base.to_f32()))
+            }
+        }
+        impl Trig for $HalfT {
+            impl_half_unary! {
+                fn _sin => sin;
+                fn _cos => cos;
+                fn _tan => tan;
+                fn _asin => asin;
+                fn _acos => acos;
+                fn _atan => atan;
+                fn _sinh => sinh;
+                fn _cosh => cosh;
+                fn _tanh => tanh;
+                fn _asinh => asinh;
+                fn _acosh => acosh;
+                fn _atanh => atanh;
+                fn _to_degrees => to_degrees;
+                fn _to_radians => to_radians;
+            }
+            impl_half_binary! {
+                fn _atan2 => atan2;
+                fn _hypot => hypot;
+            }
+        }
+
+        // Casting implementations =================================================================
+
+        impl ToPrimitive for $HalfT {
+            impl_half_to_primitive! { $HalfT:
+                fn to_isize -> isize;
+                fn to_i8 -> i8;
+                fn to_i16 -> i16;
+                fn to_i32 -> i32;
+                fn to_i64 -> i64;
+                fn to_i128 -> i128;
+                fn to_usize -> usize;
+                fn to_u8 -> u8;
+                fn to_u16 -> u16;
+                fn to_u32 -> u32;
+                fn to_u64 -> u64;
+                fn to_u128 -> u128;
+                fn to_f32 -> f32;
+                fn to_f64 -> f64;
+            }
+        }
+
+        impl FromPrimitive for $HalfT {
+            impl_half_from_primitive! {
+                fn from_isize(isize);
+                fn from_i8(i8);
+                fn from_i16(i16);
+                fn from_i32(i32);
+                fn from_i64(i64);
+                fn from_i128(i128);
+                fn from_usize(usize);
+                fn from_u8(u8);
+                fn from_u16(u16);
+                fn from_u32(u32);
+                fn from_u64(u64);
+                fn from_u128(u128);
+                fn from_f64(f64);
+            }
+            #[inline]
+            fn from_f32(n: f32) -> Option<Self> {
+                Some($HalfT::from_f32(n))
+            }
+        }
+
+        impl UnitCast for $HalfT {
+            const _CAST_ROUNDING: Rounding = Rounding::Convergent;
+            #[inline]
+            fn _to_cast_value(self) -> CastValue {
+                CastValue::from_f64(self.to_f64())
+            }
+            #[inline]
+            fn _from_cast_value(v: CastValue, rounding: Rounding, overflow: Option<Overflow>) -> Option<Self> {
+                v.to_float(Self::_MANTISSA_DIGITS, Self::_MIN_EXP, Self::_MAX_EXP, rounding, overflow).map($HalfT::from_f64)
+            }
+        }
+    };
+}
+
+impl_half!(F16, sig_bits: 10, min_exp: -13, max_exp: 16, digits: 3, min_10_exp: -4, max_10_exp: 4);
+impl_half!(BF16, sig_bits: 7, min_exp: -125, max_exp: 128, digits: 2, min_10_exp: -37, max_10_exp: 38);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f16_encoding() {
+        assert_eq!(F16::_ONE.to_bits(), 0x3c00);
+        assert_eq!(F16::_MAX.to_bits(), 0x7bff);
+        assert_eq!(F16::_MAX.to_f32(), 65504.0);
+        assert_eq!(F16::_MIN_POSITIVE.to_f32(), 6.1035156e-5);
+        assert_eq!(F16::_EPSILON.to_f32(), 0.0009765625);
+        assert_eq!(F16::_PI.to_bits(), 0x4248);
+        assert_eq!(F16::_EXP_MASK, 0x7c00);
+        assert_eq!(F16::from_f32(65520.0), F16::_INFINITY);
+        assert_eq!(F16::from_f32(65519.0), F16::_MAX);
+        assert!(F16::_NAN.to_f32().is_nan());
+        assert!(F16::from_f32(f32::NAN).to_f32().is_nan());
+        assert_eq!(F16::from_f32(-0.0).to_bits(), 0x8000);
+    }
+
+    #[test]
+    fn test_f16_rounding_and_subnormals() {
+        // 1 + 2^-11 is exactly halfway between 1 and 1 + 2^-10, ties to even
+        assert_eq!(F16::from_f32(1.0 + 2f32.powi(-11)).to_bits(), 0x3c00);
+        assert_eq!(F16::from_f32(1.0 + 3.0 * 2f32.powi(-11)).to_bits(), 0x3c02);
+        // smallest subnormal, half of it ties to zero, anything above rounds up
+        assert_eq!(F16::from_f32(2f32.powi(-24)).to_bits(), 0x0001);
+        assert_eq!(F16::from_f32(2f32.powi(-25)).to_bits(), 0x0000);
+        assert_eq!(F16::from_f32(1.5 * 2f32.powi(-25)).to_bits(), 0x0001);
+        assert_eq!(F16::from_bits(0x0001).to_f32(), 2f32.powi(-24));
+        assert_eq!(F16::from_bits(0x03ff).to_f32(), 1023.0 * 2f32.powi(-24));
+        // f64 input must not double round through f32
+        let x = 1.0 + 2f64.powi(-11) + 2f64.powi(-40);
+        assert_eq!(F16::from_f64(x).to_bits(), 0x3c01);
+        // every f16 survives the round trip through f32
+        for bits in 0..=u16::MAX {
+            let h = F16::from_bits(bits);
+            if !h.to_f32().is_nan() {
+                assert_eq!(F16::from_f32(h.to_f32()).to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bf16() {
+        assert_eq!(BF16::_ONE.to_bits(), 0x3f80);
+        assert_eq!(BF16::_PI.to_bits(), 0x4049);
+        assert_eq!(BF16::_E.to_bits(), 0x402e);
+        assert_eq!(BF16::_MAX.to_f32(), f32::from_bits(0x7f7f_0000));
+        assert_eq!(BF16::_EXP_MASK, 0x7f80);
+        assert_eq!(BF16::from_f32(f32::MAX), BF16::_INFINITY);
+        assert_eq!(BF16::from_f32(f32::from_bits(0x0000_8000)).to_bits(), 0x0000);
+        assert_eq!(BF16::from_f32(f32::from_bits(0x0001_8000)).to_bits(), 0x0002);
+        for bits in 0..=u16::MAX {
+            let h = BF16::from_bits(bits);
+            if !h.to_f32().is_nan() {
+                assert_eq!(BF16::from_f32(h.to_f32()).to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = F16::from_f32(1.5);
+        let b = F16::from_f32(0.25);
+        assert_eq!(a + b, F16::from_f32(1.75));
+        assert_eq!(a * b, F16::from_f32(0.375));
+        assert_eq!(-a, F16::from_f32(-1.5));
+        assert_eq!(F16::from_f32(0.0), F16::from_f32(-0.0));
+        assert!(F16::_NAN != F16::_NAN);
+        assert!(a > b);
+        assert_eq!(F16::from_f32(4.0)._sqrt(), F16::from_f32(2.0));
+        assert_eq!(BF16::from_i32(3).unwrap().to_i32(), Some(3));
+        assert_eq!(F16::_from_le_bytes(a._to_le_bytes()), a);
+        assert_eq!(Complex::new(a, b)._to_bits() as u16, a.to_bits());
+    }
+}
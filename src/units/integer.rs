@@ -2,6 +2,38 @@
 
 pub trait Integer: Unit + OrderedReflexive + Bounded + Bitwise + ExpPowDynamic<u32> + CastPrimitive {}
 
+/// Division rounding so that the remainder is never negative
+pub trait EuclidDiv: Integer {
+    /// q such that self = q * rhs + r with 0 <= r < abs(rhs)
+    fn _div_euclid(self, rhs: Self) -> Self;
+    /// r such that self = q * rhs + r with 0 <= r < abs(rhs)
+    fn _rem_euclid(self, rhs: Self) -> Self;
+}
+
+/// Greatest common divisor, never negative and gcd(0, 0) == 0
+/// - for signed units gcd(MIN, 0) and gcd(MIN, MIN) do not fit and wrap to MIN
+pub trait Gcd: Integer {
+    fn _gcd(self, other: Self) -> Self;
+}
+
+/// Least common multiple, never negative and zero when either operand is zero
+/// - overflows like multiplication
+pub trait Lcm: Gcd {
+    fn _lcm(self, other: Self) -> Self {
+        if self._is_zero() || other._is_zero() {
+            return Self::_ZERO;
+        }
+        let lcm = self / self._gcd(other) * other;
+        if lcm < Self::_ZERO { Self::_ZERO - lcm } else { lcm }
+    }
+}
+
+/// Inverse in the ring of integers modulo `modulus`
+pub trait ModInverse: Integer {
+    /// x in [0, modulus) with self * x = 1 (mod modulus), `None` when gcd(self, modulus) != 1 or modulus <= 0
+    fn _mod_inverse(self, modulus: Self) -> Option<Self>;
+}
+
 macro_rules! impl_integer {
     ($SrcT:ident) => {
         impl Integer for $SrcT {}
@@ -36,6 +68,178 @@ impl_integer!(i64);
 impl_integer!(i128);
 impl_integer!(isize);
 
+/// (type, saturating negation), division by zero panics except in `CheckedOps`
+macro_rules! impl_integer_overflow_ops {
+    ($SrcT:ident, $saturating_neg:path) => {
+        impl CheckedOps for $SrcT {
+            fn _checked_add(self, rhs: Self) -> Option<Self> {
+                $SrcT::checked_add(self, rhs)
+            }
+            fn _checked_sub(self, rhs: Self) -> Option<Self> {
+                $SrcT::checked_sub(self, rhs)
+            }
+            fn _checked_mul(self, rhs: Self) -> Option<Self> {
+                $SrcT::checked_mul(self, rhs)
+            }
+            fn _checked_div(self, rhs: Self) -> Option<Self> {
+                $SrcT::checked_div(self, rhs)
+            }
+            fn _checked_rem(self, rhs: Self) -> Option<Self> {
+                $SrcT::checked_rem(self, rhs)
+            }
+            fn _checked_neg(self) -> Option<Self> {
+                $SrcT::checked_neg(self)
+            }
+        }
+        impl WrappingOps for $SrcT {
+            fn _wrapping_add(self, rhs: Self) -> Self {
+                $SrcT::wrapping_add(self, rhs)
+            }
+            fn _wrapping_sub(self, rhs: Self) -> Self {
+                $SrcT::wrapping_sub(self, rhs)
+            }
+            fn _wrapping_mul(self, rhs: Self) -> Self {
+                $SrcT::wrapping_mul(self, rhs)
+            }
+            fn _wrapping_div(self, rhs: Self) -> Self {
+                $SrcT::wrapping_div(self, rhs)
+            }
+            fn _wrapping_rem(self, rhs: Self) -> Self {
+                $SrcT::wrapping_rem(self, rhs)
+            }
+            fn _wrapping_neg(self) -> Self {
+                $SrcT::wrapping_neg(self)
+            }
+        }
+        impl SaturatingOps for $SrcT {
+            fn _saturating_add(self, rhs: Self) -> Self {
+                $SrcT::saturating_add(self, rhs)
+            }
+            fn _saturating_sub(self, rhs: Self) -> Self {
+                $SrcT::saturating_sub(self, rhs)
+            }
+            fn _saturating_mul(self, rhs: Self) -> Self {
+                $SrcT::saturating_mul(self, rhs)
+            }
+            fn _saturating_div(self, rhs: Self) -> Self {
+                $SrcT::saturating_div(self, rhs)
+            }
+            fn _saturating_neg(self) -> Self {
+                $saturating_neg(self)
+            }
+        }
+        impl OverflowingOps for $SrcT {
+            fn _overflowing_add(self, rhs: Self) -> (Self, bool) {
+                $SrcT::overflowing_add(self, rhs)
+            }
+            fn _overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                $SrcT::overflowing_sub(self, rhs)
+            }
+            fn _overflowing_mul(self, rhs: Self) -> (Self, bool) {
+                $SrcT::overflowing_mul(self, rhs)
+            }
+            fn _overflowing_div(self, rhs: Self) -> (Self, bool) {
+                $SrcT::overflowing_div(self, rhs)
+            }
+            fn _overflowing_rem(self, rhs: Self) -> (Self, bool) {
+                $SrcT::overflowing_rem(self, rhs)
+            }
+            fn _overflowing_neg(self) -> (Self, bool) {
+                $SrcT::overflowing_neg(self)
+            }
+        }
+    }
+}
+
+/// Any negative result of an unsigned unit saturates to zero
+fn saturating_neg_unsigned<T: Zero>(_: T) -> T {
+    T::_ZERO
+}
+
+impl_integer_overflow_ops!(u8, saturating_neg_unsigned);
+impl_integer_overflow_ops!(u16, saturating_neg_unsigned);
+impl_integer_overflow_ops!(u32, saturating_neg_unsigned);
+impl_integer_overflow_ops!(u64, saturating_neg_unsigned);
+impl_integer_overflow_ops!(u128, saturating_neg_unsigned);
+impl_integer_overflow_ops!(usize, saturating_neg_unsigned);
+impl_integer_overflow_ops!(i8, i8::saturating_neg);
+impl_integer_overflow_ops!(i16, i16::saturating_neg);
+impl_integer_overflow_ops!(i32, i32::saturating_neg);
+impl_integer_overflow_ops!(i64, i64::saturating_neg);
+impl_integer_overflow_ops!(i128, i128::saturating_neg);
+impl_integer_overflow_ops!(isize, isize::saturating_neg);
+
+/// (type, unsigned type of the same width, magnitude as the unsigned type)
+macro_rules! impl_integer_number_theory {
+    ($SrcT:ident, $UnsignedT:ident, $magnitude:path) => {
+        impl EuclidDiv for $SrcT {
+            fn _div_euclid(self, rhs: Self) -> Self {
+                $SrcT::div_euclid(self, rhs)
+            }
+            fn _rem_euclid(self, rhs: Self) -> Self {
+                $SrcT::rem_euclid(self, rhs)
+            }
+        }
+        impl Gcd for $SrcT {
+            fn _gcd(self, other: Self) -> Self {
+                // binary gcd on the magnitudes
+                let (mut a, mut b) = ($magnitude(self), $magnitude(other));
+                if a == 0 || b == 0 {
+                    return (a | b) as $SrcT;
+                }
+                let shift = (a | b).trailing_zeros();
+                a >>= a.trailing_zeros();
+                while b != 0 {
+                    b >>= b.trailing_zeros();
+                    if a > b {
+                        (a, b) = (b, a);
+                    }
+                    b -= a;
+                }
+                (a << shift) as $SrcT
+            }
+        }
+        impl Lcm for $SrcT {}
+        impl ModInverse for $SrcT {
+            fn _mod_inverse(self, modulus: Self) -> Option<Self> {
+                if modulus < 1 {
+                    return None;
+                }
+                // extended Euclid, the coefficients alternate in sign so only their magnitudes are kept
+                // (they never exceed the modulus)
+                let m = modulus as $UnsignedT;
+                let (mut r0, mut r1) = (m, self.rem_euclid(modulus) as $UnsignedT);
+                let (mut t0, mut t1): ($UnsignedT, $UnsignedT) = (0, 1);
+                let mut negative = true;
+                while r1 != 0 {
+                    let q = r0 / r1;
+                    (r0, r1) = (r1, r0 - q * r1);
+                    (t0, t1) = (t1, t0 + q * t1);
+                    negative = !negative;
+                }
+                if r0 != 1 {
+                    return if m == 1 { Some(0) } else { None };
+                }
+                // t0 is the magnitude of the coefficient, which is negative after an odd number of steps
+                Some((if negative { m - t0 } else { t0 } % m) as $SrcT)
+            }
+        }
+    }
+}
+
+impl_integer_number_theory!(u8, u8, std::convert::identity);
+impl_integer_number_theory!(u16, u16, std::convert::identity);
+impl_integer_number_theory!(u32, u32, std::convert::identity);
+impl_integer_number_theory!(u64, u64, std::convert::identity);
+impl_integer_number_theory!(u128, u128, std::convert::identity);
+impl_integer_number_theory!(usize, usize, std::convert::identity);
+impl_integer_number_theory!(i8, u8, i8::unsigned_abs);
+impl_integer_number_theory!(i16, u16, i16::unsigned_abs);
+impl_integer_number_theory!(i32, u32, i32::unsigned_abs);
+impl_integer_number_theory!(i64, u64, i64::unsigned_abs);
+impl_integer_number_theory!(i128, u128, i128::unsigned_abs);
+impl_integer_number_theory!(isize, usize, isize::unsigned_abs);
+
 macro_rules! impl_basic_unit_bounds {
     ($SrcT:ident, $SrcReprT:ident) => {
         impl Unit for $SrcT {}
@@ -46,17 +250,15 @@ macro_rules! impl_basic_unit_bounds {
         impl One for $SrcT {
             const _ONE: Self = 1 as $SrcT;
         }
-        impl Inv for $SrcT {
-            fn _inv(self) -> Self {
-                self._recip()
-            }
-        }
         impl Symbolic for $SrcT {
             type Base = $SrcT;
         }
+        // SAFETY: primitive integers have no padding and no invalid bit patterns
+        unsafe impl PlainBytes for $SrcT {}
         impl PhysicalRepr for $SrcT {
             const _BITS: u32 = size_of::<$SrcT>() as u32 * 8;
             const _BYTES: usize = size_of::<$SrcT>();
+            const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = Some(PlainBytesProof::PROOF);
             type BitsRepr = $SrcReprT;
             type BytesRepr = [u8; size_of::<$SrcT>()];
             fn _from_bits(v: Self::BitsRepr) -> Self {
@@ -163,4 +365,43 @@ impl_properties_signed!(i16);
 impl_properties_signed!(i32);
 impl_properties_signed!(i64);
 impl_properties_signed!(i128);
-impl_properties_signed!(isize);
\ No newline at end of file
+impl_properties_signed!(isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_euclid_gcd_lcm() {
+        assert_eq!((-7i32)._div_euclid(2), -4);
+        assert_eq!((-7i32)._rem_euclid(2), 1);
+        assert_eq!(7i32._rem_euclid(-2), 1);
+        assert_eq!(48000u32._gcd(44100), 300);
+        assert_eq!((-12i64)._gcd(18), 6);
+        assert_eq!(0u8._gcd(0), 0);
+        assert_eq!(i8::MIN._gcd(6), 2);
+        assert_eq!(i8::MIN._gcd(0), i8::MIN);
+        assert_eq!((-4i16)._lcm(6), 12);
+        assert_eq!(0u64._lcm(9), 0);
+        assert_eq!(u128::MAX._gcd(u128::MAX - 1), 1);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(3u32._mod_inverse(7), Some(5));
+        assert_eq!((-3i32)._mod_inverse(7), Some(2));
+        assert_eq!(6u8._mod_inverse(9), None);
+        assert_eq!(5i8._mod_inverse(0), None);
+        assert_eq!(5i8._mod_inverse(-7), None);
+        assert_eq!(4u16._mod_inverse(1), Some(0));
+        // NTT friendly prime, the coefficients stay below the modulus even at the top of the range
+        let p = 0xffff_ffff_0000_0001u64;
+        for a in [2u64, 3, 12345, p - 1, p - 2] {
+            let inv = a._mod_inverse(p).unwrap();
+            assert_eq!((a as u128 * inv as u128 % p as u128), 1);
+        }
+        for a in 1..=250u8 {
+            assert_eq!(a._mod_inverse(251).map(|inv| a as u32 * inv as u32 % 251), Some(1));
+        }
+    }
+}
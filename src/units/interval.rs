@@ -0,0 +1,450 @@
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use super::*;
+
+/// Closed interval [lo, hi] over a floating point unit, every operation returns an enclosure of all
+/// results over the operands
+/// - rounding is outward: results are widened by one ulp per bound (two for the library functions,
+///   assuming they are within one ulp), so an interval evaluation bounds the float evaluation error
+/// - `partial_cmp` orders intervals that are certainly ordered and calls identical intervals equal (in
+///   line with `==`), other overlapping intervals are unordered; see `certainly_lt` / `possibly_lt`
+/// - an empty result (e.g. ln of negative numbers) is represented by NaN bounds
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+/// Number of ulps the library functions (exp, ln, ...) are widened by
+const LIBM_ULPS: u32 = 2;
+
+impl<T: Float> Interval<T> {
+    /// The interval [lo, hi], panics when lo > hi
+    pub fn new(lo: T, hi: T) -> Self {
+        assert!(lo.partial_cmp(&hi) != Some(Ordering::Greater), "interval lower bound is above the upper bound");
+        Interval { lo, hi }
+    }
+    /// The degenerate interval [x, x]
+    pub const fn point(x: T) -> Self {
+        Interval { lo: x, hi: x }
+    }
+    /// [-inf, inf]
+    pub const fn entire() -> Self {
+        Interval { lo: T::_NEG_INFINITY, hi: T::_INFINITY }
+    }
+    const fn empty() -> Self {
+        Interval { lo: T::_NAN, hi: T::_NAN }
+    }
+    pub fn is_empty(self) -> bool {
+        self.lo.partial_cmp(&self.hi).is_none()
+    }
+    pub fn contains(self, x: T) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+    pub fn width(self) -> T {
+        (self.hi - self.lo)._next_up()
+    }
+    pub fn midpoint(self) -> T {
+        self.lo / (T::_ONE + T::_ONE) + self.hi / (T::_ONE + T::_ONE)
+    }
+    /// max(|lo|, |hi|)
+    pub fn magnitude(self) -> T {
+        self.lo._abs()._max(self.hi._abs())
+    }
+    /// Smallest interval containing both
+    pub fn hull(self, other: Self) -> Self {
+        Interval { lo: self.lo._min(other.lo), hi: self.hi._max(other.hi) }
+    }
+    pub fn intersect(self, other: Self) -> Self {
+        let (lo, hi) = (self.lo._max(other.lo), self.hi._min(other.hi));
+        if lo > hi { Self::empty() } else { Interval { lo, hi } }
+    }
+    pub fn overlaps(self, other: Self) -> bool {
+        self.lo <= other.hi && other.lo <= self.hi
+    }
+    /// Whether every value of self is below every value of other
+    pub fn certainly_lt(self, other: Self) -> bool {
+        self.hi < other.lo
+    }
+    /// Whether some value of self is below some value of other
+    pub fn possibly_lt(self, other: Self) -> bool {
+        self.lo < other.hi
+    }
+    /// [lo, hi] widened outward by `ulps` on each side
+    fn outward(lo: T, hi: T, ulps: u32) -> Self {
+        let (mut lo, mut hi) = (lo, hi);
+        for _ in 0..ulps {
+            lo = lo._next_down();
+            hi = hi._next_up();
+        }
+        Interval { lo, hi }
+    }
+    /// Hull of the candidate bounds, widened outward; NaN candidates (inf / inf) are ignored and the
+    /// entire line is returned when every candidate is NaN
+    fn hull_of(candidates: [T; 4], ulps: u32) -> Self {
+        if candidates.iter().all(|c| c._is_nan()) {
+            return Self::entire();
+        }
+        let lo = candidates.iter().fold(T::_INFINITY, |acc, &c| acc._min(c));
+        let hi = candidates.iter().fold(T::_NEG_INFINITY, |acc, &c| acc._max(c));
+        Self::outward(lo, hi, ulps)
+    }
+    fn is_zero_point(self) -> bool {
+        self.lo._is_zero() && self.hi._is_zero()
+    }
+    /// Image under a non-decreasing function defined on [min, inf)
+    fn increasing(self, f: impl Fn(T) -> T, min: T) -> Self {
+        if self.is_empty() || self.hi < min {
+            return Self::empty();
+        }
+        Self::outward(f(self.lo._max(min)), f(self.hi), LIBM_ULPS)
+    }
+}
+
+impl<T: Float> From<T> for Interval<T> {
+    fn from(x: T) -> Self {
+        Self::point(x)
+    }
+}
+
+impl<T: Float> PartialOrd for Interval<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.certainly_lt(*other) {
+            Some(Ordering::Less)
+        } else if other.certainly_lt(*self) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+// Unit implementations ============================================================================
+
+impl<T: Float> Unit for Interval<T> where Self: PhysicalRepr {}
+impl<T: Float> UnitOps for Interval<T> {}
+impl<T: Float> Zero for Interval<T> {
+    const _ZERO: Self = Self::point(T::_ZERO);
+}
+impl<T: Float> One for Interval<T> {
+    const _ONE: Self = Self::point(T::_ONE);
+}
+impl<T: Float> Inv for Interval<T> {
+    fn _inv(self) -> Self {
+        self._recip()
+    }
+}
+impl<T: Float> Symbolic for Interval<T> {
+    type Base = T;
+    fn _dismantle(self) -> Option<Self::Base> {
+        if self.lo == self.hi { Some(self.lo) } else { None }
+    }
+}
+
+/// Bound-wise min/max, the enclosure of min/max over the operands
+impl<T: Float> Ordered for Interval<T> {
+    fn _min(self, other: Self) -> Self {
+        Interval { lo: self.lo._min(other.lo), hi: self.hi._min(other.hi) }
+    }
+    fn _max(self, other: Self) -> Self {
+        Interval { lo: self.lo._max(other.lo), hi: self.hi._max(other.hi) }
+    }
+    fn _clamp(self, min: Self, max: Self) -> Self {
+        self._max(min)._min(max)
+    }
+}
+
+// std::ops implementations ========================================================================
+
+impl<T: Float> Add for Interval<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::outward(self.lo + rhs.lo, self.hi + rhs.hi, 1)
+    }
+}
+
+impl<T: Float> Sub for Interval<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::outward(self.lo - rhs.hi, self.hi - rhs.lo, 1)
+    }
+}
+
+impl<T: Float> Mul for Interval<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_empty() || rhs.is_empty() {
+            return Self::empty();
+        }
+        // an exact zero factor gives an exact zero, also against infinite bounds (0 * inf = 0)
+        if self.is_zero_point() || rhs.is_zero_point() {
+            return Self::_ZERO;
+        }
+        let mul = |a: T, b: T| if a._is_zero() || b._is_zero() { T::_ZERO } else { a * b };
+        Self::hull_of([mul(self.lo, rhs.lo), mul(self.lo, rhs.hi), mul(self.hi, rhs.lo), mul(self.hi, rhs.hi)], 1)
+    }
+}
+
+impl<T: Float> Div for Interval<T> {
+    type Output = Self;
+    /// A divisor containing zero gives the entire line (or nothing for [0, 0])
+    fn div(self, rhs: Self) -> Self::Output {
+        if self.is_empty() || rhs.is_empty() || rhs.is_zero_point() {
+            return Self::empty();
+        }
+        if rhs.contains(T::_ZERO) {
+            return Self::entire();
+        }
+        Self::hull_of([self.lo / rhs.lo, self.lo / rhs.hi, self.hi / rhs.lo, self.hi / rhs.hi], 1)
+    }
+}
+
+impl<T: Float> Rem for Interval<T> {
+    type Output = Self;
+    /// Truncated remainder: exact when |self| is certainly below |rhs|, else bounded by |rhs| and self
+    fn rem(self, rhs: Self) -> Self::Output {
+        if self.is_empty() || rhs.is_empty() || rhs.contains(T::_ZERO) {
+            return Self::empty();
+        }
+        let smallest = rhs.lo._abs()._min(rhs.hi._abs());
+        if self.magnitude() < smallest {
+            return self;
+        }
+        let bound = rhs.magnitude();
+        Interval { lo: self.lo._max(-bound)._min(T::_ZERO), hi: self.hi._min(bound)._max(T::_ZERO) }
+    }
+}
+
+impl<T: Float> Neg for Interval<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Interval { lo: -self.hi, hi: -self.lo }
+    }
+}
+
+/// (trait, method, assign_trait, assign_method)
+macro_rules! impl_interval_assign_and_scalar {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        // Interval += Interval
+        impl<T: Float> $assign_trait for Interval<T> {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        // Interval + T
+        impl<T: Float> $trait<T> for Interval<T> {
+            type Output = Self;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$method(Self::point(rhs))
+            }
+        }
+
+        // Interval += T
+        impl<T: Float> $assign_trait<T> for Interval<T> {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = (*self).$method(Self::point(rhs));
+            }
+        }
+    };
+}
+
+impl_interval_assign_and_scalar!(Add, add, AddAssign, add_assign);
+impl_interval_assign_and_scalar!(Sub, sub, SubAssign, sub_assign);
+impl_interval_assign_and_scalar!(Mul, mul, MulAssign, mul_assign);
+impl_interval_assign_and_scalar!(Div, div, DivAssign, div_assign);
+
+// Elementary functions ============================================================================
+
+impl<T: Float> ExpBasic for Interval<T> where Self: Unit {
+    type Output = Self;
+    /// Tighter than self * self, the result is never negative
+    fn _sq(self) -> <Self as ExpBasic>::Output {
+        if self.is_empty() {
+            return self;
+        }
+        let (a, b) = (self.lo * self.lo, self.hi * self.hi);
+        if self.contains(T::_ZERO) {
+            Interval { lo: T::_ZERO, hi: a._max(b)._next_up() }
+        } else {
+            Self::outward(a._min(b), a._max(b), 1)
+        }
+    }
+    fn _sqrt(self) -> <Self as ExpBasic>::Output {
+        let iv = self.increasing(|x| x._sqrt(), T::_ZERO);
+        Interval { lo: iv.lo._max(T::_ZERO), ..iv }
+    }
+}
+
+impl<T: Float> ExpPowDynamic<Self> for Interval<T> where Self: Unit {
+    /// e^(rhs ln(self)), defined for self >= 0
+    fn _pow(self, rhs: Self) -> <Self as ExpBasic>::Output {
+        (rhs * self._ln())._exp()
+    }
+}
+
+impl<T: Float> ExpRootDynamic<Self> for Interval<T> where Self: Unit {
+    fn _root(self, n: Self) -> <Self as ExpBasic>::Output {
+        self._pow(n._recip())
+    }
+}
+
+impl<T: Float> ExpFloat for Interval<T> where Self: Unit {
+    fn _exp(self) -> <Self as ExpBasic>::Output {
+        let iv = self.increasing(|x| x._exp(), T::_NEG_INFINITY);
+        Interval { lo: iv.lo._max(T::_ZERO), ..iv }
+    }
+    fn _exp2(self) -> <Self as ExpBasic>::Output {
+        let iv = self.increasing(|x| x._exp2(), T::_NEG_INFINITY);
+        Interval { lo: iv.lo._max(T::_ZERO), ..iv }
+    }
+    fn _exp_m1(self) -> <Self as ExpBasic>::Output {
+        let iv = self.increasing(|x| x._exp_m1(), T::_NEG_INFINITY);
+        Interval { lo: iv.lo._max(-T::_ONE), ..iv }
+    }
+    fn _log(self, base: Self) -> <Self as ExpBasic>::Output {
+        self._ln() / base._ln()
+    }
+    fn _log2(self) -> <Self as ExpBasic>::Output {
+        self.increasing(|x| x._log2(), T::_ZERO)
+    }
+    fn _log10(self) -> <Self as ExpBasic>::Output {
+        self.increasing(|x| x._log10(), T::_ZERO)
+    }
+    fn _ln(self) -> <Self as ExpBasic>::Output {
+        self.increasing(|x| x._ln(), T::_ZERO)
+    }
+    fn _ln_1p(self) -> <Self as ExpBasic>::Output {
+        self.increasing(|x| x._ln_1p(), -T::_ONE)
+    }
+}
+
+// Concrete implementations ========================================================================
+
+macro_rules! impl_interval {
+    ($SrcT:ident, $SrcReprT:ident) => {
+        impl PhysicalRepr for Interval<$SrcT> {
+            const _BITS: u32 = 2 * $SrcT::_BITS;
+            const _BYTES: usize = 2 * $SrcT::_BYTES;
+            /// `lo` is packed into the low half and `hi` into the high half
+            type BitsRepr = $SrcReprT;
+            type BytesRepr = [u8; 2 * size_of::<$SrcT>()];
+            fn _from_bits(v: Self::BitsRepr) -> Self {
+                let lo = $SrcT::_from_bits(v as <$SrcT as PhysicalRepr>::BitsRepr);
+                let hi = $SrcT::_from_bits((v >> $SrcT::_BITS) as <$SrcT as PhysicalRepr>::BitsRepr);
+                Interval { lo, hi }
+            }
+            fn _to_bits(self) -> Self::BitsRepr {
+                (self.lo._to_bits() as $SrcReprT) | ((self.hi._to_bits() as $SrcReprT) << $SrcT::_BITS)
+            }
+            fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_be_bytes(bytes))
+            }
+            fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_le_bytes(bytes))
+            }
+            fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_ne_bytes(bytes))
+            }
+            fn _to_be_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_be_bytes()
+            }
+            fn _to_le_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_le_bytes()
+            }
+            fn _to_ne_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_ne_bytes()
+            }
+        }
+    };
+}
+
+impl_interval!(f32, u64);
+impl_interval!(f64, u128);
+impl_interval!(F16, u32);
+impl_interval!(BF16, u32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::tests::horner;
+
+    #[test]
+    fn test_enclosure() {
+        let coefs = [0.1f32, -0.7, 1.3, 0.25];
+        let x = Interval::new(0.3f32, 0.31);
+        let bound = horner(&coefs.map(Interval::point), x);
+        for i in 0..=100 {
+            let t = 0.3 + 0.01 * i as f64 / 100.0;
+            let exact = coefs.iter().fold(0.0, |acc, &c| acc * t + c as f64);
+            assert!(bound.lo as f64 <= exact && exact <= bound.hi as f64, "{exact} outside {bound:?}");
+        }
+        // the float sum of ten 0.1f32 drifts, the interval still holds the exact sum
+        let tenth = Interval::point(0.1f32);
+        let sum = (0..10).fold(Interval::_ZERO, |acc, _| acc + tenth);
+        let exact = 10.0 * 0.1f32 as f64;
+        assert!(sum.lo as f64 <= exact && exact <= sum.hi as f64);
+        assert!(sum.width() < 1e-5);
+    }
+
+    #[test]
+    fn test_zero_times_infinite() {
+        let zero = Interval::point(0.0f64);
+        assert_eq!(zero * Interval::entire(), zero);
+        assert_eq!(Interval::new(f64::NEG_INFINITY, 0.0) * zero, zero);
+        let p = Interval::new(0.0f64, 1.0) * Interval::new(2.0, f64::INFINITY);
+        assert!(p.lo <= 0.0 && p.lo > -1e-300 && p.hi == f64::INFINITY);
+        assert_eq!(Interval::new(0.0f32, 1.0) * Interval::entire(), Interval::entire());
+        let inf = Interval::point(f64::INFINITY);
+        assert_eq!(inf / inf, Interval::entire());
+    }
+
+    #[test]
+    fn test_division_and_rem() {
+        let a = Interval::new(1.0f64, 2.0);
+        assert_eq!(a / Interval::new(-1.0, 1.0), Interval::entire());
+        let q = a / Interval::new(4.0, 8.0);
+        assert!(q.contains(0.125) && q.contains(0.5) && q.lo > 0.12 && q.hi < 0.51);
+        assert!((a / Interval::_ZERO).is_empty());
+        assert_eq!(Interval::new(0.5f64, 1.5) % Interval::point(2.0), Interval::new(0.5, 1.5));
+        let r = Interval::new(-7.0f64, 9.0) % Interval::new(2.0, 3.0);
+        assert!(r.lo == -3.0 && r.hi == 3.0);
+    }
+
+    #[test]
+    fn test_elementary() {
+        let x = Interval::new(-1.0f64, 2.0);
+        let e = x._exp();
+        assert!(e.contains((-1.0f64).exp()) && e.contains(2.0f64.exp()) && e.lo < (-1.0f64).exp());
+        let l = Interval::new(-3.0f64, 8.0)._log2();
+        assert!(l.lo == f64::NEG_INFINITY && l.contains(3.0));
+        assert!(Interval::new(-3.0f64, -1.0)._ln().is_empty());
+        assert_eq!(x._sq().lo, 0.0);
+        assert!(x._sq().contains(4.0));
+        assert!(Interval::new(4.0f64, 9.0)._sqrt().contains(3.0));
+        let p = Interval::new(2.0f64, 3.0)._pow(Interval::point(2.0));
+        assert!(p.contains(4.0) && p.contains(9.0) && p.hi < 9.001);
+    }
+
+    #[test]
+    fn test_ordering_and_repr() {
+        let a = Interval::new(1.0f32, 2.0);
+        let b = Interval::new(3.0f32, 4.0);
+        let c = Interval::new(1.5f32, 3.5);
+        assert!(a < b);
+        assert!(b > a);
+        assert_eq!(a.partial_cmp(&c), None);
+        assert_eq!(a.partial_cmp(&a), Some(Ordering::Equal));
+        assert!(a <= a);
+        assert!(a >= a);
+        assert!(!a.certainly_lt(a) && a.possibly_lt(a));
+        assert_eq!(Interval::<f32>::empty().partial_cmp(&Interval::empty()), None);
+        assert!(a.possibly_lt(c) && !a.certainly_lt(c));
+        assert_eq!(a._max(c), Interval::new(1.5, 3.5));
+        assert_eq!(Interval::<f32>::_from_bits(c._to_bits()), c);
+        assert_eq!(Interval::<F16>::_from_le_bytes(Interval::point(F16::from_f32(0.5))._to_le_bytes())._dismantle(), Some(F16::from_f32(0.5)));
+    }
+}
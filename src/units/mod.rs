@@ -1,9 +1,6 @@
 ﻿/// Module for extending the primitive types and introducing a system for working with primitive data (numbers)
 /// todo:
-/// - abstract SIMD operations (way later ; or just use nightly)
-/// - implement the bigint, fixed-point and bigfloat types (way later)
 /// - add tests and benchmarks (next)
-/// - implement the complex type (later)
 
 mod cast;
 pub use cast::*;
@@ -23,9 +20,63 @@ pub use integer::*;
 mod float;
 pub use float::*;
 
+mod half;
+pub use half::*;
+
+mod complex;
+pub use complex::*;
+
+mod dual;
+pub use dual::*;
+
+mod interval;
+pub use interval::*;
+
+mod rational;
+pub use rational::*;
+
+mod simd;
+pub use simd::*;
+
+mod fixed;
+pub use fixed::*;
+
+mod saturating;
+pub use saturating::*;
+
+mod approx;
+pub use approx::*;
+
+mod conversions;
+pub use conversions::*;
+
+mod quantity;
+pub use quantity::*;
+
+mod format;
+pub use format::*;
+
+mod bytes;
+pub use bytes::*;
+
+mod denormal;
+pub use denormal::*;
+
+#[macro_use]
+mod bigint;
+pub use bigint::*;
+
+mod bigfloat;
+pub use bigfloat::*;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    /// Polynomial with the highest coefficient first, generic code shared by the unit tests
+    pub fn horner<T: Unit>(coefs: &[T], x: T) -> T {
+        coefs.iter().fold(T::_ZERO, |acc, &c| acc * x + c)
+    }
     
     #[test]
     fn test_add() {
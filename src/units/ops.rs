@@ -41,3 +41,81 @@ pub trait ExpFloat<RHS = Self>: ExpRootDynamic<RHS> + ExpPowDynamic<RHS> {
     /// ln(self + 1)
     fn _ln_1p(self) -> <Self as ExpBasic>::Output;
 }
+
+pub trait Trig: Unit {
+    /// sin(self)
+    fn _sin(self) -> Self;
+    /// cos(self)
+    fn _cos(self) -> Self;
+    /// (sin(self), cos(self))
+    fn _sin_cos(self) -> (Self, Self) {
+        (self._sin(), self._cos())
+    }
+    /// tan(self)
+    fn _tan(self) -> Self;
+    /// arcsin(self)
+    fn _asin(self) -> Self;
+    /// arccos(self)
+    fn _acos(self) -> Self;
+    /// arctan(self)
+    fn _atan(self) -> Self;
+    /// four quadrant arctan(self / other), self is the y coordinate
+    fn _atan2(self, other: Self) -> Self;
+    /// sinh(self)
+    fn _sinh(self) -> Self;
+    /// cosh(self)
+    fn _cosh(self) -> Self;
+    /// tanh(self)
+    fn _tanh(self) -> Self;
+    /// arsinh(self)
+    fn _asinh(self) -> Self;
+    /// arcosh(self)
+    fn _acosh(self) -> Self;
+    /// artanh(self)
+    fn _atanh(self) -> Self;
+    /// sqrt(self^2 + other^2) without undue overflow or underflow
+    fn _hypot(self, other: Self) -> Self;
+    /// radians to degrees
+    fn _to_degrees(self) -> Self;
+    /// degrees to radians
+    fn _to_radians(self) -> Self;
+}
+
+/// Arithmetic that reports overflow (and division by zero) as `None`
+pub trait CheckedOps: Unit {
+    fn _checked_add(self, rhs: Self) -> Option<Self>;
+    fn _checked_sub(self, rhs: Self) -> Option<Self>;
+    fn _checked_mul(self, rhs: Self) -> Option<Self>;
+    fn _checked_div(self, rhs: Self) -> Option<Self>;
+    fn _checked_rem(self, rhs: Self) -> Option<Self>;
+    fn _checked_neg(self) -> Option<Self>;
+}
+
+/// Arithmetic that wraps around at the bounds (two's complement)
+pub trait WrappingOps: Unit {
+    fn _wrapping_add(self, rhs: Self) -> Self;
+    fn _wrapping_sub(self, rhs: Self) -> Self;
+    fn _wrapping_mul(self, rhs: Self) -> Self;
+    fn _wrapping_div(self, rhs: Self) -> Self;
+    fn _wrapping_rem(self, rhs: Self) -> Self;
+    fn _wrapping_neg(self) -> Self;
+}
+
+/// Arithmetic that clamps to the bounds
+pub trait SaturatingOps: Unit {
+    fn _saturating_add(self, rhs: Self) -> Self;
+    fn _saturating_sub(self, rhs: Self) -> Self;
+    fn _saturating_mul(self, rhs: Self) -> Self;
+    fn _saturating_div(self, rhs: Self) -> Self;
+    fn _saturating_neg(self) -> Self;
+}
+
+/// Wrapping arithmetic that also reports whether it overflowed
+pub trait OverflowingOps: Unit {
+    fn _overflowing_add(self, rhs: Self) -> (Self, bool);
+    fn _overflowing_sub(self, rhs: Self) -> (Self, bool);
+    fn _overflowing_mul(self, rhs: Self) -> (Self, bool);
+    fn _overflowing_div(self, rhs: Self) -> (Self, bool);
+    fn _overflowing_rem(self, rhs: Self) -> (Self, bool);
+    fn _overflowing_neg(self) -> (Self, bool);
+}
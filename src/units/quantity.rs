@@ -0,0 +1,316 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use super::*;
+
+// Physical quantities over a unit, with only the dimensionally valid operators
+// - a quantity adds to and subtracts from the same quantity, and scales by a bare value
+// - the ratio of two equal quantities is a bare value
+// - they are not units themselves, since e.g. Hz x Hz is not Hz
+//
+// Across quantities: Seconds x Hertz is a bare value, Samples / Hertz is Seconds and
+// Samples / Seconds is Hertz
+
+/// Frequency, cycles per second
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Hertz<T>(pub T);
+
+/// Duration
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Seconds<T>(pub T);
+
+/// Level of amplitude, 20 log10(gain)
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Decibels<T>(pub T);
+
+/// Angle, or angular frequency per sample
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Radians<T>(pub T);
+
+/// Whole number of samples, a duration or position at some sample rate
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Samples(pub usize);
+
+/// (quantity, symbol)
+macro_rules! impl_quantity {
+    ($Q:ident, $symbol:literal) => {
+        impl<T: Unit> Add for $Q<T> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                $Q(self.0 + rhs.0)
+            }
+        }
+
+        impl<T: Unit> Sub for $Q<T> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                $Q(self.0 - rhs.0)
+            }
+        }
+
+        impl<T: Unit> AddAssign for $Q<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Unit> SubAssign for $Q<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T: Unit + Neg<Output = T>> Neg for $Q<T> {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                $Q(-self.0)
+            }
+        }
+
+        // Quantity * T
+        impl<T: Unit> Mul<T> for $Q<T> {
+            type Output = Self;
+            fn mul(self, rhs: T) -> Self::Output {
+                $Q(self.0 * rhs)
+            }
+        }
+
+        impl<T: Unit> MulAssign<T> for $Q<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                *self = *self * rhs;
+            }
+        }
+
+        // Quantity / T
+        impl<T: Unit> Div<T> for $Q<T> {
+            type Output = Self;
+            fn div(self, rhs: T) -> Self::Output {
+                $Q(self.0 / rhs)
+            }
+        }
+
+        impl<T: Unit> DivAssign<T> for $Q<T> {
+            fn div_assign(&mut self, rhs: T) {
+                *self = *self / rhs;
+            }
+        }
+
+        // Quantity / Quantity
+        impl<T: Unit> Div for $Q<T> {
+            type Output = T;
+            fn div(self, rhs: Self) -> Self::Output {
+                self.0 / rhs.0
+            }
+        }
+
+        /// The value followed by the symbol, formatting options apply to the value
+        impl<T: Display> Display for $Q<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)?;
+                f.write_str($symbol)
+            }
+        }
+    };
+}
+
+impl_quantity!(Hertz, " Hz");
+impl_quantity!(Seconds, " s");
+impl_quantity!(Decibels, " dB");
+impl_quantity!(Radians, " rad");
+
+impl<T: Float> Hertz<T> {
+    /// Duration of one cycle
+    pub fn period(self) -> Seconds<T> {
+        Seconds(self.0._recip())
+    }
+    /// Half of this sample rate
+    pub fn nyquist(self) -> Self {
+        Hertz(self.0 / (T::_ONE + T::_ONE))
+    }
+    /// Angular frequency per sample at `sample_rate`
+    pub fn to_omega(self, sample_rate: Hertz<T>) -> Radians<T> {
+        Radians(self.0._hz_to_omega(sample_rate.0))
+    }
+    pub fn from_midi(note: T) -> Self {
+        Hertz(note._midi_to_hz())
+    }
+    pub fn to_midi(self) -> T {
+        self.0._hz_to_midi()
+    }
+}
+
+impl<T: Float> Seconds<T> {
+    /// Frequency with this period
+    pub fn frequency(self) -> Hertz<T> {
+        Hertz(self.0._recip())
+    }
+    /// Nearest whole number of samples at `sample_rate`, `None` when negative or out of range
+    pub fn to_samples(self, sample_rate: Hertz<T>) -> Option<Samples> {
+        (self.0 * sample_rate.0)._round().to_usize().map(Samples)
+    }
+}
+
+impl<T: Float> Decibels<T> {
+    pub fn from_gain(gain: T) -> Self {
+        Decibels(gain._gain_to_db())
+    }
+    /// Linear gain, -inf dB is a gain of zero
+    pub fn to_gain(self) -> T {
+        self.0._db_to_gain()
+    }
+}
+
+impl<T: Float> Radians<T> {
+    pub fn from_degrees(degrees: T) -> Self {
+        Radians(degrees._to_radians())
+    }
+    pub fn to_degrees(self) -> T {
+        self.0._to_degrees()
+    }
+    /// Frequency of this angular frequency per sample at `sample_rate`
+    pub fn to_hz(self, sample_rate: Hertz<T>) -> Hertz<T> {
+        Hertz(self.0._omega_to_hz(sample_rate.0))
+    }
+    pub fn sin(self) -> T {
+        self.0._sin()
+    }
+    pub fn cos(self) -> T {
+        self.0._cos()
+    }
+}
+
+impl Samples {
+    /// Duration at `sample_rate`
+    pub fn to_seconds<T: Float>(self, sample_rate: Hertz<T>) -> Seconds<T> {
+        self / sample_rate
+    }
+}
+
+// Seconds * Hertz
+impl<T: Unit> Mul<Hertz<T>> for Seconds<T> {
+    type Output = T;
+    fn mul(self, rhs: Hertz<T>) -> Self::Output {
+        self.0 * rhs.0
+    }
+}
+
+// Hertz * Seconds
+impl<T: Unit> Mul<Seconds<T>> for Hertz<T> {
+    type Output = T;
+    fn mul(self, rhs: Seconds<T>) -> Self::Output {
+        self.0 * rhs.0
+    }
+}
+
+// Samples / Hertz
+/// A count beyond the range of `T` is infinite
+impl<T: Float> Div<Hertz<T>> for Samples {
+    type Output = Seconds<T>;
+    fn div(self, rhs: Hertz<T>) -> Self::Output {
+        Seconds(T::from_usize(self.0).unwrap_or(T::_INFINITY) / rhs.0)
+    }
+}
+
+// Samples / Seconds
+/// A count beyond the range of `T` is infinite
+impl<T: Float> Div<Seconds<T>> for Samples {
+    type Output = Hertz<T>;
+    fn div(self, rhs: Seconds<T>) -> Self::Output {
+        Hertz(T::from_usize(self.0).unwrap_or(T::_INFINITY) / rhs.0)
+    }
+}
+
+impl Add for Samples {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Samples(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Samples {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Samples(self.0 - rhs.0)
+    }
+}
+
+impl AddAssign for Samples {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Samples {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Mul<usize> for Samples {
+    type Output = Self;
+    fn mul(self, rhs: usize) -> Self::Output {
+        Samples(self.0 * rhs)
+    }
+}
+
+impl Div<usize> for Samples {
+    type Output = Self;
+    fn div(self, rhs: usize) -> Self::Output {
+        Samples(self.0 / rhs)
+    }
+}
+
+impl Display for Samples {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)?;
+        f.write_str(" samples")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A constructor taking typed arguments
+    fn one_pole_coefficient(cutoff: Hertz<f64>, sample_rate: Hertz<f64>) -> f64 {
+        (-cutoff.to_omega(sample_rate).0)._exp()
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let rate = Hertz(48000.0);
+        let block = Samples(480);
+        assert_eq!(block / rate, Seconds(0.01));
+        assert_eq!(block / Seconds(0.01), rate);
+        assert_eq!(Seconds(0.5) * rate, 24000.0);
+        assert_eq!(Seconds(0.5).to_samples(rate), Some(Samples(24000)));
+        assert_eq!(Seconds(-1.0).to_samples(rate), None);
+        assert_eq!(block.to_seconds(rate) * rate, 480.0);
+        assert_eq!(rate / Hertz(44100.0), 48000.0 / 44100.0);
+        assert_eq!(rate.nyquist(), Hertz(24000.0));
+        assert_eq!(Hertz(4.0).period(), Seconds(0.25));
+        assert_eq!(Hertz(12000.0).to_omega(rate), Radians(std::f64::consts::FRAC_PI_2));
+        assert_eq!(Radians(std::f64::consts::FRAC_PI_2).to_hz(rate), Hertz(12000.0));
+        assert_eq!(Hertz::from_midi(69.0f32), Hertz(440.0));
+        assert_eq!(Decibels(-6.0) + Decibels(-6.0), Decibels(-12.0));
+        assert_eq!(Decibels::from_gain(0.1f64), Decibels(-20.0));
+        assert_eq!(Decibels(f32::NEG_INFINITY).to_gain(), 0.0);
+        assert_eq!(Samples(100) - Samples(40) + Samples(2) * 3, Samples(66));
+        assert_eq!((Samples(1 << 20) / Hertz(F16::_ONE)).0, F16::_INFINITY);
+        assert_eq!((Samples(1 << 20) / Seconds(BF16::_ONE)).0, BF16::from_f32(1048576.0));
+        assert!(one_pole_coefficient(Hertz(1000.0), rate) < one_pole_coefficient(Hertz(100.0), rate));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{:.1}", Hertz(440.0)), "440.0 Hz");
+        assert_eq!(Seconds(0.25f32).to_string(), "0.25 s");
+        assert_eq!(Decibels(-6).to_string(), "-6 dB");
+        assert_eq!(Samples(128).to_string(), "128 samples");
+        assert_eq!((-Radians(1.5)).to_string(), "-1.5 rad");
+    }
+}
@@ -0,0 +1,536 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use super::*;
+
+/// Exact fraction num / den over an integer unit
+/// - always normalized: den > 0 and gcd(num, den) = 1, so the derived equality and hashing are exact
+/// - operations cross-reduce before multiplying to delay overflow, which otherwise behaves like the
+///   underlying integer ops
+/// - it is a `Unit` for the integers up to 64 bits (num and den pack into a primitive integer)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    num: T,
+    den: T,
+}
+
+/// Greatest common divisor up to its sign
+fn euclid<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while !b._is_zero() {
+        // MIN % -1 overflows, any remainder by -1 is 0
+        let r = if b < T::_ZERO && b + T::_ONE == T::_ZERO { T::_ZERO } else { a % b };
+        (a, b) = (b, r);
+    }
+    a
+}
+
+fn gcd<T: Integer>(a: T, b: T) -> T {
+    let g = euclid(a, b);
+    if g < T::_ZERO { T::_ZERO - g } else { g }
+}
+
+/// Floor division for a positive divisor, (q, r) with 0 <= r < b
+fn floor_div_rem<T: Integer>(a: T, b: T) -> (T, T) {
+    let (q, r) = (a / b, a % b);
+    if r < T::_ZERO { (q - T::_ONE, r + b) } else { (q, r) }
+}
+
+impl<T: Integer> Rational<T> {
+    /// num / den reduced to lowest terms, panics on a zero denominator or when the reduced fraction
+    /// does not fit (see `checked_new`)
+    pub fn new(num: T, den: T) -> Self {
+        Self::checked_new(num, den).expect("rational with zero denominator or out of range")
+    }
+    /// None on a zero denominator, or when making den positive overflows, e.g. 1 / MIN
+    pub fn checked_new(num: T, den: T) -> Option<Self> {
+        if den._is_zero() {
+            return None;
+        }
+        // the divisor takes the sign of den, so the division also makes den positive
+        let g = euclid(num, den);
+        let g = if (g < T::_ZERO) != (den < T::_ZERO) { T::_ZERO - g } else { g };
+        // x / -1 only overflows for MIN, whose negation does not fit
+        if g < T::_ZERO && g + T::_ONE == T::_ZERO && (num == T::_MIN || den == T::_MIN) {
+            return None;
+        }
+        Some(Rational { num: num / g, den: den / g })
+    }
+    /// n / 1
+    pub const fn from_integer(n: T) -> Self {
+        Rational { num: n, den: T::_ONE }
+    }
+    pub fn numer(self) -> T {
+        self.num
+    }
+    pub fn denom(self) -> T {
+        self.den
+    }
+    pub fn is_integer(self) -> bool {
+        self.den == T::_ONE
+    }
+    /// Largest integer not above self
+    pub fn floor(self) -> T {
+        floor_div_rem(self.num, self.den).0
+    }
+    /// Integer part, rounded towards zero
+    pub fn trunc(self) -> T {
+        self.num / self.den
+    }
+    pub fn fract(self) -> Self {
+        Rational { num: self.num % self.den, den: self.den }
+    }
+
+    /// Best rational approximation of `x` with a denominator of at most `max_den` (continued fraction
+    /// convergents and semiconvergents), computed exactly from the binary value of `x`
+    /// - `None` for NaN, infinities and values outside the range of the integer
+    pub fn approximate(x: f64, max_den: T) -> Option<Self> {
+        if !x.is_finite() || max_den < T::_ONE || (x < 0.0 && T::_MIN._is_zero()) {
+            return None;
+        }
+        let negative = x < 0.0;
+        let max_num = if negative {
+            BigInt::from(T::_MIN.to_i128()?).abs()
+        } else {
+            BigInt::from(T::_MAX.to_u128()?)
+        };
+        let max_den = BigInt::from(max_den.to_u128()?);
+        // |x| = p / q exactly
+        let bits = x.abs().to_bits();
+        let biased = ((bits >> 52) & 0x7ff) as i64;
+        let frac = bits & ((1 << 52) - 1);
+        let (m, e) = if biased == 0 { (frac, -1074) } else { (frac | (1 << 52), biased - 1075) };
+        let (mut p, mut q) = if e >= 0 {
+            (BigInt::from(m) << e as usize, BigInt::one())
+        } else {
+            (BigInt::from(m), BigInt::one() << (-e) as usize)
+        };
+        if p > &max_num * &q {
+            return None;
+        }
+        let target = (p.clone(), q.clone());
+        // convergents h/k, starting from h_-2/k_-2 = 0/1 and h_-1/k_-1 = 1/0
+        let (mut h0, mut k0, mut h1, mut k1) = (BigInt::ZERO, BigInt::one(), BigInt::one(), BigInt::ZERO);
+        loop {
+            let a = &p / &q;
+            let (h, k) = (&a * &h1 + &h0, &a * &k1 + &k0);
+            if h > max_num || k > max_den {
+                // largest semiconvergent within bounds, kept only if it beats the last convergent
+                let t_den = if k1.is_zero() { a.clone() } else { (&max_den - &k0) / &k1 };
+                let t_num = if h1.is_zero() { a.clone() } else { (&max_num - &h0) / &h1 };
+                let t = t_den.min(t_num);
+                let (hs, ks) = (&t * &h1 + &h0, &t * &k1 + &k0);
+                let closer = |h: &BigInt, k: &BigInt| (h * &target.1 - k * &target.0).abs() * &k1;
+                if !t.is_zero() && !ks.is_zero() && (k1.is_zero() || closer(&hs, &ks) < closer(&h1, &k1) * &ks / &k1) {
+                    (h1, k1) = (hs, ks);
+                }
+                break;
+            }
+            (h0, k0, h1, k1) = (h1, k1, h, k);
+            let r = &p - &a * &q;
+            if r.is_zero() {
+                break;
+            }
+            (p, q) = (q, r);
+        }
+        if k1.is_zero() {
+            return None;
+        }
+        let num = h1.to_i128()?;
+        let num = T::from_i128(if negative { -num } else { num })?;
+        Some(Rational { num, den: T::from_i128(k1.to_i128()?)? })
+    }
+}
+
+impl<T: Integer> From<T> for Rational<T> {
+    fn from(n: T) -> Self {
+        Self::from_integer(n)
+    }
+}
+
+impl<T: Integer + Display> Display for Rational<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.den == T::_ONE { write!(f, "{}", self.num) } else { write!(f, "{}/{}", self.num, self.den) }
+    }
+}
+
+impl<T: Integer> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer> Ord for Rational<T> {
+    /// Compares the continued fraction expansions, so it never overflows
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b, mut c, mut d) = (self.num, self.den, other.num, other.den);
+        let mut flip = false;
+        loop {
+            let (q1, r1) = floor_div_rem(a, b);
+            let (q2, r2) = floor_div_rem(c, d);
+            let ord = match (r1._is_zero(), r2._is_zero()) {
+                _ if q1 != q2 => q1.cmp(&q2),
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                // r1/b vs r2/d is d/r2 vs b/r1 with the order reversed
+                (false, false) => {
+                    (a, b, c, d) = (b, r1, d, r2);
+                    flip = !flip;
+                    continue;
+                }
+            };
+            return if flip { ord.reverse() } else { ord };
+        }
+    }
+}
+
+// Unit implementations ============================================================================
+
+impl<T: Integer> Unit for Rational<T> where Self: PhysicalRepr {}
+impl<T: Integer> UnitOps for Rational<T> {}
+impl<T: Integer> Zero for Rational<T> {
+    const _ZERO: Self = Self::from_integer(T::_ZERO);
+}
+impl<T: Integer> One for Rational<T> {
+    const _ONE: Self = Self::from_integer(T::_ONE);
+}
+/// Exact, panics for zero
+impl<T: Integer> Inv for Rational<T> {
+    fn _inv(self) -> Self {
+        self._recip()
+    }
+    /// den / num
+    fn _recip(self) -> Self {
+        Self::new(self.den, self.num)
+    }
+}
+impl<T: Integer> Symbolic for Rational<T> {
+    type Base = T;
+    fn _dismantle(self) -> Option<Self::Base> {
+        if self.is_integer() { Some(self.num) } else { None }
+    }
+}
+impl<T: Integer> Ordered for Rational<T> {
+    fn _min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+    fn _max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+    fn _clamp(self, min: Self, max: Self) -> Self {
+        Ord::clamp(self, min, max)
+    }
+}
+impl<T: Integer> OrderedReflexive for Rational<T> {}
+impl<T: Integer> Bounded for Rational<T> {
+    const _MIN: Self = Self::from_integer(T::_MIN);
+    const _MAX: Self = Self::from_integer(T::_MAX);
+}
+
+// std::ops implementations ========================================================================
+
+impl<T: Integer> Add for Rational<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.den, rhs.den);
+        let num = self.num * (rhs.den / g) + rhs.num * (self.den / g);
+        Self::new(num, self.den / g * rhs.den)
+    }
+}
+
+impl<T: Integer> Sub for Rational<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.den, rhs.den);
+        let num = self.num * (rhs.den / g) - rhs.num * (self.den / g);
+        Self::new(num, self.den / g * rhs.den)
+    }
+}
+
+impl<T: Integer> Mul for Rational<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let g1 = gcd(self.num, rhs.den);
+        let g2 = gcd(rhs.num, self.den);
+        if g1._is_zero() || g2._is_zero() {
+            return Self::_ZERO;
+        }
+        Self::new((self.num / g1) * (rhs.num / g2), (self.den / g2) * (rhs.den / g1))
+    }
+}
+
+impl<T: Integer> Div for Rational<T> {
+    type Output = Self;
+    /// Panics when dividing by zero
+    fn div(self, rhs: Self) -> Self::Output {
+        Mul::mul(self, rhs._recip())
+    }
+}
+
+impl<T: Integer> Rem for Rational<T> {
+    type Output = Self;
+    /// self - trunc(self / rhs) * rhs, with the sign of self
+    fn rem(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.den, rhs.den);
+        let (a, b) = (self.num * (rhs.den / g), rhs.num * (self.den / g));
+        Self::new(a % b, self.den / g * rhs.den)
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Neg for Rational<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Rational { num: -self.num, den: self.den }
+    }
+}
+
+/// (trait, method, assign_trait, assign_method)
+macro_rules! impl_rational_assign_and_scalar {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        // Rational += Rational
+        impl<T: Integer> $assign_trait for Rational<T> {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        // Rational + T
+        impl<T: Integer> $trait<T> for Rational<T> {
+            type Output = Self;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$method(Self::from_integer(rhs))
+            }
+        }
+
+        // Rational += T
+        impl<T: Integer> $assign_trait<T> for Rational<T> {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = (*self).$method(Self::from_integer(rhs));
+            }
+        }
+    };
+}
+
+impl_rational_assign_and_scalar!(Add, add, AddAssign, add_assign);
+impl_rational_assign_and_scalar!(Sub, sub, SubAssign, sub_assign);
+impl_rational_assign_and_scalar!(Mul, mul, MulAssign, mul_assign);
+impl_rational_assign_and_scalar!(Div, div, DivAssign, div_assign);
+impl_rational_assign_and_scalar!(Rem, rem, RemAssign, rem_assign);
+
+// Casting implementations =========================================================================
+
+macro_rules! impl_rational_to_primitive {
+    ($( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            self.trunc().$method()
+        }
+    )*}
+}
+
+macro_rules! impl_rational_from_primitive {
+    ($( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            T::$method(n).map(Self::from_integer)
+        }
+    )*}
+}
+
+/// Integer casts truncate towards zero, float casts divide the converted parts
+impl<T: Integer> ToPrimitive for Rational<T> {
+    impl_rational_to_primitive! {
+        fn to_isize -> isize;
+        fn to_i8 -> i8;
+        fn to_i16 -> i16;
+        fn to_i32 -> i32;
+        fn to_i64 -> i64;
+        fn to_i128 -> i128;
+        fn to_usize -> usize;
+        fn to_u8 -> u8;
+        fn to_u16 -> u16;
+        fn to_u32 -> u32;
+        fn to_u64 -> u64;
+        fn to_u128 -> u128;
+    }
+    fn to_f32(&self) -> Option<f32> {
+        Some(self.num.to_f32()? / self.den.to_f32()?)
+    }
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.num.to_f64()? / self.den.to_f64()?)
+    }
+}
+
+/// Float casts are the best approximation within the integer range, exact whenever representable
+impl<T: Integer> FromPrimitive for Rational<T> {
+    impl_rational_from_primitive! {
+        fn from_isize(isize);
+        fn from_i8(i8);
+        fn from_i16(i16);
+        fn from_i32(i32);
+        fn from_i64(i64);
+        fn from_i128(i128);
+        fn from_usize(usize);
+        fn from_u8(u8);
+        fn from_u16(u16);
+        fn from_u32(u32);
+        fn from_u64(u64);
+        fn from_u128(u128);
+    }
+    fn from_f32(n: f32) -> Option<Self> {
+        Self::approximate(n as f64, T::_MAX)
+    }
+    fn from_f64(n: f64) -> Option<Self> {
+        Self::approximate(n, T::_MAX)
+    }
+}
+
+// Concrete implementations ========================================================================
+
+macro_rules! impl_rational {
+    ($SrcT:ident, $SrcReprT:ident) => {
+        impl PhysicalRepr for Rational<$SrcT> {
+            const _BITS: u32 = 2 * $SrcT::_BITS;
+            const _BYTES: usize = 2 * $SrcT::_BYTES;
+            /// `num` is packed into the low half and `den` into the high half
+            type BitsRepr = $SrcReprT;
+            type BytesRepr = [u8; 2 * size_of::<$SrcT>()];
+            /// The bits are trusted to hold a normalized fraction
+            fn _from_bits(v: Self::BitsRepr) -> Self {
+                let num = $SrcT::_from_bits(v as <$SrcT as PhysicalRepr>::BitsRepr);
+                let den = $SrcT::_from_bits((v >> $SrcT::_BITS) as <$SrcT as PhysicalRepr>::BitsRepr);
+                Rational { num, den }
+            }
+            fn _to_bits(self) -> Self::BitsRepr {
+                (self.num._to_bits() as $SrcReprT) | ((self.den._to_bits() as $SrcReprT) << $SrcT::_BITS)
+            }
+            fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_be_bytes(bytes))
+            }
+            fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_le_bytes(bytes))
+            }
+            fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+                Self::_from_bits($SrcReprT::from_ne_bytes(bytes))
+            }
+            fn _to_be_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_be_bytes()
+            }
+            fn _to_le_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_le_bytes()
+            }
+            fn _to_ne_bytes(self) -> Self::BytesRepr {
+                self._to_bits().to_ne_bytes()
+            }
+        }
+    };
+}
+
+macro_rules! impl_rational_signed {
+    ($SrcT:ident, $SrcReprT:ident) => {
+        impl_rational!($SrcT, $SrcReprT);
+        /// The sign mask selects the sign bit of `num`
+        impl Signed for Rational<$SrcT> {
+            const _SIGN_MASK: Self::BitsRepr = <$SrcT as Signed>::_SIGN_MASK as $SrcReprT;
+            const _NEG_ONE: Self = Self::from_integer($SrcT::_NEG_ONE);
+            fn _abs(self) -> Self {
+                Rational { num: self.num._abs(), den: self.den }
+            }
+            fn _signum(self) -> Self {
+                Self::from_integer(self.num._signum())
+            }
+            fn _is_positive(self) -> bool {
+                self.num._is_positive()
+            }
+            fn _is_negative(self) -> bool {
+                self.num._is_negative()
+            }
+        }
+        impl BoundedSigned for Rational<$SrcT> {
+            const _MIN_POSITIVE: Self = Rational { num: 1, den: $SrcT::MAX };
+        }
+    };
+}
+
+impl_rational!(u8, u16);
+impl_rational!(u16, u32);
+impl_rational!(u32, u64);
+impl_rational!(u64, u128);
+impl_rational_signed!(i8, u16);
+impl_rational_signed!(i16, u32);
+impl_rational_signed!(i32, u64);
+impl_rational_signed!(i64, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::tests::horner;
+
+    #[test]
+    fn test_normalization_and_arithmetic() {
+        let ratio = Rational::new(44100u32, 48000);
+        assert_eq!((ratio.numer(), ratio.denom()), (147, 160));
+        assert_eq!(Rational::new(3i32, -6), Rational::new(-1, 2));
+        let (a, b) = (Rational::new(1i64, 6), Rational::new(3, 10));
+        assert_eq!(a + b, Rational::new(7, 15));
+        assert_eq!(a - b, Rational::new(-2, 15));
+        assert_eq!(a * b, Rational::new(1, 20));
+        assert_eq!(a / b, Rational::new(5, 9));
+        assert_eq!(Rational::new(7i32, 2) % Rational::new(3, 2), Rational::new(1, 2));
+        assert_eq!(Rational::new(-7i32, 2) % Rational::new(3, 2), Rational::new(-1, 2));
+        assert_eq!(-a, Rational::new(-1, 6));
+        assert_eq!(b._inv()._inv(), b);
+        assert_eq!(Rational::new(-7i32, 2).floor(), -4);
+        assert_eq!(Rational::new(-7i32, 2).trunc(), -3);
+        assert_eq!(horner(&[Rational::new(1i32, 2), Rational::_ZERO, Rational::_NEG_ONE], Rational::new(2, 3)), Rational::new(-7, 9));
+        assert_eq!(Rational::new(5i32, 3).to_string(), "5/3");
+        assert_eq!(Rational::checked_new(1i32, 0), None);
+        assert_eq!(Rational::checked_new(1i32, i32::MIN), None);
+        assert_eq!(Rational::checked_new(i32::MIN, -1), None);
+        assert_eq!(Rational::checked_new(-1i32, i32::MIN), None);
+        assert_eq!(Rational::checked_new(i8::MIN, i8::MIN), Some(Rational::_ONE));
+        assert_eq!(Rational::checked_new(0i8, i8::MIN), Some(Rational::_ZERO));
+        assert_eq!(Rational::checked_new(2i16, i16::MIN), Some(Rational::new(-1, 1 << 14)));
+        assert_eq!(Rational::checked_new(i64::MIN, 3), Some(Rational::new(i64::MIN, 3)));
+        assert_eq!(Rational::new(i64::MIN, -2).numer(), 1 << 62);
+    }
+
+    #[test]
+    fn test_ordering_without_overflow() {
+        let a = Rational::new(i8::MAX, 126);
+        let b = Rational::new(126i8, 125);
+        assert!(a < b);
+        assert!(Rational::new(-1i8, 127) > Rational::new(-1, 126));
+        assert_eq!(Rational::new(2i8, 4).cmp(&Rational::new(1, 2)), Ordering::Equal);
+        assert!(Rational::new(u64::MAX - 1, u64::MAX) > Rational::new(u64::MAX - 2, u64::MAX - 1));
+        assert_eq!(Rational::new(3i32, 4)._max(Rational::new(2, 3)), Rational::new(3, 4));
+    }
+
+    #[test]
+    fn test_approximation() {
+        assert_eq!(Rational::approximate(std::f64::consts::PI, 1000i32), Some(Rational::new(355, 113)));
+        assert_eq!(Rational::approximate(std::f64::consts::PI, 100i32), Some(Rational::new(311, 99)));
+        assert_eq!(Rational::approximate(-0.75, 100i32), Some(Rational::new(-3, 4)));
+        assert_eq!(Rational::approximate(44100.0 / 48000.0, 1000u32), Some(Rational::new(147, 160)));
+        assert_eq!(Rational::<i64>::from_f64(0.1), Some(Rational::new(3602879701896397, 36028797018963968)));
+        assert_eq!(Rational::<u8>::from_f64(-0.5), None);
+        assert_eq!(Rational::<i8>::from_f64(300.0), None);
+        assert_eq!(Rational::<i8>::from_f64(0.007), Some(Rational::new(1, 127)));
+        assert_eq!(Rational::<i16>::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_casts_and_repr() {
+        let r = Rational::new(-7i32, 2);
+        assert_eq!(r.to_i32(), Some(-3));
+        assert_eq!(r.to_u8(), None);
+        assert_eq!(r.to_f64(), Some(-3.5));
+        assert_eq!(Rational::<i16>::from_i64(1 << 20), None);
+        assert_eq!(Rational::<i32>::_from_bits(r._to_bits()), r);
+        assert_eq!(Rational::<u16>::_from_le_bytes(Rational::new(3u16, 4)._to_le_bytes()), Rational::new(3, 4));
+        assert_eq!(r._abs(), Rational::new(7, 2));
+        assert!(r._is_negative() && r._signum() == Rational::_NEG_ONE);
+    }
+}
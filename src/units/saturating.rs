@@ -0,0 +1,333 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, BitAnd, BitOr, BitXor, Div, DivAssign, Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, Shr, Sub, SubAssign};
+use super::*;
+
+/// Unit whose std::ops clamp to the bounds instead of overflowing
+/// - a drop-in for generic code written against `Unit` or `Integer`, e.g. to clip an integer pipeline
+/// - division by zero behaves like the wrapped unit (panics for integers)
+/// - remainder cannot overflow mathematically, so it wraps (MIN % -1 == 0)
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Saturating<T>(pub T);
+
+impl<T: Display> Display for Saturating<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// Unit implementations ============================================================================
+
+impl<T: SaturatingOps + WrappingOps> Unit for Saturating<T> {}
+impl<T: SaturatingOps + WrappingOps> UnitOps for Saturating<T> {}
+impl<T: SaturatingOps + WrappingOps> Zero for Saturating<T> {
+    const _ZERO: Self = Saturating(T::_ZERO);
+}
+impl<T: SaturatingOps + WrappingOps> One for Saturating<T> {
+    const _ONE: Self = Saturating(T::_ONE);
+}
+impl<T: SaturatingOps + WrappingOps> Symbolic for Saturating<T> {
+    type Base = T;
+    fn _dismantle(self) -> Option<Self::Base> {
+        Some(self.0)
+    }
+}
+// SAFETY: transparent over `T`
+unsafe impl<T: PlainBytes> PlainBytes for Saturating<T> {}
+impl<T: PhysicalRepr> PhysicalRepr for Saturating<T> {
+    const _BITS: u32 = T::_BITS;
+    const _BYTES: usize = T::_BYTES;
+    const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = match T::_PLAIN_BYTES {
+        // SAFETY: transparent over a `PlainBytes` unit
+        Some(_) => Some(unsafe { PlainBytesProof::new_unchecked() }),
+        None => None,
+    };
+    type BitsRepr = T::BitsRepr;
+    type BytesRepr = T::BytesRepr;
+    fn _from_bits(v: Self::BitsRepr) -> Self {
+        Saturating(T::_from_bits(v))
+    }
+    fn _to_bits(self) -> Self::BitsRepr {
+        self.0._to_bits()
+    }
+    fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+        Saturating(T::_from_be_bytes(bytes))
+    }
+    fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+        Saturating(T::_from_le_bytes(bytes))
+    }
+    fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+        Saturating(T::_from_ne_bytes(bytes))
+    }
+    fn _to_be_bytes(self) -> Self::BytesRepr {
+        self.0._to_be_bytes()
+    }
+    fn _to_le_bytes(self) -> Self::BytesRepr {
+        self.0._to_le_bytes()
+    }
+    fn _to_ne_bytes(self) -> Self::BytesRepr {
+        self.0._to_ne_bytes()
+    }
+}
+
+// Properties ======================================================================================
+
+impl<T: Ordered> Ordered for Saturating<T> {
+    fn _min(self, other: Self) -> Self {
+        Saturating(self.0._min(other.0))
+    }
+    fn _max(self, other: Self) -> Self {
+        Saturating(self.0._max(other.0))
+    }
+    fn _clamp(self, min: Self, max: Self) -> Self {
+        Saturating(self.0._clamp(min.0, max.0))
+    }
+}
+impl<T: OrderedReflexive> OrderedReflexive for Saturating<T> {}
+impl<T: Bounded> Bounded for Saturating<T> {
+    const _MIN: Self = Saturating(T::_MIN);
+    const _MAX: Self = Saturating(T::_MAX);
+}
+
+/// Integer semantics with clamping, powers saturate towards the sign of the exact result
+impl<T: Integer + ExpBasic<Output = T> + SaturatingOps + WrappingOps> Integer for Saturating<T> {}
+impl<T: Integer + SaturatingOps + WrappingOps> Bitwise for Saturating<T> {}
+impl<T: Integer + ExpBasic<Output = T> + SaturatingOps + WrappingOps> ExpBasic for Saturating<T> {
+    type Output = Self;
+    fn _sq(self) -> <Self as ExpBasic>::Output {
+        self * self
+    }
+    fn _sqrt(self) -> <Self as ExpBasic>::Output {
+        Saturating(self.0._sqrt())
+    }
+}
+impl<T: Integer + ExpBasic<Output = T> + SaturatingOps + WrappingOps> ExpPowDynamic<u32> for Saturating<T> {
+    fn _pow(self, rhs: u32) -> <Self as ExpBasic>::Output {
+        // square and multiply, once saturated a magnitude of at least 2 stays saturated
+        let (mut acc, mut base, mut exp) = (Self::_ONE, self, rhs);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc *= base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base * base;
+            }
+        }
+        acc
+    }
+}
+
+// std::ops implementations ========================================================================
+
+impl<T: SaturatingOps> Add for Saturating<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Saturating(self.0._saturating_add(rhs.0))
+    }
+}
+
+impl<T: SaturatingOps> Sub for Saturating<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Saturating(self.0._saturating_sub(rhs.0))
+    }
+}
+
+impl<T: SaturatingOps> Mul for Saturating<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Saturating(self.0._saturating_mul(rhs.0))
+    }
+}
+
+impl<T: SaturatingOps> Div for Saturating<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        Saturating(self.0._saturating_div(rhs.0))
+    }
+}
+
+impl<T: WrappingOps> Rem for Saturating<T> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self::Output {
+        Saturating(self.0._wrapping_rem(rhs.0))
+    }
+}
+
+impl<T: SaturatingOps> Neg for Saturating<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Saturating(self.0._saturating_neg())
+    }
+}
+
+/// (trait, method, assign_trait, assign_method, bound)
+macro_rules! impl_saturating_assign_and_scalar {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $Bound:ident) => {
+        // Saturating += Saturating
+        impl<T: $Bound> $assign_trait for Saturating<T> {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        // Saturating + T
+        impl<T: $Bound> $trait<T> for Saturating<T> {
+            type Output = Self;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$method(Saturating(rhs))
+            }
+        }
+
+        // Saturating += T
+        impl<T: $Bound> $assign_trait<T> for Saturating<T> {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = (*self).$method(Saturating(rhs));
+            }
+        }
+    };
+}
+
+impl_saturating_assign_and_scalar!(Add, add, AddAssign, add_assign, SaturatingOps);
+impl_saturating_assign_and_scalar!(Sub, sub, SubAssign, sub_assign, SaturatingOps);
+impl_saturating_assign_and_scalar!(Mul, mul, MulAssign, mul_assign, SaturatingOps);
+impl_saturating_assign_and_scalar!(Div, div, DivAssign, div_assign, SaturatingOps);
+impl_saturating_assign_and_scalar!(Rem, rem, RemAssign, rem_assign, WrappingOps);
+
+/// (trait, method)
+macro_rules! impl_saturating_bitwise {
+    ($($trait:ident, $method:ident);*) => {$(
+        impl<T: Integer> $trait for Saturating<T> {
+            type Output = Self;
+            fn $method(self, rhs: Self) -> Self::Output {
+                Saturating(self.0.$method(rhs.0))
+            }
+        }
+    )*};
+}
+
+impl_saturating_bitwise!(BitAnd, bitand; BitOr, bitor; BitXor, bitxor; Shl, shl; Shr, shr);
+
+impl<T: Integer> Not for Saturating<T> {
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        Saturating(!self.0)
+    }
+}
+
+// Casting implementations =========================================================================
+
+macro_rules! impl_saturating_to_primitive {
+    ($( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            self.0.$method()
+        }
+    )*}
+}
+
+macro_rules! impl_saturating_from_primitive {
+    ($( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            T::$method(n).map(Saturating)
+        }
+    )*}
+}
+
+impl<T: ToPrimitive> ToPrimitive for Saturating<T> {
+    impl_saturating_to_primitive! {
+        fn to_isize -> isize;
+        fn to_i8 -> i8;
+        fn to_i16 -> i16;
+        fn to_i32 -> i32;
+        fn to_i64 -> i64;
+        fn to_i128 -> i128;
+        fn to_usize -> usize;
+        fn to_u8 -> u8;
+        fn to_u16 -> u16;
+        fn to_u32 -> u32;
+        fn to_u64 -> u64;
+        fn to_u128 -> u128;
+        fn to_f32 -> f32;
+        fn to_f64 -> f64;
+    }
+}
+
+/// Checked like the wrapped unit
+impl<T: FromPrimitive> FromPrimitive for Saturating<T> {
+    impl_saturating_from_primitive! {
+        fn from_isize(isize);
+        fn from_i8(i8);
+        fn from_i16(i16);
+        fn from_i32(i32);
+        fn from_i64(i64);
+        fn from_i128(i128);
+        fn from_usize(usize);
+        fn from_u8(u8);
+        fn from_u16(u16);
+        fn from_u32(u32);
+        fn from_u64(u64);
+        fn from_u128(u128);
+        fn from_f32(f32);
+        fn from_f64(f64);
+    }
+}
+
+/// Rounds and overflows like the wrapped unit
+impl<T: UnitCast> UnitCast for Saturating<T> {
+    const _CAST_ROUNDING: Rounding = T::_CAST_ROUNDING;
+    #[inline]
+    fn _to_cast_value(self) -> CastValue {
+        self.0._to_cast_value()
+    }
+    #[inline]
+    fn _from_cast_value(v: CastValue, rounding: Rounding, overflow: Option<Overflow>) -> Option<Self> {
+        T::_from_cast_value(v, rounding, overflow).map(Saturating)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mixing code unaware of the overflow behavior of `T`
+    fn mix<T: Integer>(a: &[T], b: &[T], gain: T) -> Vec<T> {
+        a.iter().zip(b).map(|(&x, &y)| (x + y) * gain).collect()
+    }
+
+    #[test]
+    fn test_integer_overflow_ops() {
+        assert_eq!(250u8._checked_add(10), None);
+        assert_eq!(i16::MIN._checked_div(-1), None);
+        assert_eq!(7i32._checked_rem(0), None);
+        assert_eq!(250u8._wrapping_add(10), 4);
+        assert_eq!(i8::MIN._wrapping_neg(), i8::MIN);
+        assert_eq!(i8::MIN._wrapping_rem(-1), 0);
+        assert_eq!(100i8._saturating_mul(2), i8::MAX);
+        assert_eq!(5u32._saturating_neg(), 0);
+        assert_eq!(i8::MIN._saturating_neg(), i8::MAX);
+        assert_eq!(200u8._overflowing_mul(2), (144, true));
+        assert_eq!(i32::MIN._overflowing_div(-1), (i32::MIN, true));
+    }
+
+    #[test]
+    fn test_saturating_unit() {
+        let a = [20000i16, -20000, 100].map(Saturating);
+        let b = [20000i16, -20000, -50].map(Saturating);
+        assert_eq!(mix(&a, &b, Saturating(2)), [i16::MAX, i16::MIN, 100].map(Saturating));
+        assert_eq!(Saturating(3i8)._pow(5), Saturating(i8::MAX));
+        assert_eq!(Saturating(-3i8)._pow(5), Saturating(i8::MIN));
+        assert_eq!(Saturating(-2i8)._pow(7), Saturating(-128));
+        assert_eq!(Saturating(-3i8)._pow(4), Saturating(81));
+        assert_eq!(-Saturating(i32::MIN), Saturating(i32::MAX));
+        let mut x = Saturating(250u8);
+        x += 10;
+        x %= Saturating(7);
+        assert_eq!(x, Saturating(3));
+        assert_eq!(Saturating(Q15::_MAX) + Saturating(Q15::_MAX), Saturating(Q15::_MAX));
+        assert_eq!(Saturating::<u16>::_from_le_bytes(513u16.to_le_bytes()), Saturating(513));
+        assert_eq!(Saturating::<i8>::from_i32(300), None);
+    }
+}
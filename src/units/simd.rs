@@ -0,0 +1,863 @@
+#![allow(non_camel_case_types)]
+
+use std::cmp::Ordering;
+use std::num::FpCategory;
+use std::ops::{Add, AddAssign, BitAnd, BitOr, BitXor, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, Shr, Sub, SubAssign};
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+use super::*;
+
+/// Result of a lane-wise comparison, one bit per lane (lane 0 in the lowest bit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Mask<const N: usize>(u8);
+
+impl<const N: usize> Mask<N> {
+    const ALL: u8 = ((1u16 << N) - 1) as u8;
+
+    /// Bits above lane N are ignored
+    pub const fn from_bitmask(bits: u8) -> Self {
+        Mask(bits & Self::ALL)
+    }
+    pub const fn splat(v: bool) -> Self {
+        Mask(if v { Self::ALL } else { 0 })
+    }
+    pub fn from_array(lanes: [bool; N]) -> Self {
+        Mask(lanes.iter().enumerate().fold(0, |bits, (i, &v)| bits | ((v as u8) << i)))
+    }
+    pub fn to_array(self) -> [bool; N] {
+        std::array::from_fn(|i| self.test(i))
+    }
+    pub const fn bitmask(self) -> u8 {
+        self.0
+    }
+    pub const fn test(self, lane: usize) -> bool {
+        self.0 & (1 << lane) != 0
+    }
+    pub fn set(&mut self, lane: usize, v: bool) {
+        assert!(lane < N);
+        self.0 = (self.0 & !(1 << lane)) | ((v as u8) << lane);
+    }
+    pub const fn any(self) -> bool {
+        self.0 != 0
+    }
+    pub const fn all(self) -> bool {
+        self.0 == Self::ALL
+    }
+    pub const fn none(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const N: usize> Not for Mask<N> {
+    type Output = Self;
+    fn not(self) -> Self::Output {
+        Mask(!self.0 & Self::ALL)
+    }
+}
+
+impl<const N: usize> BitAnd for Mask<N> {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Mask(self.0 & rhs.0)
+    }
+}
+
+impl<const N: usize> BitOr for Mask<N> {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Mask(self.0 | rhs.0)
+    }
+}
+
+impl<const N: usize> BitXor for Mask<N> {
+    type Output = Self;
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Mask(self.0 ^ rhs.0)
+    }
+}
+
+// Shared lane vector plumbing =====================================================================
+
+/// (vector, lane type, lanes, alignment, bits vector)
+macro_rules! impl_simd_lanes {
+    ($V:ident, $T:ident, $N:literal, $Align:literal, $Bits:ident) => {
+        /// Vector of `LANES` values, arithmetic and the `simd_*` comparisons act lane by lane
+        /// - the `bool` queries of the unit traits (`_is_nan`, `_is_negative`, `_approx_eq`, ...) and
+        ///   `PartialOrd` hold only when they hold for every lane, so `_is_nan` is false for a vector
+        ///   with a single NaN lane and `a < b` is false when only some lanes are smaller
+        /// - `_classify` picks the first class any lane is in: NaN, infinite, subnormal, zero, normal, so
+        ///   a vector is `Normal` only when every lane is and `_is_nan` (or any `_is_*`) implies that class
+        /// - the `Mask` returning methods (`simd_lt`, `is_nan`, ...) answer per lane
+        #[repr(C, align($Align))]
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        pub struct $V([$T; $N]);
+
+        impl $V {
+            pub const LANES: usize = $N;
+
+            pub const fn splat(v: $T) -> Self {
+                $V([v; $N])
+            }
+            pub const fn from_array(lanes: [$T; $N]) -> Self {
+                $V(lanes)
+            }
+            pub const fn to_array(self) -> [$T; $N] {
+                self.0
+            }
+            pub const fn as_array(&self) -> &[$T; $N] {
+                &self.0
+            }
+            /// Loads the first `LANES` values, panics when the slice is shorter
+            pub fn from_slice(slice: &[$T]) -> Self {
+                $V(slice[..$N].try_into().unwrap())
+            }
+            /// Stores into the first `LANES` values, panics when the slice is shorter
+            pub fn write_to_slice(self, slice: &mut [$T]) {
+                slice[..$N].copy_from_slice(&self.0);
+            }
+            /// Lanes of `if_true` where the mask is set, `if_false` elsewhere
+            pub fn select(mask: Mask<$N>, if_true: Self, if_false: Self) -> Self {
+                $V(std::array::from_fn(|i| if mask.test(i) { if_true.0[i] } else { if_false.0[i] }))
+            }
+            #[inline]
+            fn map(self, f: impl Fn($T) -> $T) -> Self {
+                $V(self.0.map(f))
+            }
+            #[inline]
+            fn zip(self, rhs: Self, f: impl Fn($T, $T) -> $T) -> Self {
+                $V(std::array::from_fn(|i| f(self.0[i], rhs.0[i])))
+            }
+            #[inline]
+            fn compare(self, rhs: Self, f: impl Fn(&$T, &$T) -> bool) -> Mask<$N> {
+                Mask((0..$N).fold(0, |bits, i| bits | ((f(&self.0[i], &rhs.0[i]) as u8) << i)))
+            }
+            pub fn simd_eq(self, rhs: Self) -> Mask<$N> {
+                self.compare(rhs, $T::eq)
+            }
+            pub fn simd_ne(self, rhs: Self) -> Mask<$N> {
+                self.compare(rhs, $T::ne)
+            }
+            pub fn simd_lt(self, rhs: Self) -> Mask<$N> {
+                self.compare(rhs, $T::lt)
+            }
+            pub fn simd_le(self, rhs: Self) -> Mask<$N> {
+                self.compare(rhs, $T::le)
+            }
+            pub fn simd_gt(self, rhs: Self) -> Mask<$N> {
+                self.compare(rhs, $T::gt)
+            }
+            pub fn simd_ge(self, rhs: Self) -> Mask<$N> {
+                self.compare(rhs, $T::ge)
+            }
+        }
+
+        impl From<[$T; $N]> for $V {
+            fn from(lanes: [$T; $N]) -> Self {
+                $V(lanes)
+            }
+        }
+
+        impl From<$V> for [$T; $N] {
+            fn from(v: $V) -> Self {
+                v.0
+            }
+        }
+
+        impl Index<usize> for $V {
+            type Output = $T;
+            fn index(&self, lane: usize) -> &Self::Output {
+                &self.0[lane]
+            }
+        }
+
+        impl IndexMut<usize> for $V {
+            fn index_mut(&mut self, lane: usize) -> &mut Self::Output {
+                &mut self.0[lane]
+            }
+        }
+
+        impl UnitOps for $V {}
+        impl Zero for $V {
+            const _ZERO: Self = $V::splat($T::_ZERO);
+        }
+        impl One for $V {
+            const _ONE: Self = $V::splat($T::_ONE);
+        }
+        impl Symbolic for $V {
+            type Base = $V;
+        }
+        impl Bounded for $V {
+            const _MIN: Self = $V::splat($T::_MIN);
+            const _MAX: Self = $V::splat($T::_MAX);
+        }
+
+        /// Partial order of the lanes: ordered only when every lane agrees
+        impl PartialOrd for $V {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                let (le, ge) = (self.simd_le(*other).all(), self.simd_ge(*other).all());
+                match (le, ge) {
+                    (true, true) => Some(Ordering::Equal),
+                    (true, false) => Some(Ordering::Less),
+                    (false, true) => Some(Ordering::Greater),
+                    (false, false) => None,
+                }
+            }
+        }
+
+        /// Lane-wise, as for the scalar unit
+        impl Ordered for $V {
+            fn _min(self, other: Self) -> Self {
+                self.zip(other, $T::_min)
+            }
+            fn _max(self, other: Self) -> Self {
+                self.zip(other, $T::_max)
+            }
+            fn _clamp(self, min: Self, max: Self) -> Self {
+                $V(std::array::from_fn(|i| self.0[i]._clamp(min.0[i], max.0[i])))
+            }
+        }
+
+        impl Rem for $V {
+            type Output = Self;
+            fn rem(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::rem)
+            }
+        }
+
+        impl_simd_assign_and_scalar!($V, $T, Add, add, AddAssign, add_assign);
+        impl_simd_assign_and_scalar!($V, $T, Sub, sub, SubAssign, sub_assign);
+        impl_simd_assign_and_scalar!($V, $T, Mul, mul, MulAssign, mul_assign);
+        impl_simd_assign_and_scalar!($V, $T, Div, div, DivAssign, div_assign);
+        impl_simd_assign_and_scalar!($V, $T, Rem, rem, RemAssign, rem_assign);
+
+        // SAFETY: the alignment equals the size of the lane array, so there is no padding, and lanes
+        // are plain primitives stored in order
+        unsafe impl PlainBytes for $V {}
+        /// Lanes are laid out in order, each lane in the requested byte order
+        impl PhysicalRepr for $V {
+            const _BITS: u32 = $N * $T::_BITS;
+            const _BYTES: usize = $N * $T::_BYTES;
+            const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = Some(PlainBytesProof::PROOF);
+            type BitsRepr = $Bits;
+            type BytesRepr = [u8; $N * size_of::<$T>()];
+            fn _from_bits(v: Self::BitsRepr) -> Self {
+                $V(v.0.map($T::_from_bits))
+            }
+            fn _to_bits(self) -> Self::BitsRepr {
+                $Bits(self.0.map($T::_to_bits))
+            }
+            fn _from_be_bytes(bytes: Self::BytesRepr) -> Self {
+                $V(std::array::from_fn(|i| $T::from_be_bytes(bytes[i * size_of::<$T>()..][..size_of::<$T>()].try_into().unwrap())))
+            }
+            fn _from_le_bytes(bytes: Self::BytesRepr) -> Self {
+                $V(std::array::from_fn(|i| $T::from_le_bytes(bytes[i * size_of::<$T>()..][..size_of::<$T>()].try_into().unwrap())))
+            }
+            fn _from_ne_bytes(bytes: Self::BytesRepr) -> Self {
+                $V(std::array::from_fn(|i| $T::from_ne_bytes(bytes[i * size_of::<$T>()..][..size_of::<$T>()].try_into().unwrap())))
+            }
+            fn _to_be_bytes(self) -> Self::BytesRepr {
+                let mut bytes = [0; $N * size_of::<$T>()];
+                bytes.chunks_exact_mut(size_of::<$T>()).zip(self.0).for_each(|(b, v)| b.copy_from_slice(&v.to_be_bytes()));
+                bytes
+            }
+            fn _to_le_bytes(self) -> Self::BytesRepr {
+                let mut bytes = [0; $N * size_of::<$T>()];
+                bytes.chunks_exact_mut(size_of::<$T>()).zip(self.0).for_each(|(b, v)| b.copy_from_slice(&v.to_le_bytes()));
+                bytes
+            }
+            fn _to_ne_bytes(self) -> Self::BytesRepr {
+                let mut bytes = [0; $N * size_of::<$T>()];
+                bytes.chunks_exact_mut(size_of::<$T>()).zip(self.0).for_each(|(b, v)| b.copy_from_slice(&v.to_ne_bytes()));
+                bytes
+            }
+        }
+    };
+}
+
+/// (vector, lane type, trait, method, assign_trait, assign_method)
+macro_rules! impl_simd_assign_and_scalar {
+    ($V:ident, $T:ident, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        // V += V
+        impl $assign_trait for $V {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        // V + T, broadcast to every lane
+        impl $trait<$T> for $V {
+            type Output = Self;
+            fn $method(self, rhs: $T) -> Self::Output {
+                self.$method($V::splat(rhs))
+            }
+        }
+
+        // V += T
+        impl $assign_trait<$T> for $V {
+            fn $assign_method(&mut self, rhs: $T) {
+                *self = (*self).$method($V::splat(rhs));
+            }
+        }
+    };
+}
+
+// Integer lane vectors (bit representations) ======================================================
+
+/// (vector, lane type, lanes, alignment), the vectors are their own bit representation
+macro_rules! impl_simd_int {
+    ($V:ident, $T:ident, $N:literal, $Align:literal) => {
+        impl_simd_lanes!($V, $T, $N, $Align, $V);
+
+        impl Eq for $V {}
+        impl Unit for $V {}
+        impl Bitwise for $V {}
+
+        impl Add for $V {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::add)
+            }
+        }
+        impl Sub for $V {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::sub)
+            }
+        }
+        impl Mul for $V {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::mul)
+            }
+        }
+        impl Div for $V {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::div)
+            }
+        }
+        impl Not for $V {
+            type Output = Self;
+            fn not(self) -> Self::Output {
+                self.map($T::not)
+            }
+        }
+        impl BitAnd for $V {
+            type Output = Self;
+            fn bitand(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::bitand)
+            }
+        }
+        impl BitOr for $V {
+            type Output = Self;
+            fn bitor(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::bitor)
+            }
+        }
+        impl BitXor for $V {
+            type Output = Self;
+            fn bitxor(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::bitxor)
+            }
+        }
+        /// Each lane is shifted by the matching lane of `rhs`
+        impl Shl for $V {
+            type Output = Self;
+            fn shl(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::shl)
+            }
+        }
+        /// Each lane is shifted by the matching lane of `rhs`
+        impl Shr for $V {
+            type Output = Self;
+            fn shr(self, rhs: Self) -> Self::Output {
+                self.zip(rhs, $T::shr)
+            }
+        }
+    };
+}
+
+impl_simd_int!(u32x4, u32, 4, 16);
+impl_simd_int!(u32x8, u32, 8, 32);
+impl_simd_int!(u64x2, u64, 2, 16);
+impl_simd_int!(u64x4, u64, 4, 32);
+
+// Float lane vectors ==============================================================================
+
+/// Defines `fn $name(self, rhs) -> Self` with the intrinsic where `$arch` holds, and lane by lane otherwise
+macro_rules! simd_kernel {
+    ($arch:meta, $V:ident, $Reg:ident, fn $name:ident => $intrinsic:ident, $lanes:expr) => {
+        impl $V {
+            #[cfg($arch)]
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn $name(self, rhs: Self) -> Self {
+                // the vector and register have the same size and the target feature is enabled at compile time
+                unsafe { std::mem::transmute::<$Reg, Self>($intrinsic(std::mem::transmute::<Self, $Reg>(self), std::mem::transmute::<Self, $Reg>(rhs))) }
+            }
+            #[cfg(not($arch))]
+            #[inline]
+            fn $name(self, rhs: Self) -> Self {
+                self.zip(rhs, $lanes)
+            }
+        }
+    };
+    ($arch:meta, $V:ident, $Reg:ident, fn $name:ident => $intrinsic:ident, unary $lanes:expr) => {
+        impl $V {
+            #[cfg($arch)]
+            #[inline]
+            #[allow(unused_unsafe)]
+            fn $name(self) -> Self {
+                // the vector and register have the same size and the target feature is enabled at compile time
+                unsafe { std::mem::transmute::<$Reg, Self>($intrinsic(std::mem::transmute::<Self, $Reg>(self))) }
+            }
+            #[cfg(not($arch))]
+            #[inline]
+            fn $name(self) -> Self {
+                self.map($lanes)
+            }
+        }
+    };
+}
+
+macro_rules! impl_simd_float_to_primitive {
+    ($( fn $method:ident -> $DstT:ident ; )*) => {$(
+        #[inline]
+        fn $method(&self) -> Option<$DstT> {
+            self.to_scalar()?.$method()
+        }
+    )*}
+}
+
+macro_rules! impl_simd_float_from_primitive {
+    ($T:ident : $( fn $method:ident($SrcT:ident) ; )*) => {$(
+        #[inline]
+        fn $method(n: $SrcT) -> Option<Self> {
+            $T::$method(n).map(Self::splat)
+        }
+    )*}
+}
+
+/// (vector, lane type, lanes, alignment, bits vector, arch predicate, register, add, sub, mul, div, sqrt)
+/// - add, sub, mul, div and sqrt use the `std::arch` intrinsics when the predicate holds, which are
+///   correctly rounded like the scalar ops, so results are bit-identical to the scalar unit
+/// - everything else is computed lane by lane with the scalar unit
+macro_rules! impl_simd_float {
+    ($V:ident, $T:ident, $N:literal, $Align:literal, $Bits:ident, $arch:meta, $Reg:ident,
+     $add:ident, $sub:ident, $mul:ident, $div:ident, $sqrt:ident) => {
+        impl_simd_lanes!($V, $T, $N, $Align, $Bits);
+
+        simd_kernel!($arch, $V, $Reg, fn add_lanes => $add, |a, b| a + b);
+        simd_kernel!($arch, $V, $Reg, fn sub_lanes => $sub, |a, b| a - b);
+        simd_kernel!($arch, $V, $Reg, fn mul_lanes => $mul, |a, b| a * b);
+        simd_kernel!($arch, $V, $Reg, fn div_lanes => $div, |a, b| a / b);
+        simd_kernel!($arch, $V, $Reg, fn sqrt_lanes => $sqrt, unary $T::sqrt);
+
+        impl $V {
+            #[inline]
+            fn test_lanes(self, f: impl Fn($T) -> bool) -> Mask<$N> {
+                Mask((0..$N).fold(0, |bits, i| bits | ((f(self.0[i]) as u8) << i)))
+            }
+            pub fn is_nan(self) -> Mask<$N> {
+                self.test_lanes($T::is_nan)
+            }
+            pub fn is_infinite(self) -> Mask<$N> {
+                self.test_lanes($T::is_infinite)
+            }
+            pub fn is_finite(self) -> Mask<$N> {
+                self.test_lanes($T::is_finite)
+            }
+            pub fn is_subnormal(self) -> Mask<$N> {
+                self.test_lanes($T::is_subnormal)
+            }
+            pub fn is_normal(self) -> Mask<$N> {
+                self.test_lanes($T::is_normal)
+            }
+            pub fn is_sign_positive(self) -> Mask<$N> {
+                self.test_lanes($T::is_sign_positive)
+            }
+            pub fn is_sign_negative(self) -> Mask<$N> {
+                self.test_lanes($T::is_sign_negative)
+            }
+            /// Per lane `_approx_eq`, with the tolerances of that lane
+            pub fn approx_eq_lanes(self, other: Self, rel: Self, abs: Self, ulps: u32) -> Mask<$N> {
+                Mask::from_array(std::array::from_fn(|i| self.0[i]._approx_eq(other.0[i], rel.0[i], abs.0[i], ulps)))
+            }
+            pub fn reduce_sum(self) -> $T {
+                self.0.into_iter().fold($T::_ZERO, $T::add)
+            }
+            pub fn reduce_min(self) -> $T {
+                self.0.into_iter().fold($T::_INFINITY, $T::min)
+            }
+            pub fn reduce_max(self) -> $T {
+                self.0.into_iter().fold($T::_NEG_INFINITY, $T::max)
+            }
+            /// The common value when every lane holds the same bits
+            fn to_scalar(self) -> Option<$T> {
+                let first = self.0[0];
+                self.0.iter().all(|v| v.to_bits() == first.to_bits()).then_some(first)
+            }
+        }
+
+        impl Unit for $V {}
+        impl Inv for $V {
+            fn _inv(self) -> Self {
+                self._recip()
+            }
+        }
+
+        impl Add for $V {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self::Output {
+                self.add_lanes(rhs)
+            }
+        }
+        impl Sub for $V {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.sub_lanes(rhs)
+            }
+        }
+        impl Mul for $V {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self::Output {
+                self.mul_lanes(rhs)
+            }
+        }
+        impl Div for $V {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self::Output {
+                self.div_lanes(rhs)
+            }
+        }
+        impl Neg for $V {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                self.map($T::neg)
+            }
+        }
+
+        /// The sign queries hold only when they hold for every lane, see `is_sign_negative` for a mask
+        impl Signed for $V {
+            const _SIGN_MASK: Self::BitsRepr = $Bits::splat(<$T as Signed>::_SIGN_MASK);
+            const _NEG_ONE: Self = $V::splat($T::_NEG_ONE);
+            fn _abs(self) -> Self {
+                self.map($T::abs)
+            }
+            fn _signum(self) -> Self {
+                self.map($T::signum)
+            }
+            fn _is_positive(self) -> bool {
+                self.0.iter().all(|v| v.is_sign_positive())
+            }
+            fn _is_negative(self) -> bool {
+                self.0.iter().all(|v| v.is_sign_negative())
+            }
+        }
+        impl BoundedSigned for $V {
+            const _MIN_POSITIVE: Self = $V::splat($T::MIN_POSITIVE);
+        }
+
+        /// Constants are splat across the lanes, sizes describe a single lane
+        impl Float for $V {
+            const _NAN: Self = $V::splat($T::NAN);
+            const _INFINITY: Self = $V::splat($T::INFINITY);
+            const _NEG_INFINITY: Self = $V::splat($T::NEG_INFINITY);
+            const _EPSILON: Self = $V::splat($T::EPSILON);
+            const _SIG_BITS: u32 = $T::_SIG_BITS;
+            const _EXP_BITS: u32 = $T::_EXP_BITS;
+            const _SIG_MASK: Self::BitsRepr = $Bits::splat($T::_SIG_MASK);
+            const _EXP_MASK: Self::BitsRepr = $Bits::splat($T::_EXP_MASK);
+            const _DIGITS: u32 = $T::DIGITS;
+            const _MANTISSA_DIGITS: u32 = $T::MANTISSA_DIGITS;
+            const _MIN_EXP: i32 = $T::MIN_EXP;
+            const _MAX_EXP: i32 = $T::MAX_EXP;
+            const _MIN_10_EXP: i32 = $T::MIN_10_EXP;
+            const _MAX_10_EXP: i32 = $T::MAX_10_EXP;
+            const _PI: Self = $V::splat($T::_PI);
+            const _E: Self = $V::splat($T::_E);
+            const _TAU: Self = $V::splat($T::_TAU);
+            fn _floor(self) -> Self {
+                self.map($T::floor)
+            }
+            fn _ceil(self) -> Self {
+                self.map($T::ceil)
+            }
+            fn _round(self) -> Self {
+                self.map($T::round)
+            }
+            fn _trunc(self) -> Self {
+                self.map($T::trunc)
+            }
+            fn _fract(self) -> Self {
+                self.map($T::fract)
+            }
+            /// A vector is in a class when every lane is, the `Mask` returning `is_nan`, `is_finite`, ...
+            /// classify each lane
+            fn _is_nan(self) -> bool {
+                self.0.iter().all(|v| v.is_nan())
+            }
+            fn _is_infinite(self) -> bool {
+                self.0.iter().all(|v| v.is_infinite())
+            }
+            fn _is_finite(self) -> bool {
+                self.0.iter().all(|v| v.is_finite())
+            }
+            fn _is_subnormal(self) -> bool {
+                self.0.iter().all(|v| v.is_subnormal())
+            }
+            fn _is_normal(self) -> bool {
+                self.0.iter().all(|v| v.is_normal())
+            }
+            /// The first class any lane is in, by precedence NaN, infinite, subnormal, zero, normal
+            fn _classify(self) -> FpCategory {
+                let any = |class| self.0.iter().any(|v| v.classify() == class);
+                [FpCategory::Nan, FpCategory::Infinite, FpCategory::Subnormal, FpCategory::Zero]
+                    .into_iter()
+                    .find(|&class| any(class))
+                    .unwrap_or(FpCategory::Normal)
+            }
+            fn _next_up(self) -> Self {
+                self.map(<$T as Float>::_next_up)
+            }
+            fn _next_down(self) -> Self {
+                self.map(<$T as Float>::_next_down)
+            }
+            /// Largest distance over the lanes
+            fn _ulp_distance(self, other: Self) -> u64 {
+                self.0.iter().zip(other.0).map(|(a, b)| a._ulp_distance(b)).max().unwrap()
+            }
+            /// Every lane, with the tolerances of that lane
+            fn _approx_eq(self, other: Self, rel: Self, abs: Self, ulps: u32) -> bool {
+                (0..$N).all(|i| self.0[i]._approx_eq(other.0[i], rel.0[i], abs.0[i], ulps))
+            }
+        }
+        impl Denormal for $V {
+            fn _flush_denormal(self) -> Self {
+                self.map(<$T as Denormal>::_flush_denormal)
+            }
+        }
+        impl ExpBasic for $V {
+            type Output = $V;
+            fn _sq(self) -> <Self as ExpBasic>::Output {
+                self * self
+            }
+            fn _sqrt(self) -> <Self as ExpBasic>::Output {
+                self.sqrt_lanes()
+            }
+        }
+        impl ExpPowDynamic<Self> for $V {
+            fn _pow(self, rhs: Self) -> <Self as ExpBasic>::Output {
+                self.zip(rhs, $T::powf)
+            }
+        }
+        impl ExpRootDynamic<Self> for $V {
+            fn _root(self, n: Self) -> <Self as ExpBasic>::Output {
+                self.zip(n, $T::_root)
+            }
+        }
+        impl ExpFloat for $V {
+            fn _exp(self) -> <Self as ExpBasic>::Output {
+                self.map($T::exp)
+            }
+            fn _exp2(self) -> <Self as ExpBasic>::Output {
+                self.map($T::exp2)
+            }
+            fn _exp_m1(self) -> <Self as ExpBasic>::Output {
+                self.map($T::exp_m1)
+            }
+            fn _log(self, base: Self) -> <Self as ExpBasic>::Output {
+                self.zip(base, $T::log)
+            }
+            fn _log2(self) -> <Self as ExpBasic>::Output {
+                self.map($T::log2)
+            }
+            fn _log10(self) -> <Self as ExpBasic>::Output {
+                self.map($T::log10)
+            }
+            fn _ln(self) -> <Self as ExpBasic>::Output {
+                self.map($T::ln)
+            }
+            fn _ln_1p(self) -> <Self as ExpBasic>::Output {
+                self.map($T::ln_1p)
+            }
+        }
+        impl Trig for $V {
+            fn _sin(self) -> Self {
+                self.map($T::sin)
+            }
+            fn _cos(self) -> Self {
+                self.map($T::cos)
+            }
+            fn _tan(self) -> Self {
+                self.map($T::tan)
+            }
+            fn _asin(self) -> Self {
+                self.map($T::asin)
+            }
+            fn _acos(self) -> Self {
+                self.map($T::acos)
+            }
+            fn _atan(self) -> Self {
+                self.map($T::atan)
+            }
+            fn _atan2(self, other: Self) -> Self {
+                self.zip(other, $T::atan2)
+            }
+            fn _sinh(self) -> Self {
+                self.map($T::sinh)
+            }
+            fn _cosh(self) -> Self {
+                self.map($T::cosh)
+            }
+            fn _tanh(self) -> Self {
+                self.map($T::tanh)
+            }
+            fn _asinh(self) -> Self {
+                self.map($T::asinh)
+            }
+            fn _acosh(self) -> Self {
+                self.map($T::acosh)
+            }
+            fn _atanh(self) -> Self {
+                self.map($T::atanh)
+            }
+            fn _hypot(self, other: Self) -> Self {
+                self.zip(other, $T::hypot)
+            }
+            fn _to_degrees(self) -> Self {
+                self.map($T::to_degrees)
+            }
+            fn _to_radians(self) -> Self {
+                self.map($T::to_radians)
+            }
+        }
+
+        /// Only a vector holding the same value in every lane converts to a scalar
+        impl ToPrimitive for $V {
+            impl_simd_float_to_primitive! {
+                fn to_isize -> isize;
+                fn to_i8 -> i8;
+                fn to_i16 -> i16;
+                fn to_i32 -> i32;
+                fn to_i64 -> i64;
+                fn to_i128 -> i128;
+                fn to_usize -> usize;
+                fn to_u8 -> u8;
+                fn to_u16 -> u16;
+                fn to_u32 -> u32;
+                fn to_u64 -> u64;
+                fn to_u128 -> u128;
+                fn to_f32 -> f32;
+                fn to_f64 -> f64;
+            }
+        }
+
+        /// Converts like the lane type, then splats
+        impl FromPrimitive for $V {
+            impl_simd_float_from_primitive! { $T:
+                fn from_isize(isize);
+                fn from_i8(i8);
+                fn from_i16(i16);
+                fn from_i32(i32);
+                fn from_i64(i64);
+                fn from_i128(i128);
+                fn from_usize(usize);
+                fn from_u8(u8);
+                fn from_u16(u16);
+                fn from_u32(u32);
+                fn from_u64(u64);
+                fn from_u128(u128);
+                fn from_f32(f32);
+                fn from_f64(f64);
+            }
+        }
+    };
+}
+
+impl_simd_float!(f32x4, f32, 4, 16, u32x4, target_arch = "x86_64", __m128,
+    _mm_add_ps, _mm_sub_ps, _mm_mul_ps, _mm_div_ps, _mm_sqrt_ps);
+impl_simd_float!(f64x2, f64, 2, 16, u64x2, target_arch = "x86_64", __m128d,
+    _mm_add_pd, _mm_sub_pd, _mm_mul_pd, _mm_div_pd, _mm_sqrt_pd);
+impl_simd_float!(f32x8, f32, 8, 32, u32x8, all(target_arch = "x86_64", target_feature = "avx"), __m256,
+    _mm256_add_ps, _mm256_sub_ps, _mm256_mul_ps, _mm256_div_ps, _mm256_sqrt_ps);
+impl_simd_float!(f64x4, f64, 4, 32, u64x4, all(target_arch = "x86_64", target_feature = "avx"), __m256d,
+    _mm256_add_pd, _mm256_sub_pd, _mm256_mul_pd, _mm256_div_pd, _mm256_sqrt_pd);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Scalar dsp code that runs unchanged on the vectors
+    fn softclip<T: Float>(x: T, drive: T) -> T {
+        let y = (x * drive)._tanh() / drive._tanh();
+        (y._sq() + T::_ONE)._sqrt() * y._signum() - T::_ONE * y._signum() + y / T::_TAU
+    }
+
+    #[test]
+    fn test_generic_kernel_matches_scalar() {
+        let xs = [-3.5f32, -0.25, 0.0, 0.5, 1.0, 2.0, 7.25, 100.0];
+        let v = softclip(f32x8::from_array(xs), f32x8::splat(1.5));
+        for (i, &x) in xs.iter().enumerate() {
+            assert_eq!(v[i].to_bits(), softclip(x, 1.5).to_bits());
+        }
+        let xs = [0.1f64, -2.0, 3.0, 1e300];
+        let v = f64x4::from_array(xs) / f64x4::splat(3.0) + 1.0;
+        assert_eq!(v.to_array(), xs.map(|x| x / 3.0 + 1.0));
+        let v = f32x4::from_array([4.0, 9.0, 2.0, -1.0])._sqrt();
+        assert_eq!(&v.as_array()[..3], &[2.0, 3.0, 2f32.sqrt()]);
+        assert_eq!(v.is_nan(), Mask::from_array([false, false, false, true]));
+        assert_eq!(f64x2::from_array([1.0, 2.0]).reduce_sum(), 3.0);
+    }
+
+    #[test]
+    fn test_lanewise_ordering_and_masks() {
+        let a = f32x4::from_array([1.0, 5.0, f32::NAN, -2.0]);
+        let b = f32x4::splat(2.0);
+        let lt = a.simd_lt(b);
+        assert_eq!(lt.to_array(), [true, false, false, true]);
+        assert_eq!((!lt).bitmask(), 0b0110);
+        assert!((lt | !lt).all() && (lt & !lt).none());
+        assert_eq!(f32x4::select(lt, a, b).to_array()[..2], [1.0, 2.0]);
+        assert_eq!(a._min(b).to_array(), [1.0, 2.0, 2.0, -2.0]);
+        assert_eq!(a._max(b).to_array(), [2.0, 5.0, 2.0, 2.0]);
+        assert_eq!(a.partial_cmp(&b), None);
+        assert!(f32x4::splat(1.0) < f32x4::from_array([1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(f64x2::splat(3.0)._clamp(f64x2::from_array([0.0, 4.0]), f64x2::splat(5.0)), f64x2::from_array([3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_repr_and_casts() {
+        let v = f32x4::from_array([1.0, -2.0, 0.5, -0.0]);
+        assert_eq!(v._to_bits() & <f32x4 as Signed>::_SIGN_MASK, u32x4::from_array([0, 1 << 31, 0, 1 << 31]));
+        assert_eq!(f32x4::_from_bits(v._to_bits()), v);
+        assert_eq!(f32x4::_from_le_bytes(v._to_le_bytes()), v);
+        assert_eq!(&v._to_be_bytes()[..4], &1f32.to_be_bytes());
+        assert_eq!(f64x4::_from_be_bytes(f64x4::_PI._to_be_bytes()), f64x4::_PI);
+        assert_eq!(f64x4::from_i32(3), Some(f64x4::splat(3.0)));
+        assert_eq!(f32x8::splat(2.5).to_f64(), Some(2.5));
+        assert_eq!(v.to_f32(), None);
+        assert!(f32x4::splat(-1.0)._is_negative() && !v._is_negative());
+    }
+
+    #[test]
+    fn test_lane_queries() {
+        let v = f32x4::from_array([f32::NAN, f32::NEG_INFINITY, 1e-40, -1.0]);
+        assert!(!v._is_nan() && !v._is_finite());
+        assert_eq!(v.is_nan().to_array(), [true, false, false, false]);
+        assert_eq!(v.is_infinite().bitmask(), 0b0010);
+        assert_eq!(v.is_finite().bitmask(), 0b1100);
+        assert_eq!(v.is_subnormal().bitmask(), 0b0100);
+        assert_eq!(v.is_normal().bitmask(), 0b1000);
+        assert_eq!(v.is_sign_negative().bitmask(), 0b1010);
+        assert_eq!(v.is_sign_positive(), !v.is_sign_negative());
+        assert_eq!(f32x4::from_array([f32::NAN, 1.0, 0.0, 1e-40])._classify(), FpCategory::Nan);
+        assert_eq!(f32x4::from_array([1.0, 0.0, 1e-40, 2.0])._classify(), FpCategory::Subnormal);
+        assert_eq!(f64x2::from_array([1.0, 0.0])._classify(), FpCategory::Zero);
+        assert_eq!(f64x2::from_array([1.0, -3.0])._classify(), FpCategory::Normal);
+        assert_eq!(f32x4::splat(f32::INFINITY)._classify(), FpCategory::Infinite);
+        let (a, b) = (f64x2::from_array([1.0, 2.0]), f64x2::from_array([1.0 + 1e-12, 2.5]));
+        assert!(!a._approx_eq(b, f64x2::splat(1e-9), f64x2::_ZERO, 0));
+        assert_eq!(a.approx_eq_lanes(b, f64x2::splat(1e-9), f64x2::_ZERO, 0).to_array(), [true, false]);
+    }
+}
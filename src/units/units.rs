@@ -1,5 +1,6 @@
 ﻿use std::ops::{Add, Div, Mul, Rem, Sub};
 use std::fmt::Debug;
+use std::marker::PhantomData;
 use super::*;
 
 /// Elementary operations
@@ -10,7 +11,7 @@ pub trait UnitOps: Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
 /// 2. Support elementary arithmetic (UnitOps)
 /// 3. Part of a set of values (PartialEq)
 /// 4. Have a multiplicative and additive identity (Zero and One)
-pub trait Unit: PhysicalRepr + Zero + One + UnitOps + Inv + Symbolic {}
+pub trait Unit: PhysicalRepr + Zero + One + UnitOps + Symbolic {}
 
 /// Defines the additive identity
 pub trait Zero: UnitOps {
@@ -32,18 +33,17 @@ pub trait One: UnitOps {
     fn _set_one(&mut self) {
         *self = Self::_ONE;
     }
-    fn _recip(self) -> Self {
-        Self::_ONE / self
-    }
 }
 
 pub trait PhysicalRepr: Copy + Sized + Debug {
     /// bit-width of the datatype
     const _BITS: u32;
     const _BYTES: usize;
+    /// Present when the unit is `PlainBytes`, so slices can be (de)serialized with a plain copy
+    const _PLAIN_BYTES: Option<PlainBytesProof<Self>> = None;
     /// A type that can represent the bits / base-2 internal representation of the unit
     type BitsRepr: Unit + Bitwise + Bounded + Eq;
-    type BytesRepr;
+    type BytesRepr: AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
     /// Casting from raw bits and bytes
     fn _from_bits(v: Self::BitsRepr) -> Self;
     fn _to_bits(self) -> Self::BitsRepr;
@@ -55,6 +55,29 @@ pub trait PhysicalRepr: Copy + Sized + Debug {
     fn _to_ne_bytes(self) -> Self::BytesRepr;
 }
 
+/// Units stored in memory exactly as `_to_ne_bytes`
+/// # Safety
+/// `size_of::<Self>() == _BYTES` (no padding), the bytes in memory are `_to_ne_bytes` and every
+/// pattern of `_BYTES` bytes is a valid value
+pub unsafe trait PlainBytes: PhysicalRepr {}
+
+/// Evidence that `T` is `PlainBytes`, which lets generic code over `PhysicalRepr` take the plain copy
+/// path through `_PLAIN_BYTES`
+#[derive(Debug)]
+pub struct PlainBytesProof<T>(PhantomData<fn() -> T>);
+
+impl<T: PlainBytes> PlainBytesProof<T> {
+    pub const PROOF: Self = PlainBytesProof(PhantomData);
+}
+
+impl<T> PlainBytesProof<T> {
+    /// # Safety
+    /// `T` upholds the contract of `PlainBytes`
+    pub const unsafe fn new_unchecked() -> Self {
+        PlainBytesProof(PhantomData)
+    }
+}
+
 /// Describes the property of unit to be simplified/alternatively viewed w.r.t another unit
 pub trait Symbolic: UnitOps {
     type Base: Unit;
@@ -64,6 +87,12 @@ pub trait Symbolic: UnitOps {
 }
 
 /// Describes the property of a unit having an inverse representation (guarantees self.inv().inv() == self)
-pub trait Inv: UnitOps {
+/// - exact for exact units (e.g. `Rational`), up to rounding for floating point units
+/// - not implemented for integer and fixed-point units, where 1 / self truncates
+pub trait Inv: One {
     fn _inv(self) -> Self;
+    /// 1 / self
+    fn _recip(self) -> Self {
+        Self::_ONE / self
+    }
 }